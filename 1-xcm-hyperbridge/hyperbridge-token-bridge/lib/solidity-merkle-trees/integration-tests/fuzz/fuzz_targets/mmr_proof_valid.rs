@@ -0,0 +1,8 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use solidity_merkle_trees_fuzz::fuzz_that_calculate_root_matches_ckb;
+
+fuzz_target!(|data: &[u8]| {
+    fuzz_that_calculate_root_matches_ckb(data);
+});
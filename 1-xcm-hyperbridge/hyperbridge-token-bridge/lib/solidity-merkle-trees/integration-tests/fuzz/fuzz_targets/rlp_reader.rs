@@ -0,0 +1,8 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use solidity_merkle_trees_fuzz::fuzz_that_rlp_reader_matches_rlp;
+
+fuzz_target!(|data: &[u8]| {
+    fuzz_that_rlp_reader_matches_rlp(data);
+});
@@ -1,10 +1,15 @@
 #![allow(dead_code, unused_imports)]
 
-use patricia_merkle_trie::{MemoryDB, StorageProof};
-use solidity_merkle_trees_test::Token;
+use solidity_merkle_trees_test::{
+    mmr_proof::{calculate_root_args, generate_mmr_proof, native_calculate_root},
+    proof_mutation::{MmrProof, Mutation, MutationReport, TrieKind, TrieProof},
+    rlp_item::RlpItem,
+    test_env::{self, Contract, TestEnv},
+    Token,
+};
 use sp_core::KeccakHasher;
-use sp_trie::LayoutV0;
-use std::{collections::HashSet, env, path::PathBuf};
+use sp_trie::{LayoutV0, LayoutV1, MemoryDB, StorageProof};
+use std::collections::{BTreeSet, HashSet};
 use trie_db::{
     DBValue, Hasher, Recorder, Trie, TrieDBBuilder, TrieDBMutBuilder, TrieLayout, TrieMut,
 };
@@ -112,6 +117,59 @@ fn test_generate_proof<L: TrieLayout>(
     (root, proof, items)
 }
 
+/// Stretches every value starting with an odd byte past the inline threshold of `LayoutV1`, so
+/// its proofs hold hashed values.
+fn with_hashed_values(data: Vec<(Vec<u8>, Vec<u8>)>) -> Vec<(Vec<u8>, Vec<u8>)> {
+    data.into_iter()
+        .map(|(key, value)| match value[0] % 2 {
+            0 => (key, value),
+            _ => (key, value.repeat(20)),
+        })
+        .collect()
+}
+
+/// Proofs of the same keys for a trie with `LayoutV0`, and one with `LayoutV1` and hashed values.
+/// Layouts with an empty proof are left out.
+fn generate_proofs(
+    data: Vec<(Vec<u8>, Vec<u8>)>,
+    keys: Vec<Vec<u8>>,
+) -> Vec<([u8; 32], Vec<Vec<u8>>, Vec<(Vec<u8>, Option<DBValue>)>)> {
+    let (root, proof, items) =
+        test_generate_proof::<LayoutV0<KeccakHasher>>(data.clone(), keys.clone());
+    let (root_v1, proof_v1, items_v1) =
+        test_generate_proof::<LayoutV1<KeccakHasher>>(with_hashed_values(data), keys);
+
+    [(root.into(), proof, items), (root_v1.into(), proof_v1, items_v1)]
+        .into_iter()
+        .filter(|(_, proof, _)| !proof.is_empty())
+        .collect()
+}
+
+/// Looks up every key through `VerifyKeys`, mapping empty values to `None`.
+async fn verify_keys(
+    contract: &mut Contract<'_>,
+    root: [u8; 32],
+    proof: &[Vec<u8>],
+    keys: Vec<Vec<u8>>,
+) -> Vec<Option<Vec<u8>>> {
+    let mut values = vec![];
+    for key in keys {
+        let result = contract
+            .call::<_, Vec<(Vec<u8>, Vec<u8>)>>(
+                "VerifyKeys",
+                (
+                    Token::FixedBytes(root.to_vec()),
+                    Token::Array(proof.iter().cloned().map(Token::Bytes).collect()),
+                    Token::Array(vec![Token::Bytes(key)]),
+                ),
+            )
+            .await
+            .unwrap();
+        values.push(if result[0].1.len() == 0 { None } else { Some(result[0].1.clone()) });
+    }
+    values
+}
+
 pub fn fuzz_that_verify_rejects_invalid_proofs(input: &[u8]) {
     if input.len() < 4 {
         return;
@@ -135,43 +193,78 @@ pub fn fuzz_that_verify_rejects_invalid_proofs(input: &[u8]) {
         return;
     }
 
-    let (root, proof, mut items) = test_generate_proof::<LayoutV0<KeccakHasher>>(data, keys);
-
-    if proof.len() == 0 {
+    let proofs = generate_proofs(data, keys);
+    if proofs.is_empty() {
         return;
     }
 
-    // Make all items incorrect.
-    for i in 0..items.len() {
-        match &mut items[i] {
-            (_, Some(value)) if random_int % 2 == 0 => value.push(0),
-            (_, value) if value.is_some() => *value = None,
-            (_, value) => *value = Some(DBValue::new()),
+    test_env::block_on(async move {
+        let mut env = TestEnv::shared().await;
+        let mut contract = env.contract("MerklePatriciaTest").await;
+        for (root, proof, mut items) in proofs {
+            // Make all items incorrect.
+            for i in 0..items.len() {
+                match &mut items[i] {
+                    (_, Some(value)) if random_int % 2 == 0 => value.push(0),
+                    (_, value) if value.is_some() => *value = None,
+                    (_, value) => *value = Some(DBValue::new()),
+                }
+            }
+
+            let keys = items.iter().map(|(key, _)| key.clone()).collect();
+            let values = verify_keys(&mut contract, root, &proof, keys).await;
+            for ((_, value), result) in items.into_iter().zip(values) {
+                assert_ne!(result, value);
+            }
         }
+    });
+}
+
+/// Mutates the proofs themselves, rather than the values they are checked against. Fails if
+/// `VerifyKeys` returns a value that a mutated proof doesn't show, and prints a reproducer for
+/// every mutation it accepts.
+pub fn fuzz_that_verify_rejects_mutated_proofs(input: &[u8]) {
+    let mut data = fuzz_to_data(input);
+    // Split data into 3 parts, as in `fuzz_that_verify_accepts_valid_proofs`
+    let mut keys = data[(data.len() / 3)..].iter().map(|(key, _)| key.clone()).collect::<Vec<_>>();
+    data.truncate(data.len() * 2 / 3);
+
+    let data = data_sorted_unique(data);
+    keys.sort();
+    keys.dedup();
+
+    let proofs = generate_proofs(data, keys);
+    if proofs.is_empty() {
+        return;
     }
 
-    let runtime = tokio::runtime::Runtime::new().unwrap();
-    let base_dir = env::current_dir().unwrap().parent().unwrap().display().to_string();
-    let mut runner = forge_testsuite::Runner::new(PathBuf::from(&base_dir));
-
-    runtime.block_on(async move {
-        let mut contract = runner.deploy("MerklePatriciaTest").await;
-        for (key, value) in items {
-            let result = contract
-                .call::<_, Vec<(Vec<u8>, Vec<u8>)>>(
-                    "VerifyKeys",
-                    (
-                        Token::FixedBytes(root.as_ref().to_vec()),
-                        Token::Array(proof.clone().into_iter().map(Token::Bytes).collect()),
-                        Token::Array(vec![Token::Bytes(key.to_vec())]),
-                    ),
-                )
-                .await
-                .unwrap();
-            let result = if result[0].1.len() == 0 { None } else { Some(result[0].1.clone()) };
-
-            assert_ne!(result, value);
+    test_env::block_on(async move {
+        let mut env = TestEnv::shared().await;
+        let mut contract = env.contract("MerklePatriciaTest").await;
+        let mut report = MutationReport::new("trie_proof_mutated");
+        for (root, proof, items) in proofs {
+            let keys = items.into_iter().map(|(key, _)| key).collect();
+            let proof = TrieProof::new(TrieKind::Substrate, root.into(), proof, keys);
+            for (mutation, variant) in proof.variants() {
+                let nodes = variant.nodes.iter().cloned().map(Token::Bytes).collect();
+                let keys = variant.keys.iter().cloned().map(Token::Bytes).collect();
+                let result = contract
+                    .call::<_, Vec<(Vec<u8>, Vec<u8>)>>(
+                        "VerifyKeys",
+                        (
+                            Token::FixedBytes(variant.root.as_bytes().to_vec()),
+                            Token::Array(nodes),
+                            Token::Array(keys),
+                        ),
+                    )
+                    .await
+                    .ok()
+                    .map(|result| result.into_iter().map(|(_, value)| value).collect::<Vec<_>>());
+                let outcome = variant.judge(result.as_deref());
+                report.record("VerifyKeys", &mutation, variant.to_json(), outcome);
+            }
         }
+        report.finish();
     });
 }
 
@@ -188,32 +281,105 @@ pub fn fuzz_that_verify_accepts_valid_proofs(input: &[u8]) {
     keys.sort();
     keys.dedup();
 
-    let (root, proof, items) = test_generate_proof::<LayoutV0<KeccakHasher>>(data, keys);
-
-    if proof.len() == 0 {
+    let proofs = generate_proofs(data, keys);
+    if proofs.is_empty() {
         return;
     }
 
-    let base_dir = env::current_dir().unwrap().parent().unwrap().display().to_string();
-    let mut runner = forge_testsuite::Runner::new(PathBuf::from(&base_dir));
-    let runtime = tokio::runtime::Runtime::new().unwrap();
-
-    runtime.block_on(async move {
-        let mut contract = runner.deploy("MerklePatriciaTest").await;
-        for (key, value) in items {
-            let result = contract
-                .call::<_, Vec<(Vec<u8>, Vec<u8>)>>(
-                    "VerifyKeys",
-                    (
-                        Token::FixedBytes(root.as_ref().to_vec()),
-                        Token::Array(proof.clone().into_iter().map(Token::Bytes).collect()),
-                        Token::Array(vec![Token::Bytes(key.to_vec())]),
-                    ),
-                )
-                .await
-                .unwrap();
-            let result = if result[0].1.len() == 0 { None } else { Some(result[0].1.clone()) };
-            assert_eq!(result, value)
+    test_env::block_on(async move {
+        let mut env = TestEnv::shared().await;
+        let mut contract = env.contract("MerklePatriciaTest").await;
+        for (root, proof, items) in proofs {
+            let keys = items.iter().map(|(key, _)| key.clone()).collect();
+            let values = verify_keys(&mut contract, root, &proof, keys).await;
+            for ((_, value), result) in items.into_iter().zip(values) {
+                assert_eq!(result, value)
+            }
         }
     });
 }
+
+/// Decodes `input` with `RLPReader`, which must read exactly what [`RlpItem::decode`] does
+/// wherever it accepts the input. `RLPReader` doesn't check lengths, so what it makes of anything
+/// else is undefined.
+pub fn fuzz_that_rlp_reader_matches_rlp(input: &[u8]) {
+    let Ok(expected) = RlpItem::decode(input) else { return };
+    // canonical encodings are unique
+    assert_eq!(rlp::encode(&expected).to_vec(), input);
+
+    test_env::block_on(async move {
+        let mut env = TestEnv::shared().await;
+        let mut contract = env.contract("MerklePatriciaTest").await;
+        let result = contract
+            .call::<_, Vec<u8>>("decodeRlp", (Token::Bytes(input.to_vec())))
+            .await
+            .ok();
+        assert_eq!(result, Some(expected.flatten()));
+    });
+}
+
+/// Largest mmr the MMR targets build, so every input stays quick to run
+const MAX_MMR_LEAVES: u32 = 1024;
+
+/// An mmr proof derived from the fuzz input:
+/// enc = (leaf count: 2 bytes, corrupted node: 1 byte, corrupted bit: 1 byte, proven leaves: 2
+/// bytes each)
+///
+/// Returns the leaf count, the sorted and deduplicated proven leaves and the corruption bytes.
+fn fuzz_to_mmr(input: &[u8]) -> Option<(u32, Vec<u32>, [u8; 2])> {
+    let count = u16::from_le_bytes(input.get(0..2)?.try_into().ok()?) as u32 % MAX_MMR_LEAVES + 1;
+    let corruption = input.get(2..4)?.try_into().ok()?;
+    let leaves = input[4..]
+        .chunks_exact(2)
+        .map(|chunk| u16::from_le_bytes([chunk[0], chunk[1]]) as u32 % count)
+        .collect::<BTreeSet<_>>();
+    if leaves.is_empty() {
+        return None;
+    }
+
+    Some((count, leaves.into_iter().collect(), corruption))
+}
+
+/// Calls `CalculateRoot`, `None` if it reverts.
+async fn calculate_root(contract: &mut Contract<'_>, proof: &MmrProof) -> Option<[u8; 32]> {
+    let args = calculate_root_args(proof.leaves.clone(), proof.items.clone(), proof.leaf_count);
+    contract.call::<_, [u8; 32]>("CalculateRoot", args).await.ok()
+}
+
+/// Proves a random set of leaves of a random mmr with the ckb implementation. `CalculateRoot`
+/// must calculate the same root as ckb.
+pub fn fuzz_that_calculate_root_matches_ckb(input: &[u8]) {
+    let Some((count, elems, _)) = fuzz_to_mmr(input) else { return };
+    let (root, items, leaves) = generate_mmr_proof(count, elems);
+    let proof = MmrProof::new(root, count as u64, leaves, items);
+
+    test_env::block_on(async move {
+        let mut env = TestEnv::shared().await;
+        let mut contract = env.contract("MerkleMountainRangeTest").await;
+        assert_eq!(calculate_root(&mut contract, &proof).await, Some(root));
+    });
+}
+
+/// Flips one bit of a proof item or proven leaf, as picked by the input. Neither
+/// `CalculateRoot` nor ckb may calculate the original root from the corrupted proof.
+pub fn fuzz_that_calculate_root_rejects_corrupted_proofs(input: &[u8]) {
+    let Some((count, elems, [target, bit])) = fuzz_to_mmr(input) else { return };
+    let (root, items, leaves) = generate_mmr_proof(count, elems);
+    let proof = MmrProof::new(root, count as u64, leaves, items);
+
+    // targets below the number of proof items pick an item, the others a leaf
+    let target = target as usize % (proof.items.len() + proof.leaves.len());
+    let bit = bit as usize;
+    let mutation = match target.checked_sub(proof.items.len()) {
+        None => Mutation::FlipBit { node: target, bit },
+        Some(leaf) => Mutation::FlipLeafBit { leaf, bit },
+    };
+    let corrupted = proof.apply(&mutation).expect("bit and index are in range");
+    assert_ne!(native_calculate_root(&corrupted), Some(root), "{mutation}");
+
+    test_env::block_on(async move {
+        let mut env = TestEnv::shared().await;
+        let mut contract = env.contract("MerkleMountainRangeTest").await;
+        assert_ne!(calculate_root(&mut contract, &corrupted).await, Some(root), "{mutation}");
+    });
+}
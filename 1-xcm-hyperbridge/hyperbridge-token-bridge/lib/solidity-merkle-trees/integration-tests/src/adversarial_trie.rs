@@ -0,0 +1,419 @@
+//! Tries shaped to make `VerifySubstrateProof` and `VerifyEthereumProof` as expensive as
+//! possible, and limits on proofs that a relayer can check before submitting them.
+//!
+//! The traversal loops of both verifiers are unbounded, and every step of them costs gas that
+//! grows with the proof:
+//!
+//! - `TrieDB.get` scans the proof nodes linearly, so each node visited costs a comparison per node
+//!   before it in the proof.
+//! - decoding a node copies its children, values and partial key into fresh memory, which is never
+//!   freed, so memory expansion grows with every byte decoded.
+//! - partial keys are compared nibble by nibble after copying the rest of the key.
+//!
+//! [`ProofLimits::check`] walks a proof the way the contract does and bounds each of those, so a
+//! relayer can refuse to submit a proof whose verification could cost more than
+//! [`ProofLimits::max_gas`]. The `adversarial_trie` gas test checks that bound against the
+//! [`Shape`]s below.
+//!
+//! ```ignore
+//! let (entries, keys) = Shape::Deep { depth: 64, keys: 8 }.generate();
+//! let traversal = ProofLimits::default().check(TrieKind::Substrate, root, &proof, &keys)?;
+//! ```
+
+use crate::{
+    keccak256,
+    native_verifier::{ethereum_traversal, substrate_traversal, Traversal, VerifyError},
+    proof_mutation::TrieKind,
+};
+use primitive_types::H256;
+use sp_core::KeccakHasher;
+use std::fmt;
+
+/// A trie built to stress one part of the verifiers
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Shape {
+    /// Keys sharing their first `prefix_len` bytes, so the path to each of them starts with a
+    /// long partial key or extension.
+    SharedPrefix { prefix_len: usize, keys: usize },
+    /// A key whose path branches at each of its first `depth` nibbles, along with the key leaving
+    /// each of those branches. The deepest `keys` keys are proven.
+    Deep { depth: usize, keys: usize },
+    /// Values of `value_len` bytes, which ethereum tries and `LayoutV0` store inside their leaves.
+    HugeValues { value_len: usize, keys: usize },
+    /// Every path of `levels` nibbles, so the first `levels` levels are full 16 child branches.
+    Wide { levels: usize, keys: usize },
+}
+
+impl Shape {
+    /// The entries of the trie and the keys to prove out of it
+    pub fn generate(&self) -> (Vec<(Vec<u8>, Vec<u8>)>, Vec<Vec<u8>>) {
+        let keys = match *self {
+            Shape::SharedPrefix { prefix_len, keys } => {
+                let prefix = vec![0xaa; prefix_len];
+                (0..keys)
+                    .map(|i| [&prefix[..], &keccak256(&i.to_le_bytes())[..8]].concat())
+                    .collect::<Vec<_>>()
+            },
+            Shape::Deep { depth, .. } => {
+                let target = keccak256(b"target").repeat((depth / 64) + 1);
+                let target = target[..(depth / 2 + 1).max(32)].to_vec();
+                // the key leaving the branch at nibble `i`, deepest first
+                let siblings = (0..depth).rev().map(|i| {
+                    let mut key = target.clone();
+                    key[i / 2] ^= if i % 2 == 0 { 0x10 } else { 0x01 };
+                    key
+                });
+                [target.clone()].into_iter().chain(siblings).collect()
+            },
+            Shape::HugeValues { keys, .. } =>
+                (0..keys.max(16)).map(|i| keccak256(&i.to_le_bytes()).to_vec()).collect(),
+            Shape::Wide { levels, .. } => (0..16usize.pow(levels as u32))
+                .map(|i| {
+                    let mut key = keccak256(&i.to_le_bytes()).to_vec();
+                    for level in 0..levels {
+                        let nibble = ((i >> (4 * (levels - 1 - level))) & 0xf) as u8;
+                        key[level / 2] = if level % 2 == 0 {
+                            (key[level / 2] & 0x0f) | nibble << 4
+                        } else {
+                            (key[level / 2] & 0xf0) | nibble
+                        };
+                    }
+                    key
+                })
+                .collect(),
+        };
+
+        let value = |key: &[u8]| match *self {
+            Shape::HugeValues { value_len, .. } =>
+                keccak256(key).repeat(value_len / 32 + 1)[..value_len].to_vec(),
+            _ => keccak256(key).to_vec(),
+        };
+        let entries = keys.iter().map(|key| (key.clone(), value(key))).collect();
+
+        let proven = match *self {
+            Shape::SharedPrefix { keys: count, .. } |
+            Shape::Deep { keys: count, .. } |
+            Shape::HugeValues { keys: count, .. } => keys.into_iter().take(count).collect(),
+            Shape::Wide { keys: count, .. } => {
+                let step = (keys.len() / count.max(1)).max(1);
+                keys.into_iter().step_by(step).take(count).collect()
+            },
+        };
+
+        (entries, proven)
+    }
+
+    /// A name for reports, e.g. `deep_64`
+    pub fn name(&self) -> String {
+        match self {
+            Shape::SharedPrefix { prefix_len, .. } => format!("shared_prefix_{prefix_len}"),
+            Shape::Deep { depth, .. } => format!("deep_{depth}"),
+            Shape::HugeValues { value_len, .. } => format!("huge_values_{value_len}"),
+            Shape::Wide { levels, .. } => format!("wide_{levels}"),
+        }
+    }
+}
+
+/// Limits a relayer enforces on a proof before submitting it to either verifier.
+///
+/// The defaults leave room for proofs of up to 16 hashed keys out of tries with tens of millions
+/// of entries, whose branches are at most 16 children of 32 byte hashes, and keep verifying any
+/// proof within them under [`ProofLimits::max_gas`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ProofLimits {
+    /// Number of keys proven at once
+    pub max_keys: usize,
+    /// Length of a key in bytes
+    pub max_key_len: usize,
+    /// Length of a proof node in bytes, which bounds what decoding a node copies
+    pub max_node_len: usize,
+    /// Length of all proof nodes together, which bounds hashing them and the calldata
+    pub max_proof_len: usize,
+    /// Nodes visited looking up all keys, see [`Traversal::steps`]
+    pub max_steps: usize,
+    /// Proof nodes compared against a hash looking up all keys, see [`Traversal::scanned`]
+    pub max_scanned: usize,
+    /// Gas that verifying any proof within the other limits stays under, calldata included
+    pub max_gas: u64,
+}
+
+impl Default for ProofLimits {
+    fn default() -> Self {
+        ProofLimits {
+            max_keys: 16,
+            max_key_len: 128,
+            max_node_len: 1024,
+            max_proof_len: 16 * 1024,
+            max_steps: 128,
+            max_scanned: 4096,
+            max_gas: 10_000_000,
+        }
+    }
+}
+
+/// Reasons a proof is outside [`ProofLimits`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LimitError {
+    /// More keys than [`ProofLimits::max_keys`]
+    TooManyKeys(usize),
+    /// A key longer than [`ProofLimits::max_key_len`]
+    KeyTooLong(usize),
+    /// A node longer than [`ProofLimits::max_node_len`]
+    NodeTooLong(usize),
+    /// Nodes longer in total than [`ProofLimits::max_proof_len`]
+    ProofTooLong(usize),
+    /// More steps than [`ProofLimits::max_steps`]
+    TooManySteps(usize),
+    /// More nodes scanned than [`ProofLimits::max_scanned`]
+    TooManyScanned(usize),
+    /// The contract reverts on the proof
+    Invalid(VerifyError),
+}
+
+impl fmt::Display for LimitError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LimitError::TooManyKeys(count) => write!(f, "{count} keys"),
+            LimitError::KeyTooLong(len) => write!(f, "key of {len} bytes"),
+            LimitError::NodeTooLong(len) => write!(f, "node of {len} bytes"),
+            LimitError::ProofTooLong(len) => write!(f, "proof of {len} bytes"),
+            LimitError::TooManySteps(steps) => write!(f, "{steps} steps"),
+            LimitError::TooManyScanned(scanned) => write!(f, "{scanned} nodes scanned"),
+            LimitError::Invalid(err) => write!(f, "invalid proof: {err}"),
+        }
+    }
+}
+
+impl ProofLimits {
+    /// Checks a proof of `keys` against the limits, returning the work verifying it takes.
+    pub fn check(
+        &self,
+        kind: TrieKind,
+        root: H256,
+        proof: &[Vec<u8>],
+        keys: &[Vec<u8>],
+    ) -> Result<Traversal, LimitError> {
+        if keys.len() > self.max_keys {
+            return Err(LimitError::TooManyKeys(keys.len()))
+        }
+        if let Some(len) = keys.iter().map(Vec::len).find(|len| *len > self.max_key_len) {
+            return Err(LimitError::KeyTooLong(len))
+        }
+        if let Some(len) = proof.iter().map(Vec::len).find(|len| *len > self.max_node_len) {
+            return Err(LimitError::NodeTooLong(len))
+        }
+        let proof_len = proof.iter().map(Vec::len).sum::<usize>();
+        if proof_len > self.max_proof_len {
+            return Err(LimitError::ProofTooLong(proof_len))
+        }
+
+        let traversal = match kind {
+            TrieKind::Substrate => substrate_traversal::<KeccakHasher>(root, proof, keys),
+            TrieKind::Ethereum => ethereum_traversal(root, proof, keys),
+        }
+        .map_err(LimitError::Invalid)?;
+        if traversal.steps > self.max_steps {
+            return Err(LimitError::TooManySteps(traversal.steps))
+        }
+        if traversal.scanned > self.max_scanned {
+            return Err(LimitError::TooManyScanned(traversal.scanned))
+        }
+
+        Ok(traversal)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        gas_report::{ethereum_proof, substrate_proof},
+        native_verifier::{verify_ethereum_proof, verify_substrate_proof},
+        test_env::{calldata_gas, encode_call, GasUsed, TestEnv},
+        Token,
+    };
+    use serde::Serialize;
+    use std::{env, fs, path::PathBuf};
+
+    type ProofGenerator = fn(&[(Vec<u8>, Vec<u8>)], &[Vec<u8>]) -> (Vec<u8>, Vec<Vec<u8>>);
+
+    /// A single measurement of [`test_adversarial_trie_gas`]
+    #[derive(Debug, Serialize)]
+    struct AdversarialRow {
+        verifier: &'static str,
+        shape: String,
+        keys: usize,
+        proof_nodes: usize,
+        proof_len: usize,
+        traversal: Option<Traversal>,
+        /// Why the proof is outside the limits, if it is
+        rejected: Option<String>,
+        /// `None` if the call reverted, e.g. by running out of gas
+        gas: Option<GasUsed>,
+    }
+
+    /// Shapes at, below and beyond the default limits
+    fn shapes() -> Vec<Shape> {
+        let mut shapes = vec![];
+        for prefix_len in [8, 32, 64, 120, 256] {
+            shapes.push(Shape::SharedPrefix { prefix_len, keys: 16 });
+        }
+        for depth in [8, 16, 32, 64, 128, 250] {
+            shapes.push(Shape::Deep { depth, keys: 1 });
+            shapes.push(Shape::Deep { depth, keys: 8 });
+        }
+        for value_len in [256, 900, 4096] {
+            shapes.push(Shape::HugeValues { value_len, keys: 1 });
+            shapes.push(Shape::HugeValues { value_len, keys: 16 });
+        }
+        for levels in [1, 2, 3] {
+            shapes.push(Shape::Wide { levels, keys: 16 });
+            shapes.push(Shape::Wide { levels, keys: 64 });
+        }
+        shapes
+    }
+
+    fn generate(kind: TrieKind, shape: Shape) -> (H256, Vec<Vec<u8>>, Vec<Vec<u8>>) {
+        let generate_proof: ProofGenerator = match kind {
+            TrieKind::Substrate => substrate_proof::<KeccakHasher>,
+            TrieKind::Ethereum => ethereum_proof,
+        };
+        let (entries, keys) = shape.generate();
+        let (root, proof) = generate_proof(&entries, &keys);
+        (H256::from_slice(&root), proof, keys)
+    }
+
+    #[test]
+    fn test_shapes_are_adversarial() {
+        // every sibling leaves the path of the target at its own branch
+        let (root, proof, keys) = generate(TrieKind::Substrate, Shape::Deep { depth: 40, keys: 1 });
+        let traversal = substrate_traversal::<KeccakHasher>(root, &proof, &keys).unwrap();
+        assert!(traversal.steps > 40, "{traversal:?}");
+
+        let (_, proof, _) = generate(TrieKind::Ethereum, Shape::Wide { levels: 2, keys: 16 });
+        assert!(proof.iter().any(|node| node.len() > 16 * 32));
+
+        let (_, proof, _) =
+            generate(TrieKind::Substrate, Shape::HugeValues { value_len: 2000, keys: 1 });
+        assert!(proof.iter().any(|node| node.len() > 2000));
+
+        let (_, proof, _) =
+            generate(TrieKind::Ethereum, Shape::SharedPrefix { prefix_len: 64, keys: 2 });
+        assert!(proof.iter().any(|node| node.len() > 64));
+    }
+
+    #[test]
+    fn test_limits_reject_adversarial_proofs() {
+        let limits = ProofLimits::default();
+
+        for kind in [TrieKind::Substrate, TrieKind::Ethereum] {
+            let (root, proof, keys) = generate(kind, Shape::Wide { levels: 2, keys: 16 });
+            assert!(limits.check(kind, root, &proof, &keys).is_ok());
+
+            let (root, proof, keys) = generate(kind, Shape::Wide { levels: 2, keys: 64 });
+            assert_eq!(limits.check(kind, root, &proof, &keys), Err(LimitError::TooManyKeys(64)));
+
+            let (root, proof, keys) =
+                generate(kind, Shape::SharedPrefix { prefix_len: 256, keys: 2 });
+            assert_eq!(limits.check(kind, root, &proof, &keys), Err(LimitError::KeyTooLong(264)));
+
+            let (root, proof, keys) =
+                generate(kind, Shape::HugeValues { value_len: 4096, keys: 1 });
+            assert!(matches!(
+                limits.check(kind, root, &proof, &keys),
+                Err(LimitError::NodeTooLong(_))
+            ));
+
+            let (root, proof, keys) = generate(kind, Shape::Deep { depth: 64, keys: 8 });
+            assert!(matches!(
+                limits.check(kind, root, &proof, &keys),
+                Err(LimitError::TooManySteps(_))
+            ));
+
+            let (root, proof, keys) = generate(kind, Shape::Deep { depth: 8, keys: 1 });
+            let without_root =
+                proof.into_iter().filter(|node| keccak256(node) != root.0).collect::<Vec<_>>();
+            assert_eq!(
+                limits.check(kind, root, &without_root, &keys),
+                Err(LimitError::Invalid(VerifyError::IncompleteProof(root)))
+            );
+        }
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_adversarial_trie_gas() {
+        let limits = ProofLimits::default();
+        let mut env = TestEnv::shared().await;
+        let mut contract = env.contract("MerklePatriciaTest").await;
+
+        let verifiers = [
+            ("VerifyKeysWithGas", TrieKind::Substrate),
+            ("VerifyEthereumWithGas", TrieKind::Ethereum),
+        ];
+
+        let mut rows = vec![];
+        let mut over_budget = vec![];
+        for (func, kind) in verifiers {
+            for shape in shapes() {
+                let (root, proof, keys) = generate(kind, shape);
+                let checked = limits.check(kind, root, &proof, &keys);
+
+                let args = (
+                    Token::FixedBytes(root.0.to_vec()),
+                    Token::Array(proof.iter().cloned().map(Token::Bytes).collect()),
+                    Token::Array(keys.iter().cloned().map(Token::Bytes).collect()),
+                );
+                let calldata = encode_call(&contract, func, args.clone());
+                let gas = match contract.call::<_, (Vec<(Vec<u8>, Vec<u8>)>, u64)>(func, args).await
+                {
+                    Ok((values, execution)) => {
+                        let expected = match kind {
+                            TrieKind::Substrate =>
+                                verify_substrate_proof::<KeccakHasher>(root, &proof, &keys),
+                            TrieKind::Ethereum => verify_ethereum_proof(root, &proof, &keys),
+                        };
+                        assert_eq!(Ok(values), expected, "{func} {}", shape.name());
+                        Some(GasUsed { execution, calldata: calldata_gas(&calldata) })
+                    },
+                    Err(_) => None,
+                };
+
+                if checked.is_ok() && gas.map_or(true, |gas| gas.total() > limits.max_gas) {
+                    over_budget.push(format!(
+                        "{func} {} ({} keys): {:?} gas within the limits",
+                        shape.name(),
+                        keys.len(),
+                        gas.map(|gas| gas.total())
+                    ));
+                }
+
+                rows.push(AdversarialRow {
+                    verifier: func,
+                    shape: shape.name(),
+                    keys: keys.len(),
+                    proof_nodes: proof.len(),
+                    proof_len: proof.iter().map(Vec::len).sum(),
+                    traversal: checked.ok(),
+                    rejected: checked.err().map(|err| err.to_string()),
+                    gas,
+                });
+            }
+        }
+
+        let report = serde_json::to_string_pretty(&rows).unwrap();
+        if let Ok(dir) = env::var("GAS_REPORT_DIR") {
+            let dir = PathBuf::from(dir);
+            fs::create_dir_all(&dir).unwrap();
+            fs::write(dir.join("adversarial-gas.json"), report).unwrap();
+        }
+
+        // the sweep has to reach past the limits to show they hold at the edge
+        assert!(rows.iter().any(|row| row.rejected.is_some()));
+        assert!(
+            over_budget.is_empty(),
+            "proofs within the limits use more than {} gas:\n{}",
+            limits.max_gas,
+            over_budget.join("\n")
+        );
+    }
+}
@@ -0,0 +1,139 @@
+//! Default child tries nested in a parent trie, stored and proven the way a substrate node does.
+//!
+//! The parent trie commits to every default child trie through the value at
+//! `:child_storage:default:` followed by the child's storage key, which holds the child root. A
+//! child trie without entries is not stored at all, so the parent holds no root for it and
+//! `ReadChildProofCheck` rejects it.
+
+use primitive_types::H256;
+use sp_core::{
+    storage::{ChildInfo, StateVersion, Storage, StorageChild},
+    KeccakHasher,
+};
+use sp_state_machine::{prove_child_read_on_trie_backend, Backend, InMemoryBackend};
+use std::collections::BTreeMap;
+
+/// Collects the entries of the parent trie and of its default child tries.
+#[derive(Debug, Clone, Default)]
+pub struct ChildTrieBuilder {
+    top: BTreeMap<Vec<u8>, Vec<u8>>,
+    children: BTreeMap<Vec<u8>, BTreeMap<Vec<u8>, Vec<u8>>>,
+}
+
+impl ChildTrieBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Inserts an entry in the parent trie.
+    pub fn top(mut self, key: &[u8], value: &[u8]) -> Self {
+        self.top.insert(key.to_vec(), value.to_vec());
+        self
+    }
+
+    /// Declares the child trie at `storage_key`, without any entries.
+    pub fn child(mut self, storage_key: &[u8]) -> Self {
+        self.children.entry(storage_key.to_vec()).or_default();
+        self
+    }
+
+    /// Inserts an entry in the child trie at `storage_key`.
+    pub fn child_storage(mut self, storage_key: &[u8], key: &[u8], value: &[u8]) -> Self {
+        self.children.entry(storage_key.to_vec()).or_default().insert(key.to_vec(), value.to_vec());
+        self
+    }
+
+    /// Builds the tries. With [`StateVersion::V1`], values of 33 bytes or more are hashed.
+    pub fn build(self, state_version: StateVersion) -> ChildTries {
+        let children_default = self
+            .children
+            .into_iter()
+            .map(|(storage_key, data)| {
+                let child_info = ChildInfo::new_default(&storage_key);
+                (storage_key, StorageChild { data, child_info })
+            })
+            .collect();
+        let storage = Storage { top: self.top, children_default };
+
+        ChildTries { backend: (storage, state_version).into() }
+    }
+}
+
+/// A parent trie along with its default child tries
+pub struct ChildTries {
+    backend: InMemoryBackend<KeccakHasher>,
+}
+
+impl ChildTries {
+    /// Root of the parent trie
+    pub fn root(&self) -> H256 {
+        *self.backend.root()
+    }
+
+    /// Root of the child trie at `storage_key`, as stored in the parent trie. `None` if the
+    /// child trie is empty.
+    pub fn child_root(&self, storage_key: &[u8]) -> Option<H256> {
+        let value = self.backend.storage(&child_root_key(storage_key)).unwrap()?;
+        Some(H256::from_slice(&value))
+    }
+
+    /// Value of `key` in the child trie at `storage_key`
+    pub fn child_storage(&self, storage_key: &[u8], key: &[u8]) -> Option<Vec<u8>> {
+        self.backend.child_storage(&ChildInfo::new_default(storage_key), key).unwrap()
+    }
+
+    /// A single proof holding the lookup of the child root in the parent trie, followed by the
+    /// lookup of every key in the child trie. Keys that are absent are proven absent.
+    pub fn prove(&self, storage_key: &[u8], keys: &[Vec<u8>]) -> Vec<Vec<u8>> {
+        let child_info = ChildInfo::new_default(storage_key);
+        prove_child_read_on_trie_backend(&self.backend, &child_info, keys)
+            .expect("the backend holds every node; qed")
+            .into_iter_nodes()
+            .collect()
+    }
+}
+
+/// Key of the child root in the parent trie, the way `ReadChildProofCheck` builds it
+pub fn child_root_key(storage_key: &[u8]) -> Vec<u8> {
+    ChildInfo::new_default(storage_key).prefixed_storage_key().into_inner()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sp_state_machine::read_child_proof_check;
+    use sp_trie::StorageProof;
+
+    #[test]
+    fn test_child_root_key() {
+        assert_eq!(child_root_key(b"child"), b":child_storage:default:child".to_vec());
+    }
+
+    #[test]
+    fn test_proof_reads_child_entries() {
+        let tries = ChildTrieBuilder::new()
+            .top(b"alice", &[1; 40])
+            .child_storage(b"child", b"bob", &[2; 4])
+            .child_storage(b"child", b"charlie", &[3; 40])
+            .child(b"empty")
+            .build(StateVersion::V1);
+
+        assert!(tries.child_root(b"child").is_some());
+        assert_eq!(tries.child_root(b"empty"), None);
+        assert_eq!(tries.child_storage(b"child", b"charlie"), Some(vec![3; 40]));
+
+        let keys = vec![b"bob".to_vec(), b"charlie".to_vec(), b"dave".to_vec()];
+        let proof = StorageProof::new(tries.prove(b"child", &keys));
+        let values = read_child_proof_check::<KeccakHasher, _>(
+            tries.root(),
+            proof,
+            &ChildInfo::new_default(b"child"),
+            &keys,
+        )
+        .unwrap();
+
+        assert_eq!(values[&keys[0]], Some(vec![2; 4]));
+        assert_eq!(values[&keys[1]], Some(vec![3; 40]));
+        assert_eq!(values[&keys[2]], None);
+    }
+}
@@ -0,0 +1,156 @@
+//! Conversions between a [`CompactProof`] and the full node list taken by `VerifyKeys`.
+//!
+//! A compact proof leaves out the hash of every node that is itself part of the proof, since the
+//! verifier can recompute it, and holds hashed values inside the node that refers to them. The
+//! contract only walks full nodes, so compact proofs have to be expanded against the root first.
+
+use sp_trie::{CompactProof, CompactProofError, StorageProof};
+use trie_db::Hasher;
+
+/// Error converting between compact and full proofs
+pub type Error<H> = CompactProofError<<H as Hasher>::Out, sp_trie::Error<<H as Hasher>::Out>>;
+
+/// Expands a compact proof of the trie at `root` into its full nodes, hashed values included.
+/// Fails if the proof does not decode to `root`.
+pub fn expand<H: Hasher>(root: H::Out, proof: &CompactProof) -> Result<Vec<Vec<u8>>, Error<H>> {
+    let (proof, _) = proof.to_storage_proof::<H>(Some(&root))?;
+    Ok(proof.into_iter_nodes().collect())
+}
+
+/// Compacts the full nodes of a proof of the trie at `root`. Nodes that cannot be reached from
+/// `root` are dropped.
+pub fn compact<H: Hasher>(root: H::Out, nodes: Vec<Vec<u8>>) -> Result<CompactProof, Error<H>> {
+    StorageProof::new(nodes).into_compact_proof::<H>(root)
+}
+
+/// Number of node bytes in both forms of a proof, leaving out the framing of the node list
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ProofSize {
+    /// Number of nodes in the full proof
+    pub nodes: usize,
+    /// Bytes in the full nodes
+    pub full: usize,
+    /// Bytes in the compact nodes
+    pub compact: usize,
+}
+
+impl ProofSize {
+    pub fn new(full: &[Vec<u8>], compact: &CompactProof) -> Self {
+        ProofSize {
+            nodes: full.len(),
+            full: full.iter().map(Vec::len).sum(),
+            compact: compact.encoded_nodes.iter().map(Vec::len).sum(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::{collection::vec, prelude::*};
+    use sp_core::KeccakHasher;
+    use sp_trie::{LayoutV0, LayoutV1, MemoryDB};
+    use std::collections::BTreeSet;
+    use trie_db::{Recorder, Trie, TrieDBBuilder, TrieDBMutBuilder, TrieLayout, TrieMut};
+
+    type Hash = <KeccakHasher as Hasher>::Out;
+
+    /// Builds a trie from `entries` and records the nodes read to look up `keys`.
+    fn full_proof<L: TrieLayout<Hash = KeccakHasher>>(
+        entries: &[(Vec<u8>, Vec<u8>)],
+        keys: &[Vec<u8>],
+    ) -> (Hash, Vec<Vec<u8>>) {
+        let mut db = MemoryDB::<KeccakHasher>::default();
+        let mut root = Default::default();
+        {
+            let mut trie = TrieDBMutBuilder::<L>::new(&mut db, &mut root).build();
+            for (key, value) in entries {
+                trie.insert(key, value).unwrap();
+            }
+        }
+
+        let mut recorder = Recorder::<L>::new();
+        {
+            let trie = TrieDBBuilder::<L>::new(&db, &root).with_recorder(&mut recorder).build();
+            for key in keys {
+                trie.get(key).unwrap();
+            }
+        }
+        let nodes = recorder.drain().into_iter().map(|record| record.data);
+
+        (root, nodes.collect::<BTreeSet<_>>().into_iter().collect())
+    }
+
+    fn sorted(nodes: Vec<Vec<u8>>) -> BTreeSet<Vec<u8>> {
+        nodes.into_iter().collect()
+    }
+
+    fn entries() -> impl Strategy<Value = Vec<(Vec<u8>, Vec<u8>)>> {
+        // short keys share prefixes, values straddle the inline threshold of `LayoutV1`
+        vec((vec(any::<u8>(), 1..6), vec(any::<u8>(), 1..64)), 1..100)
+    }
+
+    proptest! {
+        #[test]
+        fn test_round_trip(entries in entries(), step in 1usize..5, hashed_values: bool) {
+            let keys = entries.iter().step_by(step).map(|(key, _)| key.clone()).collect::<Vec<_>>();
+            let (root, nodes) = if hashed_values {
+                full_proof::<LayoutV1<KeccakHasher>>(&entries, &keys)
+            } else {
+                full_proof::<LayoutV0<KeccakHasher>>(&entries, &keys)
+            };
+
+            let proof = compact::<KeccakHasher>(root, nodes.clone()).unwrap();
+            let expanded = expand::<KeccakHasher>(root, &proof).unwrap();
+            prop_assert_eq!(sorted(expanded.clone()), sorted(nodes));
+
+            let recompacted = compact::<KeccakHasher>(root, expanded).unwrap();
+            prop_assert_eq!(recompacted.encoded_nodes, proof.encoded_nodes);
+        }
+    }
+
+    /// Proof of a single key in a trie of 50 entries
+    fn single_key_proof() -> (Hash, Vec<Vec<u8>>) {
+        let entries =
+            (0u32..50).map(|i| (i.to_be_bytes().to_vec(), vec![1; 8])).collect::<Vec<_>>();
+        full_proof::<LayoutV0<KeccakHasher>>(&entries, &[entries[0].0.clone()])
+    }
+
+    #[test]
+    fn test_unreachable_nodes_are_dropped() {
+        let (root, nodes) = single_key_proof();
+
+        let mut padded = nodes.clone();
+        padded.push(vec![0xff; 40]);
+        let proof = compact::<KeccakHasher>(root, padded).unwrap();
+        assert_eq!(sorted(expand::<KeccakHasher>(root, &proof).unwrap()), sorted(nodes));
+    }
+
+    #[test]
+    fn test_expand_wrong_root() {
+        let (root, nodes) = single_key_proof();
+        let proof = compact::<KeccakHasher>(root, nodes).unwrap();
+
+        assert!(expand::<KeccakHasher>(Hash::repeat_byte(1), &proof).is_err());
+    }
+
+    #[test]
+    fn test_size_comparison() {
+        let entries = (0u32..4096)
+            .map(|i| (sp_core::keccak_256(&i.to_le_bytes()).to_vec(), vec![1; 32]))
+            .collect::<Vec<_>>();
+
+        for proven in [1, 16, 256] {
+            let keys = entries.iter().take(proven).map(|(key, _)| key.clone()).collect::<Vec<_>>();
+            let (root, nodes) = full_proof::<LayoutV0<KeccakHasher>>(&entries, &keys);
+            let proof = compact::<KeccakHasher>(root, nodes.clone()).unwrap();
+            let size = ProofSize::new(&nodes, &proof);
+            assert!(size.compact < size.full, "{proven} keys: {size:?}");
+
+            // every node but the root is referenced by a hash, which is replaced by an empty
+            // inline child
+            assert_eq!(proof.encoded_nodes.len(), size.nodes);
+            assert_eq!(size.full - size.compact, 32 * (size.nodes - 1));
+        }
+    }
+}
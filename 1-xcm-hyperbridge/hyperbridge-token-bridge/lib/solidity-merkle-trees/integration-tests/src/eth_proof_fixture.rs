@@ -0,0 +1,339 @@
+//! Loads `eth_getProof` responses saved from a node so they can be replayed against the verifier.
+//!
+//! A fixture is a directory holding `proof.json`, the `eth_getProof` response, and `header.json`,
+//! the header of the block the proof was taken at as returned by `eth_getBlockByNumber`. Both
+//! may either be the bare `result` object or the whole JSON-RPC response.
+//!
+//! Headers that carry their `hash` are checked against it with [`header_hash`], which ties the
+//! state root to a real block.
+
+use crate::{
+    ethereum_trie::{read_proof, secure_key, Account, AccountProof},
+    keccak256,
+};
+use ethers::types::Bytes;
+use primitive_types::{H256, U256};
+use rlp::RlpStream;
+use serde_json::Value;
+use std::{
+    fmt, fs, io,
+    path::{Path, PathBuf},
+};
+
+/// Error types for loading fixtures
+#[derive(Debug)]
+pub enum FixtureError {
+    /// A fixture file could not be read
+    Io(PathBuf, io::Error),
+    /// A fixture file is not a valid response
+    Json(serde_json::Error),
+    /// The block header has no `stateRoot`
+    MissingStateRoot,
+    /// The block header has no `hash`
+    MissingBlockHash,
+    /// A header field that is part of the hash is missing or not hex
+    InvalidHeaderField(&'static str),
+    /// The header does not hash to the `hash` it records
+    HeaderHashMismatch { recorded: H256, computed: H256 },
+}
+
+impl fmt::Display for FixtureError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FixtureError::Io(path, err) => write!(f, "failed to read {}: {err}", path.display()),
+            FixtureError::Json(err) => write!(f, "invalid fixture: {err}"),
+            FixtureError::MissingStateRoot => write!(f, "block header has no stateRoot"),
+            FixtureError::MissingBlockHash => write!(f, "block header has no hash"),
+            FixtureError::InvalidHeaderField(field) => write!(f, "invalid header field {field}"),
+            FixtureError::HeaderHashMismatch { recorded, computed } =>
+                write!(f, "header hashes to {computed:?}, it records {recorded:?}"),
+        }
+    }
+}
+
+impl From<serde_json::Error> for FixtureError {
+    fn from(err: serde_json::Error) -> Self {
+        FixtureError::Json(err)
+    }
+}
+
+/// A single lookup to replay against `VerifyEthereum`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ProofQuery {
+    /// What is being looked up, for assertion messages
+    pub label: String,
+    pub root: H256,
+    /// Proof nodes, ordered from the root
+    pub proof: Vec<Vec<u8>>,
+    /// The hashed key
+    pub key: Vec<u8>,
+    /// Value the proof resolves to, `None` if it proves the key absent
+    pub value: Option<Vec<u8>>,
+}
+
+/// An `eth_getProof` response along with the header of its block
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EthProofFixture {
+    pub name: String,
+    pub state_root: H256,
+    /// The header, as returned by `eth_getBlockByNumber`
+    pub header: Value,
+    pub proof: AccountProof,
+}
+
+impl EthProofFixture {
+    /// Loads the fixture in `dir`, named after the directory.
+    pub fn load(dir: &Path) -> Result<Self, FixtureError> {
+        let read = |file: &str| {
+            let path = dir.join(file);
+            fs::read_to_string(&path).map_err(|err| FixtureError::Io(path, err))
+        };
+        let name = dir.file_name().unwrap_or_default().to_string_lossy().into_owned();
+
+        Self::from_json(name, &read("proof.json")?, &read("header.json")?)
+    }
+
+    /// Loads every fixture in the subdirectories of `dir`, sorted by name.
+    pub fn load_all(dir: &Path) -> Result<Vec<Self>, FixtureError> {
+        let mut dirs = fs::read_dir(dir)
+            .map_err(|err| FixtureError::Io(dir.to_path_buf(), err))?
+            .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+            .filter(|path| path.is_dir())
+            .collect::<Vec<_>>();
+        dirs.sort();
+
+        dirs.iter().map(|dir| Self::load(dir)).collect()
+    }
+
+    /// Parses an `eth_getProof` response and the header of the block it was taken at.
+    pub fn from_json(name: String, proof: &str, header: &str) -> Result<Self, FixtureError> {
+        let proof = serde_json::from_value(result(serde_json::from_str(proof)?))?;
+        let header = result(serde_json::from_str(header)?);
+        let state_root = header.get("stateRoot").cloned().ok_or(FixtureError::MissingStateRoot)?;
+        let state_root = serde_json::from_value(state_root)?;
+
+        Ok(Self { name, state_root, header, proof })
+    }
+
+    /// Checks that the header hashes to the `hash` it records. The stub headers of synthetic
+    /// fixtures only hold a `stateRoot` and fail with [`FixtureError::MissingBlockHash`].
+    pub fn check_header(&self) -> Result<H256, FixtureError> {
+        let recorded = self.header.get("hash").cloned().ok_or(FixtureError::MissingBlockHash)?;
+        let recorded = serde_json::from_value(recorded)?;
+        let computed = header_hash(&self.header)?;
+        if computed != recorded {
+            return Err(FixtureError::HeaderHashMismatch { recorded, computed })
+        }
+
+        Ok(computed)
+    }
+
+    /// The account lookup in the state trie, followed by a lookup in the storage trie for every
+    /// slot. Storage roots are taken from the account proof.
+    pub fn queries(&self) -> Vec<ProofQuery> {
+        let account = ProofQuery::new(
+            format!("{}: account {:?}", self.name, self.proof.address),
+            self.state_root,
+            self.proof.account_nodes(),
+            secure_key(self.proof.address.as_bytes()),
+        );
+
+        let storage = self.proof.storage_proof.iter().map(|storage| {
+            ProofQuery::new(
+                format!("{}: slot {:?}", self.name, storage.slot()),
+                self.proof.storage_hash,
+                storage.nodes(),
+                secure_key(storage.slot().as_bytes()),
+            )
+        });
+
+        std::iter::once(account).chain(storage).collect()
+    }
+
+    /// Checks that the values the proofs resolve to agree with the account and slot values in
+    /// the response.
+    pub fn check_values(&self) -> Result<(), String> {
+        let queries = self.queries();
+
+        let account = self.proof.account();
+        match queries[0].value.as_ref().map(|value| rlp::decode::<Account>(value)) {
+            Some(Ok(proven)) if proven == account => {},
+            None if account == Account::default() => {},
+            _ => {
+                let label = &queries[0].label;
+                return Err(format!("{label}: proof does not resolve to the account"))
+            },
+        }
+
+        for (query, storage) in queries[1..].iter().zip(&self.proof.storage_proof) {
+            let value = match &query.value {
+                Some(value) => rlp::decode::<U256>(value).map_err(|err| err.to_string())?,
+                None => U256::zero(),
+            };
+            if value != storage.value {
+                return Err(format!("{}: proof resolves to {value}", query.label))
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl ProofQuery {
+    /// Resolves the value by looking up `key` in the proof nodes. Panics if the proof is
+    /// missing a node on the path.
+    fn new(label: String, root: H256, proof: Vec<Vec<u8>>, key: Vec<u8>) -> Self {
+        let value = read_proof(root, &proof, &key)
+            .unwrap_or_else(|err| panic!("{label}: incomplete proof: {err:?}"));
+
+        Self { label, root, proof, key, value }
+    }
+}
+
+/// Fields of a block header in the order they are rlp encoded, and whether they are quantities
+/// rather than byte strings
+const HEADER_FIELDS: [(&str, bool); 15] = [
+    ("parentHash", false),
+    ("sha3Uncles", false),
+    ("miner", false),
+    ("stateRoot", false),
+    ("transactionsRoot", false),
+    ("receiptsRoot", false),
+    ("logsBloom", false),
+    ("difficulty", true),
+    ("number", true),
+    ("gasLimit", true),
+    ("gasUsed", true),
+    ("timestamp", true),
+    ("extraData", false),
+    ("mixHash", false),
+    ("nonce", false),
+];
+
+/// Fields added to the header by London, Shanghai, Cancun and Prague, in that order
+const FORK_HEADER_FIELDS: [(&str, bool); 6] = [
+    ("baseFeePerGas", true),
+    ("withdrawalsRoot", false),
+    ("blobGasUsed", true),
+    ("excessBlobGas", true),
+    ("parentBeaconBlockRoot", false),
+    ("requestsHash", false),
+];
+
+/// Hash of a header as returned by `eth_getBlockByNumber`, `keccak256(rlp(header))`. Fields
+/// added by forks are encoded for as long as the header has them.
+pub fn header_hash(header: &Value) -> Result<H256, FixtureError> {
+    let forks = FORK_HEADER_FIELDS
+        .iter()
+        .take_while(|(field, _)| header.get(field).is_some_and(|value| !value.is_null()))
+        .count();
+
+    let mut stream = RlpStream::new_list(HEADER_FIELDS.len() + forks);
+    for (field, quantity) in HEADER_FIELDS.iter().chain(&FORK_HEADER_FIELDS[..forks]) {
+        let value = header.get(field).cloned().ok_or(FixtureError::InvalidHeaderField(field))?;
+        if *quantity {
+            let value = serde_json::from_value::<U256>(value)
+                .map_err(|_| FixtureError::InvalidHeaderField(field))?;
+            stream.append(&value);
+        } else {
+            let value = serde_json::from_value::<Bytes>(value)
+                .map_err(|_| FixtureError::InvalidHeaderField(field))?;
+            stream.append(&value.to_vec());
+        }
+    }
+
+    Ok(H256(keccak256(stream.out())))
+}
+
+/// Unwraps the `result` of a JSON-RPC response
+pub fn result(mut json: Value) -> Value {
+    match json.get_mut("result") {
+        Some(result) => result.take(),
+        None => json,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ethers::types::Address;
+
+    fn fixtures_dir() -> PathBuf {
+        PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("fixtures/eth_get_proof")
+    }
+
+    #[test]
+    fn test_fixture_values_match_proofs() {
+        let fixtures = EthProofFixture::load_all(&fixtures_dir()).unwrap();
+        assert!(!fixtures.is_empty());
+
+        for fixture in fixtures {
+            fixture.check_values().unwrap();
+        }
+    }
+
+    #[test]
+    fn test_real_fixtures_hash_to_their_block() {
+        let fixtures = EthProofFixture::load_all(&fixtures_dir()).unwrap();
+        let (synthetic, real): (Vec<_>, Vec<_>) =
+            fixtures.iter().partition(|fixture| fixture.name.starts_with("synthetic_"));
+        assert!(!real.is_empty());
+
+        for fixture in real {
+            fixture.check_header().unwrap_or_else(|err| panic!("{}: {err}", fixture.name));
+        }
+        for fixture in synthetic {
+            assert!(matches!(fixture.check_header(), Err(FixtureError::MissingBlockHash)));
+        }
+
+        // Holesky's block 0
+        let holesky =
+            EthProofFixture::load(&fixtures_dir().join("holesky_block_0_deposit_contract"))
+                .unwrap();
+        assert_eq!(
+            holesky.check_header().unwrap(),
+            H256(hex_literal::hex!(
+                "b5f7f912443c940f21fd611f12828d75b534364ed9e95ca4e307729a4661bde4"
+            ))
+        );
+
+        // any change to a field changes the hash
+        let mut header = holesky.header.clone();
+        header["gasUsed"] = "0x1".into();
+        assert_ne!(header_hash(&header).unwrap(), holesky.check_header().unwrap());
+
+        header["stateRoot"] = "0xzz".into();
+        assert!(matches!(header_hash(&header), Err(FixtureError::InvalidHeaderField("stateRoot"))));
+    }
+
+    #[test]
+    fn test_parse_bare_result() {
+        let proof = r#"{
+            "address": "0x000000000000000000000000000000000000beef",
+            "accountProof": [],
+            "balance": "0x0",
+            "codeHash": "0xc5d2460186f7233c927e7db2dcc703c0e500b653ca82273b7bfad8045d85a470",
+            "nonce": "0x1",
+            "storageHash": "0x56e81f171bcc55a6ff8345e692c0f86e5b48e01b996cadc001622fb5e363b421",
+            "storageProof": [{ "key": "0x9", "value": "0x0", "proof": [] }]
+        }"#;
+        let header = r#"{ "number": "0x1", "stateRoot": "0x01" }"#;
+
+        // the state root must be a full 32 byte hash
+        assert!(matches!(
+            EthProofFixture::from_json("bare".into(), proof, header),
+            Err(FixtureError::Json(_))
+        ));
+
+        let header = format!(r#"{{ "stateRoot": "0x{}" }}"#, "11".repeat(32));
+        let fixture = EthProofFixture::from_json("bare".into(), proof, &header).unwrap();
+        assert_eq!(fixture.state_root, H256::repeat_byte(0x11));
+        assert_eq!(fixture.proof.address, Address::from_low_u64_be(0xbeef));
+        assert_eq!(fixture.proof.storage_proof[0].slot(), H256::from_low_u64_be(9));
+
+        assert!(matches!(
+            EthProofFixture::from_json("bare".into(), proof, "{}"),
+            Err(FixtureError::MissingStateRoot)
+        ));
+    }
+}
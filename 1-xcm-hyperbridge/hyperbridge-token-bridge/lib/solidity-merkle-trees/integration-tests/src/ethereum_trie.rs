@@ -0,0 +1,525 @@
+//! Builds ethereum state and storage tries from plain account data and proves accounts and
+//! storage slots out of them, in the shape returned by `eth_getProof`.
+//!
+//! ```ignore
+//! let state = StateTrieBuilder::new()
+//!     .account(address, Account { nonce: 1, ..Default::default() })
+//!     .storage(address, H256::zero(), U256::from(42))
+//!     .build();
+//! let proof = state.get_proof(address, &[H256::zero()]);
+//! ```
+
+use crate::keccak256;
+use eth_trie_db::{
+    CError, Hasher, Recorder, Trie, TrieDBBuilder, TrieDBMutBuilder, TrieHash, TrieMut,
+};
+use ethers::types::{Address, Bytes, U64};
+use hex_literal::hex;
+use patricia_merkle_trie::{keccak::KeccakHasher, EIP1186Layout, MemoryDB};
+use primitive_types::{H256, U256};
+use rlp::{Decodable, DecoderError, Encodable, Rlp, RlpStream};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashSet};
+
+/// Trie layout used by the ethereum state, storage, transaction and receipt tries
+pub type EthereumLayout = EIP1186Layout<KeccakHasher>;
+
+/// Root of a trie with no entries, `keccak256(rlp(""))`
+pub const EMPTY_ROOT: H256 =
+    H256(hex!("56e81f171bcc55a6ff8345e692c0f86e5b48e01b996cadc001622fb5e363b421"));
+
+/// Code hash of an account without code, `keccak256("")`
+pub const EMPTY_CODE_HASH: H256 =
+    H256(hex!("c5d2460186f7233c927e7db2dcc703c0e500b653ca82273b7bfad8045d85a470"));
+
+/// An account as it is stored in the state trie
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Account {
+    pub nonce: u64,
+    pub balance: U256,
+    /// Root of the account's storage trie
+    pub storage_root: H256,
+    /// Hash of the account's code
+    pub code_hash: H256,
+}
+
+impl Default for Account {
+    /// An account without storage or code
+    fn default() -> Self {
+        Account {
+            nonce: 0,
+            balance: U256::zero(),
+            storage_root: EMPTY_ROOT,
+            code_hash: EMPTY_CODE_HASH,
+        }
+    }
+}
+
+impl Encodable for Account {
+    fn rlp_append(&self, stream: &mut RlpStream) {
+        stream
+            .begin_list(4)
+            .append(&self.nonce)
+            .append(&self.balance)
+            .append(&self.storage_root)
+            .append(&self.code_hash);
+    }
+}
+
+impl Decodable for Account {
+    fn decode(rlp: &Rlp) -> Result<Self, DecoderError> {
+        if rlp.item_count()? != 4 {
+            return Err(DecoderError::RlpIncorrectListLen)
+        }
+
+        Ok(Account {
+            nonce: rlp.val_at(0)?,
+            balance: rlp.val_at(1)?,
+            storage_root: rlp.val_at(2)?,
+            code_hash: rlp.val_at(3)?,
+        })
+    }
+}
+
+/// Account and storage proofs, mirroring the response of `eth_getProof`
+#[derive(Debug, Clone, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AccountProof {
+    pub address: Address,
+    pub balance: U256,
+    pub nonce: U64,
+    pub code_hash: H256,
+    pub storage_hash: H256,
+    /// Nodes on the path to the account in the state trie, ordered from the root
+    pub account_proof: Vec<Bytes>,
+    pub storage_proof: Vec<StorageProof>,
+}
+
+impl AccountProof {
+    /// The account that was proven
+    pub fn account(&self) -> Account {
+        Account {
+            nonce: self.nonce.as_u64(),
+            balance: self.balance,
+            storage_root: self.storage_hash,
+            code_hash: self.code_hash,
+        }
+    }
+
+    /// Nodes of the account proof, as passed to `VerifyEthereum`
+    pub fn account_nodes(&self) -> Vec<Vec<u8>> {
+        self.account_proof.iter().map(|node| node.to_vec()).collect()
+    }
+}
+
+/// Proof of a single storage slot, mirroring an entry of `storageProof` in `eth_getProof`
+#[derive(Debug, Clone, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub struct StorageProof {
+    /// The slot, as it was requested
+    pub key: U256,
+    /// Value of the slot, zero if it is not set
+    pub value: U256,
+    /// Nodes on the path to the slot in the storage trie, ordered from the root
+    pub proof: Vec<Bytes>,
+}
+
+impl StorageProof {
+    /// The slot as a 32 byte word
+    pub fn slot(&self) -> H256 {
+        let mut slot = H256::zero();
+        self.key.to_big_endian(slot.as_bytes_mut());
+        slot
+    }
+
+    /// Nodes of the storage proof, as passed to `VerifyEthereum`
+    pub fn nodes(&self) -> Vec<Vec<u8>> {
+        self.proof.iter().map(|node| node.to_vec()).collect()
+    }
+}
+
+/// Collects accounts and their storage before hashing them into a [`StateTrie`].
+#[derive(Debug, Clone, Default)]
+pub struct StateTrieBuilder {
+    accounts: BTreeMap<Address, (Account, BTreeMap<H256, U256>)>,
+}
+
+impl StateTrieBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds an account, replacing any account already at `address`. If storage is added for the
+    /// account, its `storage_root` is replaced by the root of the built storage trie.
+    pub fn account(mut self, address: Address, account: Account) -> Self {
+        self.accounts.entry(address).or_default().0 = account;
+        self
+    }
+
+    /// Sets a storage slot of the account at `address`, adding an empty account if there is
+    /// none. Zero values are not stored, just like in the EVM.
+    pub fn storage(mut self, address: Address, slot: H256, value: U256) -> Self {
+        let (_, storage) = self.accounts.entry(address).or_default();
+        if value.is_zero() {
+            storage.remove(&slot);
+        } else {
+            storage.insert(slot, value);
+        }
+        self
+    }
+
+    /// Builds the storage trie of every account that has storage, followed by the state trie.
+    pub fn build(self) -> StateTrie {
+        let mut accounts = BTreeMap::new();
+        let mut storage = BTreeMap::new();
+
+        for (address, (mut account, slots)) in self.accounts {
+            if !slots.is_empty() {
+                let trie = EthereumTrie::new(
+                    slots
+                        .iter()
+                        .map(|(slot, value)| (secure_key(slot.as_bytes()), rlp_value(value))),
+                );
+                account.storage_root = trie.root();
+                storage.insert(address, trie);
+            }
+            accounts.insert(address, account);
+        }
+
+        let trie = EthereumTrie::new(accounts.iter().map(|(address, account)| {
+            (secure_key(address.as_bytes()), rlp::encode(account).to_vec())
+        }));
+
+        StateTrie { trie, accounts, storage }
+    }
+}
+
+/// A state trie along with the storage tries of its accounts
+pub struct StateTrie {
+    trie: EthereumTrie,
+    accounts: BTreeMap<Address, Account>,
+    storage: BTreeMap<Address, EthereumTrie>,
+}
+
+impl StateTrie {
+    /// The state root
+    pub fn root(&self) -> H256 {
+        self.trie.root()
+    }
+
+    /// The account at `address`, with its `storage_root` computed from its storage
+    pub fn account(&self, address: Address) -> Option<&Account> {
+        self.accounts.get(&address)
+    }
+
+    /// Proves an account and some of its storage slots, just like `eth_getProof`. Accounts that
+    /// don't exist are proven absent and reported as empty accounts, slots that aren't set are
+    /// proven absent and reported as zero.
+    pub fn get_proof(&self, address: Address, slots: &[H256]) -> AccountProof {
+        let account = self.accounts.get(&address).copied().unwrap_or_default();
+        let account_proof = self.trie.prove(&[secure_key(address.as_bytes())]);
+
+        let storage_proof = slots
+            .iter()
+            .map(|slot| {
+                let key = secure_key(slot.as_bytes());
+                let (value, proof) = match self.storage.get(&address) {
+                    Some(trie) => {
+                        let value = trie
+                            .get(&key)
+                            .map(|value| rlp::decode::<U256>(&value).expect("values are rlp; qed"));
+                        (value.unwrap_or_default(), trie.prove(&[key]))
+                    },
+                    // an empty storage trie is proven by its root alone
+                    None => (U256::zero(), vec![]),
+                };
+
+                StorageProof {
+                    key: U256::from_big_endian(slot.as_bytes()),
+                    value,
+                    proof: proof.into_iter().map(Bytes::from).collect(),
+                }
+            })
+            .collect();
+
+        AccountProof {
+            address,
+            balance: account.balance,
+            nonce: account.nonce.into(),
+            code_hash: account.code_hash,
+            storage_hash: account.storage_root,
+            account_proof: account_proof.into_iter().map(Bytes::from).collect(),
+            storage_proof,
+        }
+    }
+}
+
+/// The transactions or receipts trie of a block, keyed by `rlp(index)`
+pub struct IndexTrie {
+    trie: EthereumTrie,
+    items: Vec<Vec<u8>>,
+}
+
+/// Proof of the item at some index of an [`IndexTrie`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct IndexProof {
+    /// The key, `rlp(index)`
+    pub key: Vec<u8>,
+    /// The item, as stored in the trie
+    pub value: Vec<u8>,
+    /// Nodes on the path to the item, ordered from the root
+    pub proof: Vec<Vec<u8>>,
+}
+
+impl IndexTrie {
+    /// Builds the trie from a block's transactions or receipts, in order. Items are legacy rlp
+    /// lists or EIP-2718 typed envelopes (`type || payload`). Envelopes wrapped in an rlp string,
+    /// as they appear in block bodies, are unwrapped first, and fail if the string is malformed.
+    pub fn new(items: impl IntoIterator<Item = Vec<u8>>) -> Result<Self, DecoderError> {
+        let items = items.into_iter().map(trie_item).collect::<Result<Vec<_>, _>>()?;
+        let trie = EthereumTrie::new(
+            items.iter().enumerate().map(|(index, item)| (index_key(index), item.clone())),
+        );
+
+        Ok(Self { trie, items })
+    }
+
+    /// The `transactionsRoot` or `receiptsRoot` of the block
+    pub fn root(&self) -> H256 {
+        self.trie.root()
+    }
+
+    /// Number of items in the trie
+    pub fn len(&self) -> usize {
+        self.items.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.items.is_empty()
+    }
+
+    /// Proves the item at `index`, or returns `None` if there is no such item.
+    pub fn prove(&self, index: usize) -> Option<IndexProof> {
+        let value = self.items.get(index)?.clone();
+        let key = index_key(index);
+        let proof = self.trie.prove(&[key.clone()]);
+
+        Some(IndexProof { key, value, proof })
+    }
+}
+
+impl IndexProof {
+    /// Checks the proof against a `transactionsRoot` or `receiptsRoot`
+    pub fn verify(&self, root: H256) -> bool {
+        matches!(read_proof(root, &self.proof, &self.key), Ok(Some(value)) if value == self.value)
+    }
+}
+
+/// Key of the item at `index` in a transactions or receipts trie
+pub fn index_key(index: usize) -> Vec<u8> {
+    rlp::encode(&(index as u64)).to_vec()
+}
+
+/// Unwraps typed envelopes that are encoded as rlp strings, leaving legacy items and bare
+/// envelopes as they are.
+fn trie_item(item: Vec<u8>) -> Result<Vec<u8>, DecoderError> {
+    match item.first() {
+        Some(0x80..=0xbf) => Ok(Rlp::new(&item).data()?.to_vec()),
+        _ => Ok(item),
+    }
+}
+
+/// An in-memory trie with the [`EthereumLayout`]
+pub struct EthereumTrie {
+    db: MemoryDB<KeccakHasher>,
+    root: <KeccakHasher as Hasher>::Out,
+}
+
+impl EthereumTrie {
+    /// Inserts the entries into an empty trie. Keys are used as they are, so callers hash them
+    /// first when building a secure trie.
+    pub fn new(entries: impl IntoIterator<Item = (Vec<u8>, Vec<u8>)>) -> Self {
+        let mut db = MemoryDB::<KeccakHasher>::default();
+        let mut root = Default::default();
+        {
+            let mut trie = TrieDBMutBuilder::<EthereumLayout>::new(&mut db, &mut root).build();
+            for (key, value) in entries {
+                trie.insert(&key, &value).unwrap();
+            }
+        }
+
+        Self { db, root }
+    }
+
+    pub fn root(&self) -> H256 {
+        H256::from_slice(self.root.as_ref())
+    }
+
+    /// Looks up a key in the trie
+    pub fn get(&self, key: &[u8]) -> Option<Vec<u8>> {
+        let trie = TrieDBBuilder::<EthereumLayout>::new(&self.db, &self.root).build();
+        trie.get(key).unwrap()
+    }
+
+    /// Nodes visited while looking up `keys`, without duplicates and ordered from the root.
+    /// Inline nodes are part of their parent and are not included.
+    pub fn prove(&self, keys: &[Vec<u8>]) -> Vec<Vec<u8>> {
+        let mut recorder = Recorder::<EthereumLayout>::new();
+        {
+            let trie = TrieDBBuilder::<EthereumLayout>::new(&self.db, &self.root)
+                .with_recorder(&mut recorder)
+                .build();
+            for key in keys {
+                trie.get(key).unwrap();
+            }
+        }
+
+        let mut seen = HashSet::new();
+        recorder
+            .drain()
+            .into_iter()
+            .map(|record| record.data)
+            .filter(|node| seen.insert(node.clone()))
+            .collect()
+    }
+}
+
+/// Looks up `key` in the trie made up of the proof nodes. Fails if a node on the path to the key
+/// is missing from the proof.
+pub fn read_proof(
+    root: H256,
+    proof: &[Vec<u8>],
+    key: &[u8],
+) -> eth_trie_db::Result<Option<Vec<u8>>, TrieHash<EthereumLayout>, CError<EthereumLayout>> {
+    let db =
+        patricia_merkle_trie::StorageProof::new(proof.to_vec()).into_memory_db::<KeccakHasher>();
+    let mut hash = TrieHash::<EthereumLayout>::default();
+    hash.as_mut().copy_from_slice(root.as_bytes());
+
+    let trie = TrieDBBuilder::<EthereumLayout>::new(&db, &hash).build();
+    trie.get(key)
+}
+
+/// Key of an entry in a secure trie, `keccak256(key)`
+pub fn secure_key(key: &[u8]) -> Vec<u8> {
+    keccak256(key).to_vec()
+}
+
+/// Storage values are stored as rlp encoded integers
+fn rlp_value(value: &U256) -> Vec<u8> {
+    rlp::encode(value).to_vec()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_empty_account_at_zero_address() {
+        // the `synthetic_zero_address_single_node` eth_getProof fixture
+        let state = StateTrieBuilder::new().account(Address::zero(), Account::default()).build();
+        assert_eq!(
+            state.root(),
+            H256(hex!("0ce23f3c809de377b008a4a3ee94a0834aac8bec1f86e28ffe4fdb5a15b0c785"))
+        );
+
+        let proof = state.get_proof(Address::zero(), &[]);
+        assert_eq!(
+            proof.account_nodes(),
+            vec![hex!("f86aa1205380c7b7ae81a58eb98d9c78de4a1fd7fd9535fc953ed2be602daaa41767312ab846f8448080a056e81f171bcc55a6ff8345e692c0f86e5b48e01b996cadc001622fb5e363b421a0c5d2460186f7233c927e7db2dcc703c0e500b653ca82273b7bfad8045d85a470").to_vec()]
+        );
+    }
+
+    #[test]
+    fn test_empty_trie_root() {
+        assert_eq!(EthereumTrie::new(vec![]).root(), EMPTY_ROOT);
+        assert_eq!(H256(keccak256(b"")), EMPTY_CODE_HASH);
+    }
+
+    #[test]
+    fn test_account_rlp_round_trip() {
+        let account = Account {
+            nonce: 7,
+            balance: U256::exp10(18),
+            storage_root: H256::repeat_byte(1),
+            code_hash: H256::repeat_byte(2),
+        };
+        let encoded = rlp::encode(&account);
+        assert_eq!(rlp::decode::<Account>(&encoded), Ok(account));
+    }
+
+    #[test]
+    fn test_storage_root_is_computed() {
+        let address = Address::repeat_byte(0xaa);
+        let state = StateTrieBuilder::new()
+            .account(address, Account { nonce: 1, ..Default::default() })
+            .storage(address, H256::zero(), U256::from(42))
+            .storage(address, H256::from_low_u64_be(1), U256::zero())
+            .build();
+
+        let account = state.account(address).unwrap();
+        let storage = EthereumTrie::new(vec![(
+            secure_key(H256::zero().as_bytes()),
+            rlp_value(&U256::from(42)),
+        )]);
+        assert_eq!(account.storage_root, storage.root());
+        assert_eq!(account.nonce, 1);
+
+        let proof = state.get_proof(address, &[H256::zero(), H256::from_low_u64_be(1)]);
+        assert_eq!(proof.account(), *account);
+        assert_eq!(proof.storage_proof[0].value, U256::from(42));
+        assert_eq!(proof.storage_proof[1].value, U256::zero());
+        assert_eq!(proof.storage_proof[1].slot(), H256::from_low_u64_be(1));
+    }
+
+    #[test]
+    fn test_single_item_index_tries() {
+        // the single node transaction and receipt trie fixtures in `merkle_patricia.rs`
+        let transaction = hex!("01f89301808080808080f847f84580f842a00000000000000000000000000000000000000000000000000000000000000000a0000000000000000000000000000000000000000000000000000000000000000080a08c7939f0e613736150a05565fcddda959b22c44ddac6c6aed8ec59e1462a0498a0166d30e3763829d64fca3d38601e65ba6f0e94f7e3c544381ae5e9e9b12dacd0").to_vec();
+        let trie = IndexTrie::new(vec![transaction.clone()]).unwrap();
+        assert_eq!(
+            trie.root(),
+            H256(hex!("ecabc214ab6c55e1342e888fa677e2bcc29218a4b248a56fcebf7aa357807b60"))
+        );
+
+        // typed envelopes from a block body are wrapped in an rlp string
+        let wrapped = rlp::encode(&transaction).to_vec();
+        assert_eq!(IndexTrie::new(vec![wrapped]).unwrap().root(), trie.root());
+
+        // a string that is shorter than its header says
+        let truncated = hex!("b90100").to_vec();
+        assert_eq!(IndexTrie::new(vec![truncated]).err(), Some(DecoderError::RlpIsTooShort));
+
+        let receipt = hex!("f90107c1010180b9010000000000000000000081000000000000000000000000000000000002000000000000000000000000000000800000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000020000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000028000000000040000080000000400000000000000000000000000000000000000000000000000000000000000000000010000010000000000000000000000000000000001400000000000000008000000000000000000000000000000000f79422341ae42d6dd7384bc8584e50419ea3ac75b83fa004491edcd115127caedbd478e2e7895ed80c7847e903431f94f9cfa579cad47f80f87694e7fb22dfef11920312e4989a3a2b81e2ebf05986b8407f1fef85c4b037150d3675218e0cdb7cf38fea354759471e309f3354918a442fd85629c7eaae9ea4a10234fed31bc0aeda29b2683ebe0c1882499d272621f6b69e2d690516512020171c1ec870f6ff45398cc8609250326be89915fb538e7b").to_vec();
+        assert_eq!(
+            IndexTrie::new(vec![receipt]).unwrap().root(),
+            H256(hex!("fe815f93891907b401edd491a8601cb732dabe909f0bf74aace83d997e02918f"))
+        );
+    }
+
+    #[test]
+    fn test_index_proofs() {
+        let items = (0..300u64)
+            .map(|i| match i % 2 {
+                0 => rlp::encode_list::<u64, _>(&[i, i + 1]).to_vec(),
+                _ => [vec![0x02], rlp::encode_list::<u64, _>(&[i]).to_vec()].concat(),
+            })
+            .collect::<Vec<_>>();
+        let trie = IndexTrie::new(items.clone()).unwrap();
+        assert_eq!(trie.len(), 300);
+
+        for index in [0, 1, 0x7f, 0x80, 0x81, 299] {
+            let proof = trie.prove(index).unwrap();
+            assert_eq!(proof.key, index_key(index));
+            assert_eq!(proof.value, items[index]);
+            assert!(proof.verify(trie.root()));
+
+            let mut wrong = proof.clone();
+            wrong.value.push(0);
+            assert!(!wrong.verify(trie.root()));
+        }
+
+        assert_eq!(index_key(0), vec![0x80]);
+        assert_eq!(index_key(13000), hex!("8232c8").to_vec());
+        assert_eq!(trie.prove(300), None);
+    }
+}
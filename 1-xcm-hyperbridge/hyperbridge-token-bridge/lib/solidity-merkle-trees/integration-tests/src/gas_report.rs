@@ -0,0 +1,392 @@
+#![cfg(test)]
+//! Gas used by the verifiers over a sweep of proof sizes.
+//!
+//! `cargo test --release gas_report` runs the sweep and fails if any entry costs more than
+//! [`TOLERANCE_PERCENT`] above its entry in `gas-baseline.json`, or has no entry there.
+//!
+//! - `GAS_REPORT_DIR=<dir>` also writes the sweep to `gas-report.json` and `gas-report.csv`.
+//! - `UPDATE_GAS_BASELINE=1` replaces the baseline with the current sweep.
+
+use crate::{
+    keccak256,
+    mmr_proof::{calculate_root_args, generate_mmr_proof},
+    positional_merkle::{tree_height, Node, PositionalMerkleTree},
+    test_env::{call_with_gas, GasUsed, TestEnv},
+    Keccak256, Token, U256,
+};
+use eth_trie_db::{
+    Trie as EthTrie, TrieDBBuilder as EthTrieDBBuilder,
+    TrieDBMutBuilder as EthTrieDBMutBuilder, TrieMut as EthTrieMut,
+};
+use patricia_merkle_trie::{keccak::KeccakHasher as EthKeccakHasher, EIP1186Layout};
+use primitive_types::H256;
+use rs_merkle::MerkleTree;
+use serde::{Deserialize, Serialize};
+use sp_core::KeccakHasher;
+use sp_trie::{LayoutV0, MemoryDB};
+use std::{collections::BTreeSet, env, fs, path::PathBuf};
+use trie_db::{Hasher, Recorder, Trie, TrieDBBuilder, TrieDBMutBuilder, TrieMut};
+
+/// How much more gas than the baseline an entry may use before it counts as a regression
+const TOLERANCE_PERCENT: u64 = 2;
+
+/// Number of leaves (or trie entries) in the swept trees
+const TREE_SIZES: [u64; 3] = [16, 256, 4096];
+
+/// Number of leaves (or keys) proven in each tree
+const PROVEN_COUNTS: [u64; 4] = [1, 4, 16, 64];
+
+/// Generates the root and proof nodes for some keys of a trie
+type ProofGenerator = fn(&[(Vec<u8>, Vec<u8>)], &[Vec<u8>]) -> (Vec<u8>, Vec<Vec<u8>>);
+
+/// A single measurement of the sweep
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct GasReportRow {
+    /// The verifier that was measured
+    pub benchmark: String,
+    /// Number of leaves or trie entries
+    pub leaves: u64,
+    /// Number of leaves or keys proven
+    pub proven: u64,
+    /// Number of nodes in the proof
+    pub proof_nodes: u64,
+    /// Gas spent executing the verifier
+    pub execution_gas: u64,
+    /// Gas spent on calldata
+    pub calldata_gas: u64,
+}
+
+impl GasReportRow {
+    fn new(benchmark: &str, leaves: u64, proven: u64, proof_nodes: usize, gas: GasUsed) -> Self {
+        GasReportRow {
+            benchmark: benchmark.to_string(),
+            leaves,
+            proven,
+            proof_nodes: proof_nodes as u64,
+            execution_gas: gas.execution,
+            calldata_gas: gas.calldata,
+        }
+    }
+
+    fn total_gas(&self) -> u64 {
+        self.execution_gas + self.calldata_gas
+    }
+
+    fn is_same_case(&self, other: &GasReportRow) -> bool {
+        self.benchmark == other.benchmark &&
+            self.leaves == other.leaves &&
+            self.proven == other.proven
+    }
+}
+
+/// Evenly spread indices of `proven` out of `leaves` leaves
+fn spread_indices(leaves: u64, proven: u64) -> Vec<u64> {
+    (0..proven).map(|i| i * leaves / proven).collect()
+}
+
+/// Sizes of the sweep, skipping those that would prove more leaves than the tree has
+fn sweep_sizes() -> impl Iterator<Item = (u64, u64)> {
+    TREE_SIZES.into_iter().flat_map(|leaves| {
+        PROVEN_COUNTS
+            .into_iter()
+            .filter(move |proven| *proven <= leaves)
+            .map(move |proven| (leaves, proven))
+    })
+}
+
+/// Keys and values of a trie with `count` entries, with keccak-hashed keys
+fn trie_entries(count: u64) -> Vec<(Vec<u8>, Vec<u8>)> {
+    (0..count)
+        .map(|i| {
+            let key = keccak256(&i.to_le_bytes()).to_vec();
+            let value = keccak256(&key).to_vec();
+            (key, value)
+        })
+        .collect()
+}
+
+async fn sweep_mmr(rows: &mut Vec<GasReportRow>) {
+    let mut env = TestEnv::shared().await;
+    let mut contract = env.contract("MerkleMountainRangeTest").await;
+
+    for (leaves, proven) in sweep_sizes() {
+        let elems = spread_indices(leaves, proven).into_iter().map(|i| i as u32).collect();
+        let (root, proof_items, custom_leaves) = generate_mmr_proof(leaves as u32, elems);
+        let proof_nodes = proof_items.len();
+
+        let (calculated, gas) = call_with_gas::<_, [u8; 32]>(
+            &mut contract,
+            "CalculateRootWithGas",
+            calculate_root_args(custom_leaves, proof_items, leaves),
+        )
+        .await;
+        assert_eq!(calculated, root);
+
+        rows.push(GasReportRow::new("mmr_calculate_root", leaves, proven, proof_nodes, gas));
+    }
+}
+
+async fn sweep_multi_proof(rows: &mut Vec<GasReportRow>) {
+    let mut env = TestEnv::shared().await;
+    let mut contract = env.contract("MerkleMultiProofTest").await;
+
+    for (leaves, proven) in sweep_sizes() {
+        let leaf_hashes =
+            (0..leaves).map(|i| keccak256(&i.to_le_bytes())).collect::<Vec<[u8; 32]>>();
+        let indices =
+            spread_indices(leaves, proven).into_iter().map(|i| i as usize).collect::<Vec<_>>();
+        let tree = MerkleTree::<Keccak256>::from_leaves(&leaf_hashes);
+
+        let proof = tree.proof_2d(&indices);
+        let proof_nodes = proof.iter().map(|layer| layer.len()).sum();
+        let layers = proof
+            .into_iter()
+            .map(|layer| {
+                Token::Array(
+                    layer
+                        .into_iter()
+                        .map(|(index, node)| {
+                            Token::Tuple(vec![
+                                Token::Uint(U256::from(index)),
+                                Token::FixedBytes(node.to_vec()),
+                            ])
+                        })
+                        .collect(),
+                )
+            })
+            .collect::<Vec<_>>();
+        let proof_leaves = indices
+            .iter()
+            .map(|i| {
+                Token::Tuple(vec![
+                    Token::Uint(U256::from(*i)),
+                    Token::FixedBytes(leaf_hashes[*i].to_vec()),
+                ])
+            })
+            .collect::<Vec<_>>();
+
+        let (calculated, gas) = call_with_gas::<_, [u8; 32]>(
+            &mut contract,
+            "CalculateRootWithGas",
+            (layers, proof_leaves),
+        )
+        .await;
+        assert_eq!(Some(calculated), tree.root());
+
+        rows.push(GasReportRow::new("calculate_root_2d", leaves, proven, proof_nodes, gas));
+    }
+
+    for (leaves, proven) in sweep_sizes() {
+        let leaf_hashes =
+            (0..leaves).map(|i| H256(keccak256(&i.to_le_bytes()))).collect::<Vec<_>>();
+        let indices =
+            spread_indices(leaves, proven).into_iter().map(|i| i as usize).collect::<Vec<_>>();
+        let tree = PositionalMerkleTree::new(&leaf_hashes).unwrap();
+
+        let mut proof_items = vec![];
+        for mut layer in tree.generate_multi_proof(&indices).unwrap().into_iter().rev() {
+            layer.sort_by_key(|node| node.position);
+            proof_items.extend_from_slice(&layer);
+        }
+
+        let height = tree_height(leaves);
+        let proof_leaves = indices
+            .iter()
+            .map(|&i| Node { hash: leaf_hashes[i], position: 2usize.pow(height as u32) + i })
+            .collect::<Vec<_>>();
+
+        let node_token = |node: &Node| {
+            Token::Tuple(vec![
+                Token::Uint(U256::from(node.position)),
+                Token::FixedBytes(node.hash.0.to_vec()),
+            ])
+        };
+        let (calculated, gas) = call_with_gas::<_, [u8; 32]>(
+            &mut contract,
+            "CalculateBalancedRootWithGas",
+            (
+                proof_items.iter().map(node_token).collect::<Vec<_>>(),
+                proof_leaves.iter().map(node_token).collect::<Vec<_>>(),
+                Token::Uint(U256::from(leaves)),
+            ),
+        )
+        .await;
+        assert_eq!(H256(calculated), tree.root());
+
+        rows.push(GasReportRow::new(
+            "calculate_balanced_root",
+            leaves,
+            proven,
+            proof_items.len(),
+            gas,
+        ));
+    }
+}
+
+/// Proves `keys` from a substrate trie with `LayoutV0<H>`, with the proof nodes sorted so the gas
+/// used does not depend on their order.
+pub fn substrate_proof<H: Hasher>(
+    entries: &[(Vec<u8>, Vec<u8>)],
+    keys: &[Vec<u8>],
+) -> (Vec<u8>, Vec<Vec<u8>>) {
+    let mut db = MemoryDB::<H>::default();
+    let mut root = Default::default();
+    {
+        let mut trie = TrieDBMutBuilder::<LayoutV0<H>>::new(&mut db, &mut root).build();
+        for (key, value) in entries {
+            trie.insert(key, value).unwrap();
+        }
+    }
+
+    let mut recorder = Recorder::<LayoutV0<H>>::new();
+    {
+        let trie =
+            TrieDBBuilder::<LayoutV0<H>>::new(&db, &root).with_recorder(&mut recorder).build();
+        for key in keys {
+            trie.get(key).unwrap().unwrap();
+        }
+    }
+    let proof = recorder.drain().into_iter().map(|record| record.data).collect::<BTreeSet<_>>();
+
+    (root.as_ref().to_vec(), proof.into_iter().collect())
+}
+
+/// Proves `keys` from an ethereum trie, with the proof nodes sorted so the gas used does not
+/// depend on their order.
+pub fn ethereum_proof(
+    entries: &[(Vec<u8>, Vec<u8>)],
+    keys: &[Vec<u8>],
+) -> (Vec<u8>, Vec<Vec<u8>>) {
+    type Layout = EIP1186Layout<EthKeccakHasher>;
+
+    let mut db = patricia_merkle_trie::MemoryDB::<EthKeccakHasher>::default();
+    let mut root = Default::default();
+    {
+        let mut trie = EthTrieDBMutBuilder::<Layout>::new(&mut db, &mut root).build();
+        for (key, value) in entries {
+            trie.insert(key, value).unwrap();
+        }
+    }
+
+    let mut recorder = eth_trie_db::Recorder::<Layout>::new();
+    {
+        let trie =
+            EthTrieDBBuilder::<Layout>::new(&db, &root).with_recorder(&mut recorder).build();
+        for key in keys {
+            trie.get(key).unwrap().unwrap();
+        }
+    }
+    let proof = recorder.drain().into_iter().map(|record| record.data).collect::<BTreeSet<_>>();
+
+    (root.as_ref().to_vec(), proof.into_iter().collect())
+}
+
+async fn sweep_patricia(rows: &mut Vec<GasReportRow>) {
+    let mut env = TestEnv::shared().await;
+    let mut contract = env.contract("MerklePatriciaTest").await;
+
+    let verifiers: [(&str, &'static str, ProofGenerator); 2] = [
+        ("verify_keys", "VerifyKeysWithGas", substrate_proof::<KeccakHasher>),
+        ("verify_ethereum", "VerifyEthereumWithGas", ethereum_proof),
+    ];
+
+    for (benchmark, func, generate_proof) in verifiers {
+        for (leaves, proven) in sweep_sizes() {
+            let entries = trie_entries(leaves);
+            let proven_entries = spread_indices(leaves, proven)
+                .into_iter()
+                .map(|i| entries[i as usize].clone())
+                .collect::<Vec<_>>();
+            let keys = proven_entries.iter().map(|(key, _)| key.clone()).collect::<Vec<_>>();
+            let (root, proof) = generate_proof(&entries, &keys);
+
+            let (values, gas) = call_with_gas::<_, Vec<(Vec<u8>, Vec<u8>)>>(
+                &mut contract,
+                func,
+                (
+                    Token::FixedBytes(root),
+                    Token::Array(proof.clone().into_iter().map(Token::Bytes).collect()),
+                    Token::Array(keys.into_iter().map(Token::Bytes).collect()),
+                ),
+            )
+            .await;
+            assert_eq!(values, proven_entries);
+
+            rows.push(GasReportRow::new(benchmark, leaves, proven, proof.len(), gas));
+        }
+    }
+}
+
+fn write_csv(path: PathBuf, rows: &[GasReportRow]) {
+    let mut csv =
+        "benchmark,leaves,proven,proof_nodes,execution_gas,calldata_gas,total_gas\n".to_string();
+    for row in rows {
+        csv += &format!(
+            "{},{},{},{},{},{},{}\n",
+            row.benchmark,
+            row.leaves,
+            row.proven,
+            row.proof_nodes,
+            row.execution_gas,
+            row.calldata_gas,
+            row.total_gas()
+        );
+    }
+    fs::write(path, csv).unwrap();
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_gas_report() {
+    let mut rows = vec![];
+    sweep_mmr(&mut rows).await;
+    sweep_multi_proof(&mut rows).await;
+    sweep_patricia(&mut rows).await;
+
+    let report = serde_json::to_string_pretty(&rows).unwrap();
+    if let Ok(dir) = env::var("GAS_REPORT_DIR") {
+        let dir = PathBuf::from(dir);
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(dir.join("gas-report.json"), &report).unwrap();
+        write_csv(dir.join("gas-report.csv"), &rows);
+    }
+
+    let baseline_path = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("gas-baseline.json");
+    if env::var("UPDATE_GAS_BASELINE").is_ok() {
+        fs::write(&baseline_path, report).unwrap();
+        return
+    }
+
+    let baseline = fs::read_to_string(&baseline_path).unwrap();
+    let baseline = serde_json::from_str::<Vec<GasReportRow>>(&baseline).unwrap();
+
+    let mut regressions = vec![];
+    let mut missing = vec![];
+    for row in &rows {
+        match baseline.iter().find(|entry| entry.is_same_case(row)) {
+            Some(entry) if row.total_gas() * 100 > entry.total_gas() * (100 + TOLERANCE_PERCENT) =>
+                regressions.push(format!(
+                    "{} ({} leaves, {} proven): {} gas, baseline {}",
+                    row.benchmark,
+                    row.leaves,
+                    row.proven,
+                    row.total_gas(),
+                    entry.total_gas()
+                )),
+            Some(_) => {},
+            None => missing.push(format!(
+                "{} ({} leaves, {} proven): {} gas",
+                row.benchmark,
+                row.leaves,
+                row.proven,
+                row.total_gas()
+            )),
+        }
+    }
+
+    assert!(
+        missing.is_empty(),
+        "no baseline, run with UPDATE_GAS_BASELINE=1 to add:\n{}",
+        missing.join("\n")
+    );
+    assert!(regressions.is_empty(), "gas regressions:\n{}", regressions.join("\n"));
+}
@@ -1,9 +1,29 @@
 #![allow(unused_parens, dead_code)]
 
+pub mod adversarial_trie;
+pub mod child_trie;
+pub mod compact_proof;
+pub mod eth_proof_fixture;
+pub mod ethereum_trie;
+pub mod gas_report;
 pub mod merkle_mountain_range;
 pub mod merkle_multi_proof;
 pub mod merkle_patricia;
+pub mod minimal_proof;
+pub mod mmr_peaks;
+pub mod mmr_proof;
+pub mod mmr_utils;
+pub mod native_verifier;
 pub mod positional_merkle;
+pub mod proof_mutation;
+pub mod rlp_item;
+pub mod scale_codec;
+pub mod state_dump;
+pub mod storage_key;
+pub mod storage_slot;
+pub mod storage_value;
+pub mod test_env;
+pub mod trie_node;
 
 use ckb_merkle_mountain_range::{Error, Merge};
 pub use ethers::{abi::Token, types::U256, utils::keccak256};
@@ -1,20 +1,28 @@
 #![cfg(test)]
 
-use crate::{MergeKeccak, NumberHash, Token};
-use ckb_merkle_mountain_range::{mmr_position_to_k_index, util::MemStore, MMR};
-use forge_testsuite::{Contract, Runner};
+use crate::{
+    mmr_proof::{calculate_root_args, generate_mmr_proof, native_calculate_root},
+    mmr_utils,
+    proof_mutation::{MmrProof, MutationReport},
+    test_env::{self, TestEnv},
+    Token,
+};
+use forge_testsuite::Contract;
 use hex_literal::hex;
 use primitive_types::U256;
-use proptest::{prop_compose, proptest};
-use std::{env, path::PathBuf};
+use proptest::{
+    collection::{btree_set, vec},
+    prop_assert_eq, prop_compose, proptest,
+    strategy::Just,
+    test_runner::TestCaseError,
+};
 
 type MmrLeaf = (u64, u64, [u8; 32]);
 
 #[tokio::test(flavor = "multi_thread")]
 async fn test_mmr_utils() {
-    let base_dir = env::current_dir().unwrap().parent().unwrap().display().to_string();
-    let mut runner = Runner::new(PathBuf::from(&base_dir));
-    let mut contract = runner.deploy("MerkleMountainRangeTest").await;
+    let mut env = TestEnv::shared().await;
+    let mut contract = env.contract("MerkleMountainRangeTest").await;
 
     {
         let left = vec![3, 4].into_iter().map(|n| Token::Uint(U256::from(n))).collect();
@@ -82,120 +90,63 @@ pub async fn solidity_calculate_root(
     proof_items: Vec<Vec<u8>>,
     mmr_size: u64,
 ) -> [u8; 32] {
-    let token_leaves = custom_leaves
-        .into_iter()
-        .map(|(pos, index, hash)| {
-            Token::Tuple(vec![
-                Token::Uint(U256::from(index)),
-                Token::Uint(U256::from(pos)),
-                Token::FixedBytes(hash.to_vec()),
-            ])
-        })
-        .collect::<Vec<_>>();
-
-    let nodes = proof_items.iter().map(|n| Token::FixedBytes(n.clone())).collect::<Vec<_>>();
-
     contract
-        .call::<_, [u8; 32]>("CalculateRoot", (nodes, token_leaves, Token::Uint(mmr_size.into())))
+        .call::<_, [u8; 32]>(
+            "CalculateRoot",
+            calculate_root_args(custom_leaves, proof_items, mmr_size),
+        )
         .await
         .unwrap()
 }
 
-pub async fn test_mmr(contract: &mut Contract<'_>, count: u32, mut proof_elem: Vec<u32>) {
-    proof_elem.sort();
-    let store = MemStore::default();
-    let mut mmr = MMR::<_, MergeKeccak, _>::new(0, &store);
-
-    let positions: Vec<u64> =
-        (0u32..count).map(|i| mmr.push(NumberHash::from(i)).unwrap()).collect();
-
-    let root = mmr.get_root().expect("get root");
-    let proof = mmr
-        .gen_proof(proof_elem.iter().map(|elem| positions[*elem as usize]).collect())
-        .expect("gen proof");
-    mmr.commit().expect("commit changes");
-
-    let leaves = proof_elem
-        .iter()
-        .map(|elem| (positions[*elem as usize], NumberHash::from(*elem)))
-        .collect::<Vec<_>>();
-    let result = proof.verify(root.clone(), leaves.clone()).unwrap();
-    assert!(result);
-
-    // simplified proof verification
-
-    let mut custom_leaves = leaves
-        .into_iter()
-        .zip(proof_elem.clone().into_iter())
-        .map(|((pos, leaf), index)| {
-            let k_index = mmr_position_to_k_index(vec![pos], proof.mmr_size())[0].1;
-            let mut hash = [0u8; 32];
-            hash.copy_from_slice(&leaf.0);
-            (index, k_index, hash)
-        })
-        .collect::<Vec<_>>();
-
-    custom_leaves.dedup_by(|a, b| a.0 == b.0);
-    custom_leaves.sort_by(|a, b| a.0.cmp(&b.0));
+pub async fn test_mmr(contract: &mut Contract<'_>, count: u32, proof_elem: Vec<u32>) {
+    let (root_hash, proof_items, custom_leaves) = generate_mmr_proof(count, proof_elem);
 
-    let calculated = solidity_calculate_root(
-        contract,
-        custom_leaves,
-        proof.proof_items().to_vec().into_iter().map(|n| n.0).collect(),
-        count as u64,
-    )
-    .await;
+    let calculated =
+        solidity_calculate_root(contract, custom_leaves, proof_items, count as u64).await;
 
-    let mut root_hash = [0u8; 32];
-    root_hash.copy_from_slice(&root.0);
     assert_eq!(root_hash, calculated);
 }
 
 #[tokio::test(flavor = "multi_thread")]
 async fn test_mmr_3_peaks() {
-    let base_dir = env::current_dir().unwrap().parent().unwrap().display().to_string();
-    let mut runner = Runner::new(PathBuf::from(&base_dir));
-    let mut contract = runner.deploy("MerkleMountainRangeTest").await;
+    let mut env = TestEnv::shared().await;
+    let mut contract = env.contract("MerkleMountainRangeTest").await;
     test_mmr(&mut contract, 11, vec![5]).await;
 }
 
 #[tokio::test(flavor = "multi_thread")]
 async fn test_mmr_2_peaks() {
-    let base_dir = env::current_dir().unwrap().parent().unwrap().display().to_string();
-    let mut runner = Runner::new(PathBuf::from(&base_dir));
-    let mut contract = runner.deploy("MerkleMountainRangeTest").await;
+    let mut env = TestEnv::shared().await;
+    let mut contract = env.contract("MerkleMountainRangeTest").await;
     test_mmr(&mut contract, 10, vec![5]).await;
 }
 
 #[tokio::test(flavor = "multi_thread")]
 async fn test_mmr_1_peak() {
-    let base_dir = env::current_dir().unwrap().parent().unwrap().display().to_string();
-    let mut runner = Runner::new(PathBuf::from(&base_dir));
-    let mut contract = runner.deploy("MerkleMountainRangeTest").await;
+    let mut env = TestEnv::shared().await;
+    let mut contract = env.contract("MerkleMountainRangeTest").await;
     test_mmr(&mut contract, 8, vec![5]).await;
 }
 
 #[tokio::test(flavor = "multi_thread")]
 async fn test_mmr_first_elem_proof() {
-    let base_dir = env::current_dir().unwrap().parent().unwrap().display().to_string();
-    let mut runner = Runner::new(PathBuf::from(&base_dir));
-    let mut contract = runner.deploy("MerkleMountainRangeTest").await;
+    let mut env = TestEnv::shared().await;
+    let mut contract = env.contract("MerkleMountainRangeTest").await;
     test_mmr(&mut contract, 11, vec![0]).await;
 }
 
 #[tokio::test(flavor = "multi_thread")]
 async fn test_mmr_last_elem_proof() {
-    let base_dir = env::current_dir().unwrap().parent().unwrap().display().to_string();
-    let mut runner = Runner::new(PathBuf::from(&base_dir));
-    let mut contract = runner.deploy("MerkleMountainRangeTest").await;
+    let mut env = TestEnv::shared().await;
+    let mut contract = env.contract("MerkleMountainRangeTest").await;
     test_mmr(&mut contract, 11, vec![10]).await;
 }
 
 #[tokio::test(flavor = "multi_thread")]
 async fn test_failing_case() {
-    let base_dir = env::current_dir().unwrap().parent().unwrap().display().to_string();
-    let mut runner = Runner::new(PathBuf::from(&base_dir));
-    let mut contract = runner.deploy("MerkleMountainRangeTest").await;
+    let mut env = TestEnv::shared().await;
+    let mut contract = env.contract("MerkleMountainRangeTest").await;
     let elem = vec![
         85, 120, 113, 104, 109, 6, 101, 97, 41, 95, 15, 52, 19, 82, 33, 102, 114, 70, 53, 32, 107,
         65, 59, 80, 72, 36, 64, 22, 16, 38, 57, 106, 74, 76, 28, 81, 117, 83, 61, 122, 1, 12, 14,
@@ -206,35 +157,31 @@ async fn test_failing_case() {
 
 #[tokio::test(flavor = "multi_thread")]
 async fn test_mmr_1_elem() {
-    let base_dir = env::current_dir().unwrap().parent().unwrap().display().to_string();
-    let mut runner = Runner::new(PathBuf::from(&base_dir));
-    let mut contract = runner.deploy("MerkleMountainRangeTest").await;
+    let mut env = TestEnv::shared().await;
+    let mut contract = env.contract("MerkleMountainRangeTest").await;
     test_mmr(&mut contract, 1, vec![0]).await;
 }
 
 #[tokio::test(flavor = "multi_thread")]
 async fn test_mmr_2_elems() {
-    let base_dir = env::current_dir().unwrap().parent().unwrap().display().to_string();
-    let mut runner = Runner::new(PathBuf::from(&base_dir));
-    let mut contract = runner.deploy("MerkleMountainRangeTest").await;
+    let mut env = TestEnv::shared().await;
+    let mut contract = env.contract("MerkleMountainRangeTest").await;
     test_mmr(&mut contract, 2, vec![0]).await;
     test_mmr(&mut contract, 2, vec![1]).await;
 }
 
 #[tokio::test(flavor = "multi_thread")]
 async fn test_mmr_2_leaves_merkle_proof() {
-    let base_dir = env::current_dir().unwrap().parent().unwrap().display().to_string();
-    let mut runner = Runner::new(PathBuf::from(&base_dir));
-    let mut contract = runner.deploy("MerkleMountainRangeTest").await;
+    let mut env = TestEnv::shared().await;
+    let mut contract = env.contract("MerkleMountainRangeTest").await;
     test_mmr(&mut contract, 11, vec![3, 7]).await;
     test_mmr(&mut contract, 11, vec![3, 4]).await;
 }
 
 #[tokio::test(flavor = "multi_thread")]
 async fn test_mmr_2_sibling_leaves_merkle_proof() {
-    let base_dir = env::current_dir().unwrap().parent().unwrap().display().to_string();
-    let mut runner = Runner::new(PathBuf::from(&base_dir));
-    let mut contract = runner.deploy("MerkleMountainRangeTest").await;
+    let mut env = TestEnv::shared().await;
+    let mut contract = env.contract("MerkleMountainRangeTest").await;
     test_mmr(&mut contract, 11, vec![4, 5]).await;
     test_mmr(&mut contract, 11, vec![5, 6]).await;
     test_mmr(&mut contract, 11, vec![6, 7]).await;
@@ -242,9 +189,8 @@ async fn test_mmr_2_sibling_leaves_merkle_proof() {
 
 #[tokio::test(flavor = "multi_thread")]
 async fn test_mmr_3_leaves_merkle_proof() {
-    let base_dir = env::current_dir().unwrap().parent().unwrap().display().to_string();
-    let mut runner = Runner::new(PathBuf::from(&base_dir));
-    let mut contract = runner.deploy("MerkleMountainRangeTest").await;
+    let mut env = TestEnv::shared().await;
+    let mut contract = env.contract("MerkleMountainRangeTest").await;
     test_mmr(&mut contract, 11, vec![4, 5, 6]).await;
     test_mmr(&mut contract, 11, vec![3, 5, 7]).await;
     test_mmr(&mut contract, 11, vec![3, 4, 5]).await;
@@ -253,12 +199,38 @@ async fn test_mmr_3_leaves_merkle_proof() {
 
 #[tokio::test(flavor = "multi_thread")]
 async fn test_gen_proof_with_duplicate_leaves() {
-    let base_dir = env::current_dir().unwrap().parent().unwrap().display().to_string();
-    let mut runner = Runner::new(PathBuf::from(&base_dir));
-    let mut contract = runner.deploy("MerkleMountainRangeTest").await;
+    let mut env = TestEnv::shared().await;
+    let mut contract = env.contract("MerkleMountainRangeTest").await;
     test_mmr(&mut contract, 10, vec![5, 5]).await;
 }
 
+#[tokio::test(flavor = "multi_thread")]
+async fn test_mmr_mutated_proofs() {
+    let mut env = TestEnv::shared().await;
+    let mut contract = env.contract("MerkleMountainRangeTest").await;
+    let mut report = MutationReport::new("merkle_mountain_range");
+
+    for (count, elems) in [(15, vec![3, 7, 12]), (44, vec![0, 21, 22, 43])] {
+        let (root, items, leaves) = generate_mmr_proof(count, elems);
+        let proof = MmrProof::new(root, count as u64, leaves, items);
+        assert_eq!(native_calculate_root(&proof), Some(root));
+
+        for (mutation, variant) in proof.variants() {
+            let args = calculate_root_args(
+                variant.leaves.clone(),
+                variant.items.clone(),
+                variant.leaf_count,
+            );
+            let result = contract.call::<_, [u8; 32]>("CalculateRoot", args).await.ok();
+            report.record("CalculateRoot", &mutation, variant.to_json(), variant.judge(result));
+
+            let outcome = variant.judge(native_calculate_root(&variant));
+            report.record("MerkleProof::calculate_root", &mutation, variant.to_json(), outcome);
+        }
+    }
+    report.finish();
+}
+
 prop_compose! {
     fn count_elem(count: u32)
                 (elem in 0..count)
@@ -278,13 +250,93 @@ proptest! {
         leaves.shuffle(&mut rng);
         let leaves_count = rng.gen_range(1..count - 1);
         leaves.truncate(leaves_count as usize);
-        let runtime = tokio::runtime::Runtime::new().unwrap();
-        let base_dir = env::current_dir().unwrap().parent().unwrap().display().to_string();
-        let mut runner = Runner::new(PathBuf::from(&base_dir));
-        runtime.block_on(async move {
-            let mut contract = runner.deploy("MerkleMountainRangeTest").await;
+        test_env::block_on(async move {
+            let mut env = TestEnv::shared().await;
+            let mut contract = env.contract("MerkleMountainRangeTest").await;
 
             test_mmr(&mut contract, count, leaves).await;
         });
     }
 }
+
+fn uint_array(indices: &[u64]) -> Token {
+    Token::Array(indices.iter().map(|i| Token::Uint(U256::from(*i))).collect())
+}
+
+prop_compose! {
+    fn mmr_leaves()
+                (leaf_indices in btree_set(0u64..1_000, 0..20))
+                (k_indices in vec(0u64..512, leaf_indices.len()),
+                 leaf_indices in Just(leaf_indices))
+                -> Vec<mmr_utils::MmrLeaf> {
+                    leaf_indices
+                        .into_iter()
+                        .zip(k_indices)
+                        .map(|(leaf_index, k_index)| mmr_utils::MmrLeaf {
+                            k_index,
+                            leaf_index,
+                            hash: crate::keccak256(&leaf_index.to_le_bytes()),
+                        })
+                        .collect()
+    }
+}
+
+proptest! {
+    #[test]
+    fn test_random_mmr_utils(
+        left in vec(0u64..1_000, 0..20),
+        right in vec(0u64..1_000, 0..20),
+        leaf_count in 0u64..u64::MAX,
+        leaves in mmr_leaves(),
+        split in 0u64..1_000,
+    ) {
+        test_env::block_on(async move {
+            let mut env = TestEnv::shared().await;
+            let mut contract = env.contract("MerkleMountainRangeTest").await;
+
+            let result = contract
+                .call::<_, Vec<u64>>("difference", (uint_array(&left), uint_array(&right)))
+                .await
+                .unwrap();
+            prop_assert_eq!(result, mmr_utils::difference(&left, &right));
+
+            let result =
+                contract.call::<_, Vec<u64>>("siblingIndices", (uint_array(&left))).await.unwrap();
+            prop_assert_eq!(result, mmr_utils::sibling_indices(&left));
+
+            let result =
+                contract.call::<_, Vec<u64>>("parentIndices", (uint_array(&left))).await.unwrap();
+            prop_assert_eq!(result, mmr_utils::parent_indices(&left));
+
+            let result = contract
+                .call::<_, Vec<u64>>("subtreeHeights", (Token::Uint(U256::from(leaf_count))))
+                .await
+                .unwrap();
+            let heights = mmr_utils::subtree_heights(leaf_count);
+            prop_assert_eq!(result, heights.into_iter().map(u64::from).collect::<Vec<_>>());
+
+            let tokens = leaves.iter().map(|leaf| leaf.to_token()).collect::<Vec<_>>();
+            let result = contract
+                .call::<_, (Vec<(u64, [u8; 32])>, Vec<u64>)>("mmrLeafToNode", (tokens.clone()))
+                .await
+                .unwrap();
+            prop_assert_eq!(result, mmr_utils::mmr_leaf_to_node(&leaves));
+
+            let (left_leaves, right_leaves) = contract
+                .call::<_, (Vec<MmrLeaf>, Vec<MmrLeaf>)>(
+                    "leavesForPeak",
+                    (tokens, Token::Uint(U256::from(split))),
+                )
+                .await
+                .unwrap();
+            let (expected_left, expected_right) = mmr_utils::leaves_for_subtree(&leaves, split);
+            let into_leaves = |leaves: Vec<MmrLeaf>| {
+                leaves.into_iter().map(mmr_utils::MmrLeaf::from).collect::<Vec<_>>()
+            };
+            prop_assert_eq!(into_leaves(left_leaves), expected_left.to_vec());
+            prop_assert_eq!(into_leaves(right_leaves), expected_right.to_vec());
+
+            Ok::<_, TestCaseError>(())
+        })?;
+    }
+}
@@ -1,23 +1,26 @@
 #![cfg(test)]
 #![allow(dead_code, unused_imports, unused_variables, unused_assignments)]
 
-use crate::{keccak256, positional_merkle::*, Keccak256, Token};
+use crate::{
+    keccak256,
+    positional_merkle::*,
+    proof_mutation::{MultiProof, MutationReport, Outcome},
+    test_env::TestEnv,
+    Keccak256, Token,
+};
 use ethers::abi::{AbiEncode, Uint};
-use forge_testsuite::Runner;
 use primitive_types::{H256, U256};
 use rand::Rng;
 use rs_merkle::{merkelize_sorted, MerkleTree};
 use std::{
     collections::{HashMap, HashSet},
-    env, iter,
-    path::PathBuf,
+    iter,
 };
 
 #[tokio::test(flavor = "multi_thread")]
 async fn multi_merkle_proof() {
-    let base_dir = env::current_dir().unwrap().parent().unwrap().display().to_string();
-    let mut runner = Runner::new(PathBuf::from(&base_dir));
-    let mut contract = runner.deploy("MerkleMultiProofTest").await;
+    let mut env = TestEnv::shared().await;
+    let mut contract = env.contract("MerkleMultiProofTest").await;
 
     let num_leaves = 600;
     let threshold = ((num_leaves * 1) / 3) - 1;
@@ -126,9 +129,8 @@ async fn test_calculate_balanced_root() {
     dbg!(root);
     assert_eq!(root, H256(tree.root().unwrap()));
 
-    let base_dir = env::current_dir().unwrap().parent().unwrap().display().to_string();
-    let mut runner = Runner::new(PathBuf::from(&base_dir));
-    let mut contract = runner.deploy("MerkleMultiProofTest").await;
+    let mut env = TestEnv::shared().await;
+    let mut contract = env.contract("MerkleMultiProofTest").await;
 
     let abi_leaves = proof_leaves
         .iter()
@@ -169,3 +171,36 @@ async fn test_calculate_balanced_root() {
 
     assert_eq!(root, H256(calculated));
 }
+
+/// Abi-encodes the arguments of `CalculateRoot`
+fn calculate_root_args(proof: &MultiProof) -> (Vec<Token>, Vec<Token>) {
+    let node = |(index, hash): &(usize, [u8; 32])| {
+        Token::Tuple(vec![Token::Uint(U256::from(*index)), Token::FixedBytes(hash.to_vec())])
+    };
+    let layers = proof.layers.iter().map(|layer| Token::Array(layer.iter().map(node).collect()));
+
+    (layers.collect(), proof.leaves.iter().map(node).collect())
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_multi_merkle_proof_mutated_proofs() {
+    let mut env = TestEnv::shared().await;
+    let mut contract = env.contract("MerkleMultiProofTest").await;
+    let mut report = MutationReport::new("merkle_multi_proof");
+
+    let leaf_hashes = (0u32..64).map(|i| keccak256(i.to_le_bytes())).collect::<Vec<_>>();
+    let tree = MerkleTree::<Keccak256>::from_leaves(&leaf_hashes);
+    for indices in [vec![5], vec![3, 10, 11, 40], vec![0, 63]] {
+        let leaves = indices.iter().map(|i| (*i, leaf_hashes[*i])).collect();
+        let proof = MultiProof::new(tree.root().unwrap(), tree.proof_2d(&indices), leaves);
+        let result = contract.call::<_, [u8; 32]>("CalculateRoot", calculate_root_args(&proof));
+        assert_eq!(proof.judge(result.await.ok()), Outcome::Accepted);
+
+        for (mutation, variant) in proof.variants() {
+            let args = calculate_root_args(&variant);
+            let result = contract.call::<_, [u8; 32]>("CalculateRoot", args).await.ok();
+            report.record("CalculateRoot", &mutation, variant.to_json(), variant.judge(result));
+        }
+    }
+    report.finish();
+}
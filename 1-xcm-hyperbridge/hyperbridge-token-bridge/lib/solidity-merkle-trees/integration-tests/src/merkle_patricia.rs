@@ -1,57 +1,66 @@
 #![cfg(test)]
 #![allow(dead_code, unused_imports)]
 
-use codec::Decode;
-use ethers::abi::{Token, Uint};
-use forge_testsuite::Runner;
+use crate::{
+    child_trie::ChildTrieBuilder,
+    compact_proof,
+    eth_proof_fixture::{header_hash, EthProofFixture},
+    ethereum_trie::{
+        secure_key, Account, EthereumTrie, IndexTrie, StateTrieBuilder, EMPTY_CODE_HASH,
+        EMPTY_ROOT,
+    },
+    keccak256,
+    minimal_proof::{minimise_ethereum, minimise_substrate},
+    native_verifier::{
+        read_ethereum_proof, read_substrate_proof, verify_ethereum_proof, verify_substrate_proof,
+        KeyProof, VerifyError,
+    },
+    proof_mutation::{MutationReport, TrieKind, TrieProof},
+    rlp_item::RlpItem,
+    state_dump::StateDump,
+    storage_key::{keys_token, StorageHasher, StorageKey},
+    storage_slot::{bytes_data_slots, decode_bytes, encode_bytes, PackedField, StorageSlot},
+    storage_value::{
+        decode_account, decode_scale, decode_slot, AccountData, AccountInfo, ScaleRegistry,
+        Timestamp,
+    },
+    test_env::{self, TestEnv},
+    trie_node::{Leaf, NibbledBranch, NodeKind},
+};
+use codec::Encode;
+use ethers::{
+    abi::{Token, Uint},
+    types::{
+        transaction::eip2718::TypedTransaction, Address, Bytes, Eip1559TransactionRequest,
+        Eip2930TransactionRequest, Signature, TransactionRequest,
+    },
+};
+use forge_testsuite::Contract;
 use hex_literal::hex;
-use primitive_types::H256;
-use sp_core::KeccakHasher;
-use sp_trie::{LayoutV0, MemoryDB, NodeCodec, StorageProof};
-use std::{collections::HashSet, env, path::PathBuf};
+use primitive_types::{H256, U256};
+use proptest::{
+    collection::vec,
+    prelude::any,
+    prop_assert_eq, prop_compose, prop_oneof, proptest,
+    strategy::{Just, Strategy},
+    test_runner::TestCaseError,
+};
+use rlp::RlpStream;
+use sp_core::{storage::StateVersion, Blake2Hasher, KeccakHasher};
+use sp_trie::{LayoutV0, LayoutV1, MemoryDB, NodeCodec, StorageProof};
+use std::{
+    collections::BTreeSet,
+    fs,
+    panic::{self, UnwindSafe},
+    path::PathBuf,
+};
 use trie_db::{
-    DBValue, Hasher, NodeCodec as NodeCodecT, Recorder, Trie, TrieDBBuilder, TrieDBMutBuilder,
-    TrieLayout, TrieMut,
+    DBValue, Hasher, NibbleSlice, NodeCodec as NodeCodecT, Recorder, Trie, TrieDBBuilder,
+    TrieDBMutBuilder, TrieLayout, TrieMut,
 };
 
-type Slice = (
-    Vec<u8>, // data;
-    Uint,    // offset;
-);
-
-type NodeHandle = (
-    bool,     // isHash;
-    [u8; 32], // hash;
-    bool,     // isInline;
-    Vec<u8>,  // inLine;
-);
-
-type NodeHandleOption = (
-    bool,       // isSome,
-    NodeHandle, // value
-);
-
-type NibbledBranch = (
-    Slice,                  // key;
-    NodeHandleOption,       // value;
-    [NodeHandleOption; 16], // children;
-);
-
-type NodeKind = (
-    bool, // isEmpty;
-    bool, // isLeaf;
-    bool, // isHashedLeaf;
-    bool, // isNibbledValueBranch;
-    bool, // isNibbledHashedValueBranch;
-    bool, // isNibbledBranch;
-    bool, // isExtension;
-    bool, // isBranch;
-    Uint, // nibbleSize;
-    Slice,
-);
-
 fn proof_data() -> ([u8; 32], Vec<Vec<u8>>, Vec<u8>) {
-    let key = hex!("f0c365c3cf59d671eb72da0e7a4113c49f1f0515f462cdcf84e0f1d6045dfcbb").to_vec();
+    let key = StorageKey::new("Timestamp", "Now").build();
     let proof = vec![
         hex!("802e98809b03c6ae83e3b70aa89acfe0947b3a18b5d35569662335df7127ab8fcb88c88780e5d1b21c5ecc2891e3467f6273f27ce2e73a292d6b8306197edfa97b3d965bd080c51e5f53a03d92ea8b2792218f152da738b9340c6eeb08581145825348bbdba480ad103a9320581c7747895a01d79d2fa5f103c4b83c5af10b0a13bc1749749523806eea23c0854ced8445a3338833e2401753fdcfadb3b56277f8f1af4004f73719806d990657a5b5c3c97b8a917d9f153cafc463acd90592f881bc071d6ba64e90b380346031472f91f7c44631224cb5e61fb29d530a9fafd5253551cbf43b7e97e79a").to_vec(),
         hex!("9f00c365c3cf59d671eb72da0e7a4113c41002505f0e7b9012096b41c4eb3aaf947f6ea429080000685f0f1f0515f462cdcf84e0f1d6045dfcbb2035e90c7f86010000").to_vec(),
@@ -64,32 +73,29 @@ fn proof_data() -> ([u8; 32], Vec<Vec<u8>>, Vec<u8>) {
 
 #[tokio::test(flavor = "multi_thread")]
 async fn test_decode_nibbled_branch() {
-    let base_dir = env::current_dir().unwrap().parent().unwrap().display().to_string();
-    let mut runner = Runner::new(PathBuf::from(&base_dir));
-    let mut contract = runner.deploy("MerklePatriciaTest").await;
+    let mut env = TestEnv::shared().await;
+    let mut contract = env.contract("MerklePatriciaTest").await;
 
     let (_, proof, _) = proof_data();
 
     for item in proof.clone() {
-        let plan = NodeCodec::<KeccakHasher>::decode_plan(&mut &item[..]).unwrap().build(&item);
+        let plan = NodeCodec::<KeccakHasher>::decode_plan(&mut &item[..]).unwrap();
 
-        println!("{:?}", plan);
-
-        let result = contract
+        let kind = contract
             .call::<_, NodeKind>("decodeNodeKind", (Token::Bytes(item.clone())))
             .await
             .unwrap();
 
-        assert!(result.5); // isNibbledBranch
+        assert!(kind.is_nibbled_branch);
+        assert_eq!(kind, NodeKind::from_plan(&plan, &item));
 
-        let result = contract
-            .call::<_, Token>("decodeNibbledBranch", (Token::Bytes(item.clone())))
+        let branch = contract
+            .call::<_, NibbledBranch>("decodeNibbledBranch", (Token::Bytes(item.clone())))
             .await
             .unwrap();
 
-        println!("decodeNibbledBranch: {:?}", &result);
-
-        println!("\n\n\n");
+        assert_eq!(Some(branch.clone()), NibbledBranch::from_plan(&plan, &item));
+        assert_eq!(branch.to_plan::<KeccakHasher>(), (plan, item));
     }
 }
 
@@ -102,28 +108,27 @@ async fn test_decode_leaf() {
             214, 144, 122, 134, 1, 0, 0,
         ],
     ];
-    let base_dir = env::current_dir().unwrap().parent().unwrap().display().to_string();
-    let mut runner = Runner::new(PathBuf::from(&base_dir));
-    let mut contract = runner.deploy("MerklePatriciaTest").await;
+    let mut env = TestEnv::shared().await;
+    let mut contract = env.contract("MerklePatriciaTest").await;
 
     for leaf in leaves {
-        let plan = NodeCodec::<KeccakHasher>::decode_plan(&mut &leaf[..]).unwrap().build(&leaf);
-
-        println!("{:#?}", plan);
+        let plan = NodeCodec::<KeccakHasher>::decode_plan(&mut &leaf[..]).unwrap();
 
-        let result = contract
+        let kind = contract
             .call::<_, NodeKind>("decodeNodeKind", (Token::Bytes(leaf.clone())))
             .await
             .unwrap();
 
-        assert!(result.1); // isLeaf
+        assert!(kind.is_leaf);
+        assert_eq!(kind, NodeKind::from_plan(&plan, &leaf));
 
         let result = contract
-            .call::<_, Token>("decodeLeaf", (Token::Bytes(leaf.clone())))
+            .call::<_, Leaf>("decodeLeaf", (Token::Bytes(leaf.clone())))
             .await
             .unwrap();
 
-        println!("decodeLeaf: {:?}", &result);
+        assert_eq!(Some(result.clone()), Leaf::from_plan(&plan, &leaf));
+        assert_eq!(result.to_plan::<KeccakHasher>(), (plan, leaf));
     }
 }
 
@@ -131,9 +136,8 @@ static D: &'static [u8; 3] = &[0x01u8, 0x23, 0x45];
 
 #[tokio::test(flavor = "multi_thread")]
 async fn test_nibble_slice_ops_basics() {
-    let base_dir = env::current_dir().unwrap().parent().unwrap().display().to_string();
-    let mut runner = Runner::new(PathBuf::from(&base_dir));
-    let mut contract = runner.deploy("MerklePatriciaTest").await;
+    let mut env = TestEnv::shared().await;
+    let mut contract = env.contract("MerklePatriciaTest").await;
 
     let result = contract
         .call::<_, Uint>(
@@ -193,9 +197,8 @@ async fn test_nibble_slice_ops_basics() {
 
 #[tokio::test(flavor = "multi_thread")]
 async fn test_nibble_slice_ops_mid() {
-    let base_dir = env::current_dir().unwrap().parent().unwrap().display().to_string();
-    let mut runner = Runner::new(PathBuf::from(&base_dir));
-    let mut contract = runner.deploy("MerklePatriciaTest").await;
+    let mut env = TestEnv::shared().await;
+    let mut contract = env.contract("MerklePatriciaTest").await;
     let nibble = contract
         .call::<_, Token>(
             "mid",
@@ -238,9 +241,8 @@ async fn test_nibble_slice_ops_mid() {
 
 #[tokio::test(flavor = "multi_thread")]
 async fn test_nibble_slice_ops_shared() {
-    let base_dir = env::current_dir().unwrap().parent().unwrap().display().to_string();
-    let mut runner = Runner::new(PathBuf::from(&base_dir));
-    let mut contract = runner.deploy("MerklePatriciaTest").await;
+    let mut env = TestEnv::shared().await;
+    let mut contract = env.contract("MerklePatriciaTest").await;
     let n = Token::Tuple(vec![Token::Bytes(D.to_vec()), Token::Uint(Uint::zero())]);
 
     let other = &[0x01u8, 0x23, 0x01, 0x23, 0x45, 0x67];
@@ -308,13 +310,205 @@ async fn test_nibble_slice_ops_shared() {
     assert_eq!(result, Uint::from(0));
 }
 
+/// A `NibbleSlice` as `(data, offset)`
+type Nibbles = (Vec<u8>, usize);
+
+fn nibble_token((data, offset): &Nibbles) -> Token {
+    Token::Tuple(vec![Token::Bytes(data.clone()), Token::Uint(Uint::from(*offset))])
+}
+
+fn nibble_slice((data, offset): &Nibbles) -> NibbleSlice<'_> {
+    NibbleSlice::new_offset(data, *offset)
+}
+
+/// Runs `op` against `trie_db`, mapping a panic to `None` the same way a revert maps to `Err`.
+fn reference<T>(op: impl FnOnce() -> T + UnwindSafe) -> Option<T> {
+    panic::catch_unwind(op).ok()
+}
+
+prop_compose! {
+    // offsets run up to a few nibbles past the end of the data
+    fn nibbles()
+              (data in vec(any::<u8>(), 0..32))
+              (offset in 0..data.len() * 2 + 4, data in Just(data))
+              -> Nibbles {
+                  (data, offset)
+    }
+}
+
+prop_compose! {
+    // the second slice starts with a copy of some of the first's bytes, so that aligned slices
+    // share long prefixes
+    fn nibbles_pair()
+                   (a in nibbles(), b in nibbles())
+                   (shared in 0..=a.0.len(), same_offset in any::<bool>(),
+                    a in Just(a), b in Just(b))
+                   -> (Nibbles, Nibbles) {
+                       let mut data = a.0[..shared].to_vec();
+                       data.extend(b.0);
+                       let offset = if same_offset { a.1 } else { b.1 };
+                       (a, (data, offset))
+    }
+}
+
+proptest! {
+    #[test]
+    fn test_nibble_slice_ops_differential((a, b) in nibbles_pair(), i in 0usize..70) {
+        test_env::block_on(async move {
+            let mut env = TestEnv::shared().await;
+            let mut contract = env.contract("MerklePatriciaTest").await;
+            let (n, m) = (nibble_token(&a), nibble_token(&b));
+
+            let result = contract.call::<_, Uint>("nibbleLen", (n.clone(),)).await.ok();
+            prop_assert_eq!(result.map(|len| len.as_usize()), reference(|| nibble_slice(&a).len()));
+
+            let result = contract
+                .call::<_, Uint>("nibbleAt", (n.clone(), Token::Uint(Uint::from(i))))
+                .await
+                .ok();
+            prop_assert_eq!(
+                result.map(|nibble| nibble.as_u32() as u8),
+                reference(|| nibble_slice(&a).at(i))
+            );
+
+            let mid = contract
+                .call::<_, Token>("mid", (n.clone(), Token::Uint(Uint::from(i))))
+                .await
+                .unwrap();
+            prop_assert_eq!(&mid, &nibble_token(&(a.0.clone(), a.1 + i)));
+            let result = contract.call::<_, Uint>("nibbleLen", (mid,)).await.ok();
+            prop_assert_eq!(
+                result.map(|len| len.as_usize()),
+                reference(|| nibble_slice(&a).mid(i).len())
+            );
+
+            for (x, y, left, right) in [(&n, &m, &a, &b), (&m, &n, &b, &a)] {
+                let result = contract
+                    .call::<_, Uint>("commonPrefix", (x.clone(), y.clone()))
+                    .await
+                    .ok();
+                prop_assert_eq!(
+                    result.map(|len| len.as_usize()),
+                    reference(|| nibble_slice(left).common_prefix(&nibble_slice(right)))
+                );
+
+                let result =
+                    contract.call::<_, bool>("startsWith", (x.clone(), y.clone())).await.ok();
+                prop_assert_eq!(
+                    result,
+                    reference(|| nibble_slice(left).starts_with(&nibble_slice(right)))
+                );
+
+                let result = contract.call::<_, bool>("eq", (x.clone(), y.clone())).await.ok();
+                prop_assert_eq!(result, reference(|| nibble_slice(left) == nibble_slice(right)));
+            }
+
+            Ok::<_, TestCaseError>(())
+        })?;
+    }
+}
+
+/// Decodes `encoded` with `RLPReader`, returning `None` if the contract reverts.
+async fn decode_rlp(contract: &mut Contract<'_>, encoded: &[u8]) -> Option<Vec<u8>> {
+    contract.call::<_, Vec<u8>>("decodeRlp", (Token::Bytes(encoded.to_vec()))).await.ok()
+}
+
+fn rlp_items() -> impl Strategy<Value = RlpItem> {
+    // strings straddle the 55/56 byte boundary between the short and long forms
+    let bytes =
+        prop_oneof![vec(any::<u8>(), 0..2), vec(any::<u8>(), 54..58), vec(any::<u8>(), 0..300)];
+    bytes.prop_map(RlpItem::Bytes).prop_recursive(4, 256, 16, |inner| {
+        prop_oneof![vec(inner.clone(), 0..4), vec(inner, 0..64)].prop_map(RlpItem::List)
+    })
+}
+
+prop_compose! {
+    // truncates, overwrites or inserts a byte of a valid encoding
+    fn mutated_rlp()
+                  (encoded in rlp_items().prop_map(|item| rlp::encode(&item).to_vec()))
+                  (index in 0..encoded.len(), byte in any::<u8>(), mutation in 0u8..3,
+                   mut encoded in Just(encoded))
+                  -> Vec<u8> {
+                      match mutation {
+                          0 => encoded.truncate(index),
+                          1 => encoded[index] = byte,
+                          _ => encoded.insert(index, byte),
+                      }
+                      encoded
+    }
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_rlp_reader_edge_cases() {
+    let mut env = TestEnv::shared().await;
+    let mut contract = env.contract("MerklePatriciaTest").await;
+
+    let single_bytes = |count| RlpItem::List(vec![RlpItem::Bytes(vec![0x01]); count]);
+    let items = [
+        RlpItem::Bytes(vec![]),
+        RlpItem::Bytes(vec![0x00]),
+        RlpItem::Bytes(vec![0x7f]),
+        RlpItem::Bytes(vec![0x80]),
+        RlpItem::Bytes(vec![0xab; 55]),
+        RlpItem::Bytes(vec![0xab; 56]),
+        RlpItem::Bytes(vec![0xab; 255]),
+        RlpItem::Bytes(vec![0xab; 256]),
+        RlpItem::List(vec![]),
+        RlpItem::List(vec![RlpItem::List(vec![]); 3]),
+        // payloads of 55 and 56 bytes
+        single_bytes(55),
+        single_bytes(56),
+        // a payload with a two byte length
+        single_bytes(1000),
+    ];
+    for item in items {
+        let encoded = rlp::encode(&item).to_vec();
+        assert_eq!(decode_rlp(&mut contract, &encoded).await, Some(item.flatten()));
+    }
+
+    // the reader doesn't check that an encoding is canonical, and reads these like the short
+    // form they stand for
+    let byte = RlpItem::Bytes(vec![0x05]);
+    let non_canonical: [(&[u8], RlpItem); 3] = [
+        (&[0x81, 0x05], byte.clone()),
+        (&[0xb8, 0x01, 0x05], byte.clone()),
+        (&[0xf8, 0x01, 0x05], RlpItem::List(vec![byte])),
+    ];
+    for (encoded, item) in non_canonical {
+        assert!(RlpItem::decode(encoded).is_err());
+        assert_eq!(decode_rlp(&mut contract, encoded).await, Some(item.flatten()));
+    }
+    assert_eq!(decode_rlp(&mut contract, &[]).await, None);
+}
+
+proptest! {
+    #[test]
+    fn test_rlp_reader_differential(item in rlp_items(), mutated in mutated_rlp()) {
+        test_env::block_on(async move {
+            let mut env = TestEnv::shared().await;
+            let mut contract = env.contract("MerklePatriciaTest").await;
+
+            let encoded = rlp::encode(&item).to_vec();
+            prop_assert_eq!(decode_rlp(&mut contract, &encoded).await, Some(item.flatten()));
+
+            // a mutation can leave valid rlp, which must then be read the same way. The reader
+            // doesn't check lengths, so what it makes of anything else is undefined.
+            if let Ok(expected) = RlpItem::decode(&mutated) {
+                let result = decode_rlp(&mut contract, &mutated).await;
+                prop_assert_eq!(result, Some(expected.flatten()));
+            }
+
+            Ok::<_, TestCaseError>(())
+        })?;
+    }
+}
+
 #[tokio::test(flavor = "multi_thread")]
 async fn test_merkle_patricia_trie() {
     let (root, proof, key) = proof_data();
 
-    let base_dir = env::current_dir().unwrap().parent().unwrap().display().to_string();
-    let mut runner = Runner::new(PathBuf::from(&base_dir));
-    let mut contract = runner.deploy("MerklePatriciaTest").await;
+    let mut env = TestEnv::shared().await;
+    let mut contract = env.contract("MerklePatriciaTest").await;
 
     let result = contract
         .call::<_, Vec<(Vec<u8>, Vec<u8>)>>(
@@ -322,60 +516,159 @@ async fn test_merkle_patricia_trie() {
             (
                 Token::FixedBytes(root.to_vec()),
                 Token::Array(proof.clone().into_iter().map(Token::Bytes).collect()),
-                Token::Array(vec![Token::Bytes(key.to_vec())]),
+                keys_token(&[key]),
             ),
         )
         .await
         .unwrap();
-    dbg!(&result);
-    let timestamp = <u64>::decode(&mut &result[0].1[..]).unwrap();
-    assert_eq!(timestamp, 1_677_168_798_005)
+    let timestamp = decode_scale::<Timestamp>(&result[0].1).unwrap();
+    assert_eq!(timestamp, Timestamp(1_677_168_798_005))
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_merkle_patricia_trie_storage_keys() {
+    let (alice, bob) = ([1u8; 32], [2u8; 32]);
+    let commitment = H256::random();
+    let account = |nonce: u32, free: u128| AccountInfo {
+        nonce,
+        providers: 1,
+        data: AccountData { free, ..Default::default() },
+        ..Default::default()
+    };
+    let system_account = |who: &[u8; 32]| {
+        StorageKey::new("System", "Account").key(StorageHasher::Blake2_128Concat, who).build()
+    };
+
+    let entries = vec![
+        (StorageKey::new("Timestamp", "Now").build(), Timestamp(1_677_168_798_005).encode()),
+        (StorageKey::new("Balances", "TotalIssuance").build(), 3_000u128.encode()),
+        (system_account(&alice), account(7, 1_000).encode()),
+        (system_account(&bob), account(0, 2_000).encode()),
+        // double map
+        (
+            StorageKey::new("Assets", "Account")
+                .key(StorageHasher::Blake2_128Concat, &42u32)
+                .key(StorageHasher::Blake2_128Concat, &alice)
+                .build(),
+            500u128.encode(),
+        ),
+        (
+            StorageKey::new("Ismp", "RequestCommitments")
+                .key(StorageHasher::Identity, &commitment)
+                .build(),
+            alice.encode(),
+        ),
+        // n-map
+        (
+            StorageKey::new("Ismp", "StateCommitments")
+                .key(StorageHasher::Twox64Concat, &2000u32)
+                .key(StorageHasher::Identity, b"PARA")
+                .key(StorageHasher::Twox64Concat, &100u64)
+                .build(),
+            commitment.encode(),
+        ),
+    ];
+    let (root, proof, _) = generate_proof::<LayoutV0<KeccakHasher>>(&entries);
+
+    let mut env = TestEnv::shared().await;
+    let mut contract = env.contract("MerklePatriciaTest").await;
+
+    let keys = entries.iter().map(|(key, _)| key.clone()).collect::<Vec<_>>();
+    let result = contract
+        .call::<_, Vec<(Vec<u8>, Vec<u8>)>>(
+            "VerifyKeys",
+            (
+                Token::FixedBytes(root.as_bytes().to_vec()),
+                Token::Array(proof.into_iter().map(Token::Bytes).collect()),
+                keys_token(&keys),
+            ),
+        )
+        .await
+        .unwrap();
+
+    for ((key, value), (_, result)) in entries.iter().zip(&result) {
+        assert_eq!(result, value, "value of {}", hex::encode(key));
+    }
+
+    let registry = ScaleRegistry::with_defaults()
+        .register::<u128>(StorageKey::new("Balances", "TotalIssuance").build());
+    let decoded = registry.decode::<Timestamp>(&keys[0], &result[0].1).unwrap();
+    assert_eq!(decoded, Timestamp(1_677_168_798_005));
+    assert_eq!(registry.decode::<u128>(&keys[1], &result[1].1).unwrap(), 3_000);
+    let decoded = registry.decode::<AccountInfo>(&keys[2], &result[2].1).unwrap();
+    assert_eq!((decoded.nonce, decoded.data.free), (7, 1_000));
+    let decoded = registry.decode::<AccountInfo>(&keys[3], &result[3].1).unwrap();
+    assert_eq!((decoded.nonce, decoded.data.free), (0, 2_000));
+}
+
+/// Random entries with 32 byte keys. A quarter of the keys also have their first 16 bytes
+/// inserted, so the trie holds branches with values as well as leaves.
+fn random_entries(count: usize, value_len: impl Fn(usize) -> usize) -> Vec<(Vec<u8>, Vec<u8>)> {
+    let random_value = |i| (0..value_len(i)).map(|_| rand::random::<u8>()).collect::<Vec<_>>();
+
+    (0..count)
+        .flat_map(|i| {
+            let key = H256::random().as_bytes().to_vec();
+            let prefix = (i % 4 == 0).then(|| (key[..16].to_vec(), random_value(i + 2)));
+            std::iter::once((key, random_value(i))).chain(prefix)
+        })
+        .collect()
 }
 
 fn generate_proof<L: TrieLayout>(
+    entries: &[(Vec<u8>, Vec<u8>)],
 ) -> (<L::Hash as Hasher>::Out, Vec<Vec<u8>>, Vec<(Vec<u8>, Option<DBValue>)>) {
-    let keys = (0..10).map(|_| H256::random().as_bytes().to_vec()).collect::<Vec<_>>();
-
-    let values = (0..10).map(|_| H256::random().as_bytes().to_vec()).collect::<Vec<_>>();
+    generate_migrated_proof::<L, L>(entries, &[])
+}
 
-    let entries = keys.clone().into_iter().zip(values.clone().into_iter()).collect::<Vec<_>>();
+/// Builds a trie from `entries` with the layout `L`, then applies `updates` with the layout `M`,
+/// the way a runtime migrates its state from `LayoutV0` to `LayoutV1`: only the nodes touched by
+/// the updates are re-encoded. Proves every key of both.
+fn generate_migrated_proof<L: TrieLayout, M: TrieLayout<Hash = L::Hash>>(
+    entries: &[(Vec<u8>, Vec<u8>)],
+    updates: &[(Vec<u8>, Vec<u8>)],
+) -> (<L::Hash as Hasher>::Out, Vec<Vec<u8>>, Vec<(Vec<u8>, Option<DBValue>)>) {
     // Populate DB with full trie from entries.
-    let (db, root) = {
-        let mut db = <MemoryDB<L::Hash>>::default();
-        let mut root = Default::default();
-        {
-            let mut trie = TrieDBMutBuilder::<L>::new(&mut db, &mut root).build();
-            for (key, value) in &entries {
-                trie.insert(key, value).unwrap();
-            }
+    let mut db = <MemoryDB<L::Hash>>::default();
+    let mut root = Default::default();
+    {
+        let mut trie = TrieDBMutBuilder::<L>::new(&mut db, &mut root).build();
+        for (key, value) in entries {
+            trie.insert(key, value).unwrap();
         }
-        (db, root)
-    };
+    }
+    if !updates.is_empty() {
+        let mut trie = TrieDBMutBuilder::<M>::from_existing(&mut db, &mut root).build();
+        for (key, value) in updates {
+            trie.insert(key, value).unwrap();
+        }
+    }
+
+    let keys = entries.iter().chain(updates).map(|(key, _)| key.clone()).collect::<Vec<_>>();
 
     // Generate proof for the given keys..
     let proof = {
-        let mut recorder = Recorder::<L>::new();
-        let trie_db = TrieDBBuilder::<L>::new(&db, &root).with_recorder(&mut recorder).build();
+        let mut recorder = Recorder::<M>::new();
+        let trie_db = TrieDBBuilder::<M>::new(&db, &root).with_recorder(&mut recorder).build();
 
-        for (key, expected) in &entries {
-            let value = trie_db.get(key).unwrap().unwrap();
-            assert_eq!(&value, expected);
+        for key in &keys {
+            trie_db.get(key).unwrap().unwrap();
         }
 
-        let proof = recorder.drain().into_iter().map(|f| f.data).collect::<HashSet<_>>();
+        // sorted, so that a proof for the same trie is always in the same order
+        let proof = recorder.drain().into_iter().map(|f| f.data).collect::<BTreeSet<_>>();
         {
             let mdb = StorageProof::new(proof.clone()).into_memory_db::<L::Hash>();
-            let trie_db = TrieDBBuilder::<L>::new(&mdb, &root).build();
-            for (key, expected) in &entries {
-                let value = trie_db.get(key).unwrap().unwrap();
-                assert_eq!(&value, expected);
+            let trie_db = TrieDBBuilder::<M>::new(&mdb, &root).build();
+            for key in &keys {
+                assert!(trie_db.get(key).unwrap().is_some());
             }
         }
 
         proof.into_iter().collect::<Vec<_>>()
     };
 
-    let trie = TrieDBBuilder::<L>::new(&db, &root).build();
+    let trie = TrieDBBuilder::<M>::new(&db, &root).build();
     let items = keys
         .into_iter()
         .map(|key| {
@@ -387,50 +680,427 @@ fn generate_proof<L: TrieLayout>(
     (root, proof, items)
 }
 
+/// Kinds of the nodes in a substrate proof. Hashed values in the proof are skipped.
+fn node_kinds(proof: &[Vec<u8>]) -> Vec<NodeKind> {
+    proof
+        .iter()
+        .filter_map(|node| {
+            let plan = NodeCodec::<KeccakHasher>::decode_plan(node).ok()?;
+            Some(NodeKind::from_plan(&plan, node))
+        })
+        .collect()
+}
+
+/// Checks every item through `VerifyKeys`, all in one call, then a key that is not in the trie.
+async fn verify_keys(
+    contract: &mut Contract<'_>,
+    root: H256,
+    proof: &[Vec<u8>],
+    items: &[(Vec<u8>, Option<DBValue>)],
+) {
+    let keys = items.iter().map(|(key, _)| Token::Bytes(key.clone())).collect::<Vec<_>>();
+    let result = contract
+        .call::<_, Vec<(Vec<u8>, Vec<u8>)>>(
+            "VerifyKeys",
+            (
+                Token::FixedBytes(root.as_bytes().to_vec()),
+                Token::Array(proof.iter().cloned().map(Token::Bytes).collect()),
+                Token::Array(keys),
+            ),
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(result.len(), items.len());
+    for ((key, value), (_, result)) in items.iter().zip(result) {
+        assert_eq!(&result, value.as_ref().unwrap(), "value of {}", hex::encode(key));
+    }
+
+    // non-membership proof
+    let result = contract
+        .call::<_, Vec<(Vec<u8>, Vec<u8>)>>(
+            "VerifyKeys",
+            (
+                Token::FixedBytes(root.as_bytes().to_vec()),
+                Token::Array(proof.iter().cloned().map(Token::Bytes).collect()),
+                Token::Array(vec![Token::Bytes(H256::random().as_bytes().to_vec())]),
+            ),
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(result[0].1.len(), 0);
+}
+
 #[tokio::test(flavor = "multi_thread")]
 async fn test_merkle_patricia_trie_layout_v0() {
-    let (root, proof, entries) = generate_proof::<LayoutV0<KeccakHasher>>();
+    let entries = random_entries(10, |_| 32);
+    let (root, proof, items) = generate_proof::<LayoutV0<KeccakHasher>>(&entries);
 
-    let base_dir = env::current_dir().unwrap().parent().unwrap().display().to_string();
-    let mut runner = Runner::new(PathBuf::from(&base_dir));
-    let mut contract = runner.deploy("MerklePatriciaTest").await;
+    let mut env = TestEnv::shared().await;
+    let mut contract = env.contract("MerklePatriciaTest").await;
+
+    verify_keys(&mut contract, root, &proof, &items).await;
+}
 
-    for (key, value) in entries {
+#[tokio::test(flavor = "multi_thread")]
+async fn test_merkle_patricia_trie_layout_v1() {
+    // values of 33 bytes or more are hashed, smaller ones stay inline
+    let entries = random_entries(40, |i| [1, 32, 33, 100][i % 4]);
+    let (root, proof, items) = generate_proof::<LayoutV1<KeccakHasher>>(&entries);
+
+    let kinds = node_kinds(&proof);
+    assert!(kinds.iter().any(|kind| kind.is_hashed_leaf));
+    assert!(kinds.iter().any(|kind| kind.is_nibbled_hashed_value_branch));
+    assert!(kinds.iter().any(|kind| kind.is_leaf));
+
+    let mut env = TestEnv::shared().await;
+    let mut contract = env.contract("MerklePatriciaTest").await;
+
+    verify_keys(&mut contract, root, &proof, &items).await;
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_merkle_patricia_trie_layout_v0_to_v1_migration() {
+    // large values stay inline in the nodes that are not touched by the migration
+    let entries = random_entries(40, |i| [8, 64][i % 2]);
+    let updates = entries
+        .iter()
+        .step_by(3)
+        .map(|(key, value)| (key.clone(), [value.as_slice(), &[0xff; 40]].concat()))
+        .chain(random_entries(10, |_| 48))
+        .collect::<Vec<_>>();
+    let (root, proof, items) =
+        generate_migrated_proof::<LayoutV0<KeccakHasher>, LayoutV1<KeccakHasher>>(
+            &entries, &updates,
+        );
+
+    let kinds = node_kinds(&proof);
+    assert!(kinds.iter().any(|kind| kind.is_hashed_leaf));
+    assert!(kinds.iter().any(|kind| kind.is_leaf));
+
+    let mut env = TestEnv::shared().await;
+    let mut contract = env.contract("MerklePatriciaTest").await;
+
+    verify_keys(&mut contract, root, &proof, &items).await;
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_merkle_patricia_trie_expanded_compact_proof() {
+    let entries = random_entries(40, |i| [1, 32, 33, 100][i % 4]);
+    let (root, proof, items) = generate_proof::<LayoutV1<KeccakHasher>>(&entries);
+
+    let compact = compact_proof::compact::<KeccakHasher>(root, proof).unwrap();
+    let expanded = compact_proof::expand::<KeccakHasher>(root, &compact).unwrap();
+
+    let mut env = TestEnv::shared().await;
+    let mut contract = env.contract("MerklePatriciaTest").await;
+
+    verify_keys(&mut contract, root, &expanded, &items).await;
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_merkle_patricia_trie_minimal_proofs() {
+    use rand::seq::SliceRandom;
+
+    let entries = random_entries(100, |i| [1, 32, 33, 100][i % 4]);
+    let (root, proof, items) = generate_proof::<LayoutV1<KeccakHasher>>(&entries);
+    let all = items.iter().map(|(key, _)| key.clone()).collect::<Vec<_>>();
+    let ethereum = EthereumTrie::new(entries.clone());
+    let ethereum_proof = ethereum.prove(&all);
+
+    let mut env = TestEnv::shared().await;
+    let mut contract = env.contract("MerklePatriciaTest").await;
+
+    for count in [1, 5, 30] {
+        let subset =
+            items.choose_multiple(&mut rand::thread_rng(), count).cloned().collect::<Vec<_>>();
+        let keys = subset.iter().map(|(key, _)| key.clone()).collect::<Vec<_>>();
+
+        let minimal = minimise_substrate::<LayoutV1<KeccakHasher>>(&root, &proof, &keys).unwrap();
+        assert!(minimal.nodes.len() < proof.len());
+        assert!(minimal.warning().is_some());
+        verify_keys(&mut contract, root, &minimal.nodes, &subset).await;
+
+        let minimal = minimise_ethereum(ethereum.root(), &ethereum_proof, &keys).unwrap();
+        assert!(minimal.nodes.len() < ethereum_proof.len());
         let result = contract
             .call::<_, Vec<(Vec<u8>, Vec<u8>)>>(
-                "VerifyKeys",
+                "VerifyEthereum",
                 (
-                    Token::FixedBytes(root.as_bytes().to_vec()),
-                    Token::Array(proof.clone().into_iter().map(Token::Bytes).collect()),
-                    Token::Array(vec![Token::Bytes(key.to_vec())]),
+                    Token::FixedBytes(ethereum.root().as_bytes().to_vec()),
+                    Token::Array(minimal.nodes.into_iter().map(Token::Bytes).collect()),
+                    Token::Array(keys.iter().cloned().map(Token::Bytes).collect()),
                 ),
             )
             .await
             .unwrap();
-        assert_eq!(result[0].1, value.unwrap());
+        for (key, (_, value)) in keys.iter().zip(result) {
+            assert_eq!(Some(value), ethereum.get(key));
+        }
     }
+}
 
-    // non-membership proof
-    let result = contract
+/// Calls `function` of the test contract with a root, proof and keys
+async fn call_verifier(
+    contract: &mut Contract<'_>,
+    function: &str,
+    root: H256,
+    proof: &[Vec<u8>],
+    keys: &[Vec<u8>],
+) -> Option<Vec<(Vec<u8>, Vec<u8>)>> {
+    contract
         .call::<_, Vec<(Vec<u8>, Vec<u8>)>>(
-            "VerifyKeys",
+            function,
             (
                 Token::FixedBytes(root.as_bytes().to_vec()),
-                Token::Array(proof.clone().into_iter().map(Token::Bytes).collect()),
-                Token::Array(vec![Token::Bytes(H256::random().as_bytes().to_vec())]),
+                Token::Array(proof.iter().cloned().map(Token::Bytes).collect()),
+                Token::Array(keys.iter().cloned().map(Token::Bytes).collect()),
             ),
         )
         .await
-        .unwrap();
+        .ok()
+}
 
-    assert_eq!(result[0].1.len(), 0);
+#[tokio::test(flavor = "multi_thread")]
+async fn test_merkle_patricia_trie_native_verifiers_match_contract() {
+    let mut env = TestEnv::shared().await;
+    let mut contract = env.contract("MerklePatriciaTest").await;
+
+    let entries = random_entries(60, |i| [1, 2, 32, 33, 100][i % 5]);
+    let absent = H256::random().as_bytes().to_vec();
+    for (root, proof, items) in [
+        generate_proof::<LayoutV0<KeccakHasher>>(&entries),
+        generate_proof::<LayoutV1<KeccakHasher>>(&entries),
+    ] {
+        let keys = items.iter().map(|(key, _)| key.clone()).chain([absent.clone()]);
+        let keys = keys.collect::<Vec<_>>();
+
+        let native = verify_substrate_proof::<KeccakHasher>(root, &proof, &keys).ok();
+        assert!(native.is_some());
+        assert_eq!(call_verifier(&mut contract, "VerifyKeys", root, &proof, &keys).await, native);
+
+        // without the root node
+        let proof = proof.into_iter().filter(|node| keccak256(node) != root.0);
+        let proof = proof.collect::<Vec<_>>();
+        let native = verify_substrate_proof::<KeccakHasher>(root, &proof, &keys).ok();
+        assert_eq!(native, None);
+        let result = call_verifier(&mut contract, "VerifyKeys", root, &proof, &keys).await;
+        assert_eq!(result, native);
+    }
+
+    // secure keys, then small keys and values that make for inline nodes, which the contract
+    // reads as absent
+    let small = (1u8..16).map(|i| (vec![i], vec![i])).collect::<Vec<_>>();
+    for entries in [
+        entries.iter().map(|(key, value)| (secure_key(key), value.clone())).collect(),
+        small.clone(),
+        small[..2].to_vec(),
+    ] {
+        let trie = EthereumTrie::new(entries.clone());
+        let keys = small.iter().map(|(key, _)| key.clone()).chain([secure_key(&absent)]);
+        let keys = keys.chain(entries.iter().map(|(key, _)| key.clone())).collect::<Vec<_>>();
+        let proof = trie.prove(&keys);
+
+        let native = verify_ethereum_proof(trie.root(), &proof, &keys).ok();
+        let result = call_verifier(&mut contract, "VerifyEthereum", trie.root(), &proof, &keys);
+        assert_eq!(result.await, native);
+    }
+}
+
+/// Most parachains hash their state trie with blake2, which only the native verifier can read:
+/// the contract hashes proof nodes with keccak, so it finds none of them.
+#[tokio::test(flavor = "multi_thread")]
+async fn test_merkle_patricia_trie_blake2_proofs() {
+    let mut env = TestEnv::shared().await;
+    let mut contract = env.contract("MerklePatriciaTest").await;
+
+    let system_account = |who: &[u8; 32]| {
+        StorageKey::new("System", "Account").key(StorageHasher::Blake2_128Concat, who).build()
+    };
+    let entries = (0u8..40)
+        .map(|i| (system_account(&[i; 32]), AccountInfo { nonce: i as u32, ..Default::default() }))
+        .map(|(key, account)| (key, account.encode()))
+        .chain(random_entries(20, |i| [1, 32, 33, 100][i % 4]))
+        .collect::<Vec<_>>();
+
+    for (root, proof, items) in [
+        generate_proof::<LayoutV0<Blake2Hasher>>(&entries),
+        generate_proof::<LayoutV1<Blake2Hasher>>(&entries),
+    ] {
+        let keys = items.iter().map(|(key, _)| key.clone()).collect::<Vec<_>>();
+        let result = verify_substrate_proof::<Blake2Hasher>(root, &proof, &keys).unwrap();
+        for ((key, expected), (_, value)) in items.iter().zip(&result) {
+            assert_eq!(Some(value), expected.as_ref(), "value of {}", hex::encode(key));
+        }
+
+        assert_eq!(
+            verify_substrate_proof::<KeccakHasher>(root, &proof, &keys),
+            Err(VerifyError::IncompleteProof(root))
+        );
+        assert_eq!(call_verifier(&mut contract, "VerifyKeys", root, &proof, &keys).await, None);
+    }
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_merkle_patricia_trie_truncated_proofs() {
+    let mut env = TestEnv::shared().await;
+    let mut contract = env.contract("MerklePatriciaTest").await;
+
+    let entries = random_entries(20, |i| [4, 64][i % 2]);
+    let (root, mut proof, items) = generate_proof::<LayoutV1<KeccakHasher>>(&entries);
+    let keys = items.iter().map(|(key, _)| key.clone()).chain([H256::random().0.to_vec()]);
+    let keys = keys.collect::<Vec<_>>();
+
+    // the contract reverts on any key that is incomplete, so check every key on its own
+    let dropped = proof.remove(proof.len() / 2);
+    let outcomes = read_substrate_proof::<KeccakHasher>(root, &proof, &keys).unwrap();
+    assert!(outcomes.contains(&KeyProof::Incomplete(H256(keccak256(&dropped)))));
+    for (key, outcome) in keys.iter().zip(outcomes) {
+        let keys = [key.clone()];
+        let result = call_verifier(&mut contract, "VerifyKeys", root, &proof, &keys).await;
+        let expected = verify_substrate_proof::<KeccakHasher>(root, &proof, &keys);
+        assert_eq!(result, expected.ok());
+        assert_eq!(result.is_none(), matches!(outcome, KeyProof::Incomplete(_)));
+    }
+
+    let entries = entries.into_iter().map(|(key, value)| (secure_key(&key), value));
+    let trie = EthereumTrie::new(entries.collect::<Vec<_>>());
+    let keys = keys.iter().map(|key| secure_key(key)).collect::<Vec<_>>();
+    let mut proof = trie.prove(&keys);
+    let dropped = proof.remove(proof.len() / 2);
+
+    let outcomes = read_ethereum_proof(trie.root(), &proof, &keys).unwrap();
+    assert!(outcomes.contains(&KeyProof::Incomplete(H256(keccak256(&dropped)))));
+    for (key, outcome) in keys.iter().zip(outcomes) {
+        let keys = [key.clone()];
+        let result =
+            call_verifier(&mut contract, "VerifyEthereum", trie.root(), &proof, &keys).await;
+        assert_eq!(result, verify_ethereum_proof(trie.root(), &proof, &keys).ok());
+        if let KeyProof::Present(value) = outcome {
+            assert_eq!(result, Some(vec![(key.clone(), value)]));
+        }
+    }
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_merkle_patricia_trie_mutated_proofs() {
+    let mut env = TestEnv::shared().await;
+    let mut contract = env.contract("MerklePatriciaTest").await;
+    let mut report = MutationReport::new("merkle_patricia");
+
+    // single byte keys make for inline leaves in the substrate tries
+    let small = (1u8..6).map(|i| (vec![i], vec![i]));
+    let entries = random_entries(8, |i| [4, 64][i % 2]).into_iter().chain(small);
+    let entries = entries.collect::<Vec<_>>();
+    let absent = H256::random().as_bytes().to_vec();
+
+    let mut proofs = vec![];
+    for (root, proof, items) in [
+        generate_proof::<LayoutV0<KeccakHasher>>(&entries),
+        generate_proof::<LayoutV1<KeccakHasher>>(&entries),
+    ] {
+        let keys = items.into_iter().map(|(key, _)| key).chain([absent.clone()]).collect();
+        proofs.push(TrieProof::new(TrieKind::Substrate, root, proof, keys));
+    }
+    let entries = entries.iter().map(|(key, value)| (secure_key(key), value.clone()));
+    let entries = entries.collect::<Vec<_>>();
+    let trie = EthereumTrie::new(entries.clone());
+    let keys = entries.into_iter().map(|(key, _)| key).chain([secure_key(&absent)]);
+    let keys = keys.collect::<Vec<_>>();
+    proofs.push(TrieProof::new(TrieKind::Ethereum, trie.root(), trie.prove(&keys), keys));
+
+    for proof in proofs {
+        let function = match proof.kind {
+            TrieKind::Substrate => "VerifyKeys",
+            TrieKind::Ethereum => "VerifyEthereum",
+        };
+        for (mutation, variant) in proof.variants() {
+            let result =
+                call_verifier(&mut contract, function, variant.root, &variant.nodes, &variant.keys);
+            let values = result
+                .await
+                .map(|result| result.into_iter().map(|(_, value)| value).collect::<Vec<_>>());
+            let outcome = variant.judge(values.as_deref());
+            report.record(function, &mutation, variant.to_json(), outcome);
+        }
+    }
+    report.finish();
+}
+
+/// Reads `keys` of the child trie at `storage_key` through `ReadChildProofCheck`
+async fn read_child_proof_check(
+    contract: &mut Contract<'_>,
+    root: H256,
+    proof: &[Vec<u8>],
+    keys: &[Vec<u8>],
+    storage_key: &[u8],
+) -> Result<Vec<(Vec<u8>, Vec<u8>)>, String> {
+    contract
+        .call::<_, Vec<(Vec<u8>, Vec<u8>)>>(
+            "ReadChildProofCheck",
+            (
+                Token::FixedBytes(root.as_bytes().to_vec()),
+                Token::Array(proof.iter().cloned().map(Token::Bytes).collect()),
+                Token::Array(keys.iter().cloned().map(Token::Bytes).collect()),
+                Token::Bytes(storage_key.to_vec()),
+            ),
+        )
+        .await
+        .map_err(|err| format!("{err:?}"))
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_merkle_patricia_trie_read_child_proof_check() {
+    let entries = random_entries(30, |i| [4, 32, 64][i % 3]);
+    let mut builder = ChildTrieBuilder::new().child(b"empty");
+    for (key, value) in &entries {
+        builder = builder.top(key, value).child_storage(b"child", key, value);
+    }
+    builder = builder.child_storage(b"other", b"key", b"value");
+
+    for state_version in [StateVersion::V0, StateVersion::V1] {
+        let tries = builder.clone().build(state_version);
+        let root = tries.root();
+        assert_eq!(tries.child_root(b"empty"), None);
+
+        let mut env = TestEnv::shared().await;
+        let mut contract = env.contract("MerklePatriciaTest").await;
+
+        // present and absent keys, all proven by one proof
+        let absent = H256::random().as_bytes().to_vec();
+        let keys = entries.iter().map(|(key, _)| key.clone()).chain([absent]).collect::<Vec<_>>();
+        let proof = tries.prove(b"child", &keys);
+        let result =
+            read_child_proof_check(&mut contract, root, &proof, &keys, b"child").await.unwrap();
+
+        assert_eq!(result.len(), keys.len());
+        for (key, (_, value)) in keys.iter().zip(&result) {
+            let expected = tries.child_storage(b"child", key).unwrap_or_default();
+            assert_eq!(value, &expected, "value of {}", hex::encode(key));
+        }
+        assert!(result.last().unwrap().1.is_empty());
+
+        // the proof does not reach the root of `other`
+        let result = read_child_proof_check(&mut contract, root, &proof, &keys, b"other").await;
+        assert!(result.unwrap_err().contains("Incomplete Proof"));
+
+        // an empty child trie has no root in the parent trie
+        let proof = tries.prove(b"empty", &keys);
+        let result = read_child_proof_check(&mut contract, root, &proof, &keys, b"empty").await;
+        assert!(result.unwrap_err().contains("Invalid child trie proof"));
+
+        // so does a child trie that was never created
+        let proof = tries.prove(b"missing", &keys);
+        let result = read_child_proof_check(&mut contract, root, &proof, &keys, b"missing").await;
+        assert!(result.unwrap_err().contains("Invalid child trie proof"));
+    }
 }
 
 #[tokio::test(flavor = "multi_thread")]
 async fn test_merkle_patricia_trie_ethereum_verify_transaction_trie_single_node() {
-    let base_dir = env::current_dir().unwrap().parent().unwrap().display().to_string();
-    let mut runner = Runner::new(PathBuf::from(&base_dir));
-    let mut contract = runner.deploy("MerklePatriciaTest").await;
+    let mut env = TestEnv::shared().await;
+    let mut contract = env.contract("MerklePatriciaTest").await;
 
     let result = contract.call::<_, Vec<(Vec<u8>, Vec<u8>)>>(
         "VerifyEthereum",
@@ -446,9 +1116,8 @@ async fn test_merkle_patricia_trie_ethereum_verify_transaction_trie_single_node(
 
 #[tokio::test(flavor = "multi_thread")]
 async fn test_merkle_patricia_trie_ethereum_verify_transaction_trie_multi_node() {
-    let base_dir = env::current_dir().unwrap().parent().unwrap().display().to_string();
-    let mut runner = Runner::new(PathBuf::from(&base_dir));
-    let mut contract = runner.deploy("MerklePatriciaTest").await;
+    let mut env = TestEnv::shared().await;
+    let mut contract = env.contract("MerklePatriciaTest").await;
 
     let result = contract.call::<_, Vec<(Vec<u8>, Vec<u8>)>>(
         "VerifyEthereum",
@@ -469,49 +1138,158 @@ async fn test_merkle_patricia_trie_ethereum_verify_transaction_trie_multi_node()
 }
 
 #[tokio::test(flavor = "multi_thread")]
-async fn test_merkle_patricia_trie_ethereum_verify_state_trie_single_node() {
-    let base_dir = env::current_dir().unwrap().parent().unwrap().display().to_string();
-    let mut runner = Runner::new(PathBuf::from(&base_dir));
-    let mut contract = runner.deploy("MerklePatriciaTest").await;
+async fn test_merkle_patricia_trie_ethereum_verify_sepolia_transactions() {
+    let dir = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("fixtures/block/sepolia_7265502");
+    let read = |file: &str| {
+        serde_json::from_str::<serde_json::Value>(&fs::read_to_string(dir.join(file)).unwrap())
+            .unwrap()
+    };
+    let block = read("block.json");
+    let raw_transactions = serde_json::from_value::<Vec<Bytes>>(read("raw_transactions.json"))
+        .unwrap()
+        .into_iter()
+        .map(|transaction| transaction.to_vec())
+        .collect::<Vec<_>>();
+    let field = |field: &str| serde_json::from_value::<H256>(block[field].clone()).unwrap();
+
+    // the header is the one of the block, and the encodings are the ones of its transactions
+    assert_eq!(header_hash(&block).unwrap(), field("hash"));
+    let transactions = block["transactions"].as_array().unwrap();
+    assert_eq!(raw_transactions.len(), transactions.len());
+    for (raw, transaction) in raw_transactions.iter().zip(transactions) {
+        let hash = serde_json::from_value::<H256>(transaction["hash"].clone()).unwrap();
+        assert_eq!(H256(keccak256(raw)), hash);
+    }
 
-    let result = contract.call::<_, Vec<(Vec<u8>, Vec<u8>)>>(
-        "VerifyEthereum",
-        (Token::FixedBytes(hex!("0ce23f3c809de377b008a4a3ee94a0834aac8bec1f86e28ffe4fdb5a15b0c785").to_vec()),
-        Token::Array(vec![
-            hex!("f86aa1205380c7b7ae81a58eb98d9c78de4a1fd7fd9535fc953ed2be602daaa41767312ab846f8448080a056e81f171bcc55a6ff8345e692c0f86e5b48e01b996cadc001622fb5e363b421a0c5d2460186f7233c927e7db2dcc703c0e500b653ca82273b7bfad8045d85a470").to_vec(),
-          ].into_iter().map(Token::Bytes).collect()),
-        Token::Array(vec![Token::Bytes(hex!("5380c7b7ae81a58eb98d9c78de4a1fd7fd9535fc953ed2be602daaa41767312a").to_vec())]),),
-    ).await
-    .unwrap();
-    assert_eq!(result[0].1, hex!("f8448080a056e81f171bcc55a6ff8345e692c0f86e5b48e01b996cadc001622fb5e363b421a0c5d2460186f7233c927e7db2dcc703c0e500b653ca82273b7bfad8045d85a470").to_vec())
+    let trie = IndexTrie::new(raw_transactions.clone()).unwrap();
+    assert_eq!(trie.root(), field("transactionsRoot"));
+
+    let mut env = TestEnv::shared().await;
+    let mut contract = env.contract("MerklePatriciaTest").await;
+
+    // legacy, EIP-1559 and blob transactions, and both ends of the trie
+    let first = |kind: &str| {
+        transactions.iter().position(|transaction| transaction["type"] == kind).unwrap()
+    };
+    for index in [0, first("0x0"), first("0x2"), first("0x3"), trie.len() - 1] {
+        let proof = trie.prove(index).unwrap();
+        assert_eq!(proof.value, raw_transactions[index]);
+        assert!(proof.verify(trie.root()));
+
+        let result = contract
+            .call::<_, Vec<(Vec<u8>, Vec<u8>)>>(
+                "VerifyEthereum",
+                (
+                    Token::FixedBytes(trie.root().as_bytes().to_vec()),
+                    Token::Array(proof.proof.into_iter().map(Token::Bytes).collect()),
+                    Token::Array(vec![Token::Bytes(proof.key)]),
+                ),
+            )
+            .await
+            .unwrap();
+        assert_eq!(result[0].1, raw_transactions[index], "transaction {index}");
+    }
 }
 
 #[tokio::test(flavor = "multi_thread")]
-async fn test_merkle_patricia_trie_ethereum_verify_state_trie_multi_node() {
-    let base_dir = env::current_dir().unwrap().parent().unwrap().display().to_string();
-    let mut runner = Runner::new(PathBuf::from(&base_dir));
-    let mut contract = runner.deploy("MerklePatriciaTest").await;
+async fn test_merkle_patricia_trie_ethereum_verify_eth_get_proof_fixtures() {
+    let dir = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("fixtures/eth_get_proof");
+    let fixtures = EthProofFixture::load_all(&dir).unwrap();
+    assert!(!fixtures.is_empty());
+
+    let mut env = TestEnv::shared().await;
+    let mut contract = env.contract("MerklePatriciaTest").await;
+
+    for fixture in fixtures {
+        fixture.check_values().unwrap();
+        if !fixture.name.starts_with("synthetic_") {
+            fixture.check_header().unwrap();
+        }
 
-    let result = contract.call::<_, Vec<(Vec<u8>, Vec<u8>)>>(
-        "VerifyEthereum",
-        (Token::FixedBytes(hex!("4dc3e58e944d713c36c6b9cc58df023b3e578093de16e175faefa8f91727ca6e").to_vec()),
-        Token::Array(vec![
-            hex!("f90211a07466452b9c24acc76f0c9a0a4d43f5b362a031626eb6a0d7e7409c9c2fe1ecdda00bc98d4eaa34347ecb42d4d716161612692c2ac599cf3c4f5eade18fbb07da4ca00146ab246da36aec011dcdf8fd4c6a505ec7caca75441268c7eab80e9aa96767a038a41d42f1edccf59f2a1d6e7887ab1f3dfcd0a26f3be309ee237ed4aecd9d38a05f542a7ffff85163015c7cfc8c7d947ac98114f1bc576ab74ec663dd97d9596fa0208cb7384b248a341c22ef52d1882f6045345c6435c38a5b4d382e7a02c53f48a086b590086c7e7738c59cd0bba1a136dc42cc099fa9ae8af77abaa76fa1f3f503a0ab69ef5e7d461a547675de48c30be16f2d297509f6d005325365cbebe8735104a0896573c4595ea56992cb4237091ce8f00a73988f80506ef7bde78de9cfddbfa9a06d78ae475034b4aec9afef58c3f93e997f2c50f2a4948a7214b0295c5ae1776ea0763c0ec3ea13b7cbfe139cf8a3cf76e75026b2d42854bf822a47a0497dabf679a028fd50ebf9eed4e9a0969a73682ea615cb1134510f80aa057c60acf657a13a05a00c9f1e12244dabf2db619f0ce1098dd6d19f7c9dd1b17da1ffd02b0e0f3d4d7ea0aa2a772e989b23bd7e2eba714f153031c79c03cb835539fc56debc7669b64148a07f6544adbc5e30eca006a050384d85df7a510bac66dde8c32b7741486b319610a0c859cc09be23308083a16f96c19dffd9b48770b715c150220a35e07ff5ea716b80").to_vec(),
-            hex!("f90171a02ffa31221e3db9f56751599b181b16cd0489d9870f58a481ee1398d6991a6ed3a0af7f7b8a8aa219ebd9e9562bd3f917c47f7205bb0f29bdb064c63e51f8e14ee680a0fc0482eb10e5eccc57013233746a95072c2d80746898c30a23fc43c52eabddf5a0985b92a5617ee65b05be517cbbeb1c5598e5479bdb45107552b80d339c6c23eba0984d7ace51d61b40d4336aec39c867fc5db4566036ef0e6225df604dd93a6538a068ff593d5fc203763242dc6b95e559472f8d43ceed1a4fececb11a0eb6ef1070a0d7a609f017d3641ff18327916212ebf21d6ae93b3ffe09ae6fc5e1160c7f571f80a0d62e9137db3e883d506c3927310d0100e624b5befe180830f029c99859451230a0ce9a78f4d5abb17cfe7c189a83524a479bd8aed870cc7608c3ede431cdd214cd8080a0f58d48fa8ca7b152aa825128a173c6061b6cabe9143473e246f6d8732f59cc3880a030a23d0cd92c346ce27019e17ddbf8651695cd3fd6350f0f7862f915269a57ff80").to_vec(),
-            hex!("f8518080808080808080a0fed0c7841e83453b78135ca2a36e8fb5d8c5fbb5883746f3f93054e42205e7e880808080a0711f6aa6ad472844f3e563a1c3ff5777c4e2c3b3126b42fa4eb4d115546116c5808080").to_vec(),
-            hex!("f8689f30c7b7ae81a58eb98d9c78de4a1fd7fd9535fc953ed2be602daaa41767312ab846f8448080a056e81f171bcc55a6ff8345e692c0f86e5b48e01b996cadc001622fb5e363b421a0c5d2460186f7233c927e7db2dcc703c0e500b653ca82273b7bfad8045d85a470").to_vec()
-          ].into_iter().map(Token::Bytes).collect()),
-        Token::Array(vec![Token::Bytes(hex!("5380c7b7ae81a58eb98d9c78de4a1fd7fd9535fc953ed2be602daaa41767312a").to_vec())]),),
-    ).await
-    .unwrap();
-    assert_eq!(result[0].1,hex!("f8448080a056e81f171bcc55a6ff8345e692c0f86e5b48e01b996cadc001622fb5e363b421a0c5d2460186f7233c927e7db2dcc703c0e500b653ca82273b7bfad8045d85a470").to_vec())
+        for query in fixture.queries() {
+            let result = contract
+                .call::<_, Vec<(Vec<u8>, Vec<u8>)>>(
+                    "VerifyEthereum",
+                    (
+                        Token::FixedBytes(query.root.as_bytes().to_vec()),
+                        Token::Array(query.proof.into_iter().map(Token::Bytes).collect()),
+                        Token::Array(vec![Token::Bytes(query.key)]),
+                    ),
+                )
+                .await
+                .unwrap();
+            assert_eq!(result[0].1, query.value.unwrap_or_default(), "{}", query.label);
+        }
+    }
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_merkle_patricia_trie_ethereum_verify_state_dump_fixtures() {
+    let dir = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("fixtures/state_dump");
+
+    let mut env = TestEnv::shared().await;
+    let mut contract = env.contract("MerklePatriciaTest").await;
+
+    for name in ["genesis.json", "dump_block.json", "holesky_genesis.json"] {
+        let dump = StateDump::load(&dir.join(name)).unwrap();
+        let root = dump.check_root().unwrap();
+
+        // every account, the first few slots of each, a slot that isn't set and an account that
+        // doesn't exist
+        let addresses = dump.addresses().chain([Address::repeat_byte(0xab)]).collect::<Vec<_>>();
+        for address in addresses {
+            let mut slots = dump.slots(address).into_iter().take(3).collect::<Vec<_>>();
+            slots.push(H256::repeat_byte(0xab));
+            let proof = dump.state.get_proof(address, &slots);
+
+            let result = contract
+                .call::<_, Vec<(Vec<u8>, Vec<u8>)>>(
+                    "VerifyEthereum",
+                    (
+                        Token::FixedBytes(root.as_bytes().to_vec()),
+                        Token::Array(proof.account_nodes().into_iter().map(Token::Bytes).collect()),
+                        Token::Array(vec![Token::Bytes(secure_key(address.as_bytes()))]),
+                    ),
+                )
+                .await
+                .unwrap();
+            let account = decode_account(&result[0].1).unwrap();
+            assert_eq!(account.as_ref(), dump.state.account(address), "{name}: {address:?}");
+            // an empty storage trie has no nodes to prove anything with
+            if account.map_or(true, |account| account.storage_root == EMPTY_ROOT) {
+                continue
+            }
+
+            for storage_proof in proof.storage_proof {
+                let slot = storage_proof.slot();
+                let result = contract
+                    .call::<_, Vec<(Vec<u8>, Vec<u8>)>>(
+                        "VerifyEthereum",
+                        (
+                            Token::FixedBytes(proof.storage_hash.as_bytes().to_vec()),
+                            Token::Array(
+                                storage_proof.nodes().into_iter().map(Token::Bytes).collect(),
+                            ),
+                            Token::Array(vec![Token::Bytes(secure_key(slot.as_bytes()))]),
+                        ),
+                    )
+                    .await
+                    .unwrap();
+                let expected = dump.storage(address).and_then(|storage| storage.get(&slot));
+                assert_eq!(
+                    decode_slot(&result[0].1).unwrap(),
+                    expected.copied().unwrap_or_default(),
+                    "{name}: {address:?} slot {slot:?}"
+                );
+            }
+        }
+    }
 }
 
 #[tokio::test(flavor = "multi_thread")]
 async fn test_merkle_patricia_trie_ethereum_verify_receipt_trie_single_node() {
-    let base_dir = env::current_dir().unwrap().parent().unwrap().display().to_string();
-    let mut runner = Runner::new(PathBuf::from(&base_dir));
-    let mut contract = runner.deploy("MerklePatriciaTest").await;
+    let mut env = TestEnv::shared().await;
+    let mut contract = env.contract("MerklePatriciaTest").await;
 
     let result = contract.call::<_, Vec<(Vec<u8>, Vec<u8>)>>(
         "VerifyEthereum",
@@ -527,9 +1305,8 @@ async fn test_merkle_patricia_trie_ethereum_verify_receipt_trie_single_node() {
 
 #[tokio::test(flavor = "multi_thread")]
 async fn test_merkle_patricia_trie_ethereum_verify_receipt_trie_multi_node() {
-    let base_dir = env::current_dir().unwrap().parent().unwrap().display().to_string();
-    let mut runner = Runner::new(PathBuf::from(&base_dir));
-    let mut contract = runner.deploy("MerklePatriciaTest").await;
+    let mut env = TestEnv::shared().await;
+    let mut contract = env.contract("MerklePatriciaTest").await;
 
     let result = contract.call::<_, Vec<(Vec<u8>, Vec<u8>)>>(
         "VerifyEthereum",
@@ -547,9 +1324,8 @@ async fn test_merkle_patricia_trie_ethereum_verify_receipt_trie_multi_node() {
 
 #[tokio::test(flavor = "multi_thread")]
 async fn test_merkle_patricia_trie_ethereum_verify_storage_trie_single_node() {
-    let base_dir = env::current_dir().unwrap().parent().unwrap().display().to_string();
-    let mut runner = Runner::new(PathBuf::from(&base_dir));
-    let mut contract = runner.deploy("MerklePatriciaTest").await;
+    let mut env = TestEnv::shared().await;
+    let mut contract = env.contract("MerklePatriciaTest").await;
 
     let result = contract
         .call::<_, Vec<(Vec<u8>, Vec<u8>)>>(
@@ -577,27 +1353,7 @@ async fn test_merkle_patricia_trie_ethereum_verify_storage_trie_single_node() {
         .await
         .unwrap();
     assert_eq!(result[0].1, hex!("80").to_vec());
-}
-
-#[tokio::test(flavor = "multi_thread")]
-async fn test_merkle_patricia_trie_ethereum_verify_storage_trie_multi_node() {
-    let base_dir = env::current_dir().unwrap().parent().unwrap().display().to_string();
-    let mut runner = Runner::new(PathBuf::from(&base_dir));
-    let mut contract = runner.deploy("MerklePatriciaTest").await;
-
-    let result = contract.call::<_, Vec<(Vec<u8>, Vec<u8>)>>(
-        "VerifyEthereum",
-        (Token::FixedBytes(hex!("ffb7f68eaf1ef5b5aa838621a8ae0f2317992672da7d572236895557be62dead").to_vec()),
-        Token::Array(vec![
-            hex!("f90211a0c63bc0f27d1bf083b52c9162b8390a5f2f3e0fd1fc3c68dc9b53f414dec4293aa05d0312b83f74fb3324d01ff2a56de4bded7486ecb004539709ea7bd40b8a7b5ea01563c02eb256f474f597b0aef92ae30d34c492dcba30580eca279fcd99ec6275a0437128e7cd04a4129f48222735c59e2c1a4035286e0e2892d117849d07657ad6a0e812dda3a599643c60b990f9dcc0259473e4b02ed4da5624f36a7bcd3583d84ba082d7b25fd52ef540173638c22cf30cabfb1ca8880533a41a7ba9616e23ae5e30a085afed9de3cdcd64ca16dac64eb60a0a49d7926b9de0df7d135b0ef9299aa2e7a07d1f8cbfdd80fdb8e734139e7cff500002ce3ea04f8e7e7a65f7ee76373ba052a082d333caa80beb65d520d7eada9f48382c951ed201c4db6e234ed4bfd741831aa0fecf3839dca4fb2074056d45d8654446fc0bc35dd729c057ce47f624da1406c3a0a220dbb9b1afa52e1c8356134f72d79e78119882bc4ae82b0c9eb09e8237f73ba0430d634294001ad8bbf7e9e8934ed53fac9c1dd58b318e868161e549b96fbdcaa0d139a88a1fc490c42445c4c3fb7056cccccc9fb422221ccee614d6a8c3283ae9a0f327ef1cd0da3765c2218466667775b858fe170f7699063ebb386f615b581fc7a0504fddf4ea09a2f3be60c9783dc4d4b1f759c9ffa1083189442b904576b69576a0a646afb9b8836e8dab290064e553af0f6bd42c038791aee602197b26d2dbf87580").to_vec(),
-            hex!("f90111a07389d98c79eb4836dabcb6bd3a7791d450c706c7556040cdd3988e5367857466a08dc351c579002352b5c36aa489ec19cce91825a5463ad956756fa3f74856c27d808080a0fbbe85f885abfae725bccc4a6c2f7cb9028271cfc48eb51c78a32c2ef8a58ddaa071fe8e3fb4bf1025153ecca0478fddbf8d791bc1de5f54fab5d5f906d1d2a6e8808080a01e0a980530f5be28de03db7cb8d973e70098c0ecbb05bc653f3482a49f50dcd58080a067c53bc2ed22d7afbc86921e0a7fdc2467ae93e0ce1dbc8353df46e9c128fc8ba0a3038946f9746409bb3c437ceb86c3933fd311fe1a81b7d89b91ac51065634d5a07f9f281b145be5d9a0854f1fc3c0720566aca0c5585d0024cd1128895ae00d3980").to_vec(),
-            hex!("f85180a0449562ac8ce77c19644bb844d2162ae4f7426d156232af2cf5c7bebf985ccff18080a03a24d1a9a95ff6d53aeb3a6771cb596208ab47c33045ea2015d68b4a9e1a8445808080808080808080808080").to_vec(),
-            hex!("e59f3540171b6c0c960b71a7020d9f60077f6af931a8bbf590da0223dacf75c7af84830dbba0").to_vec()
-          ].into_iter().map(Token::Bytes).collect()),
-        Token::Array(vec![Token::Bytes(hex!("6e1540171b6c0c960b71a7020d9f60077f6af931a8bbf590da0223dacf75c7af").to_vec())]),),
-    ).await
-    .unwrap();
-    assert_eq!(result[0].1, hex!("830dbba0").to_vec());
+    assert_eq!(decode_slot(&result[0].1).unwrap(), U256::zero());
 }
 
 #[tokio::test(flavor = "multi_thread")]
@@ -615,9 +1371,8 @@ async fn test_merkle_patricia_trie_ethereum_verify_storage_trie() {
         hex!("f86d9d3c3738deb88e49108e7a5bd83c14ad65b5ba598e2932551dc9b9ad1879b84df84b10874ef05b2fe9d8c8a056e81f171bcc55a6ff8345e692c0f86e5b48e01b996cadc001622fb5e363b421a0c5d2460186f7233c927e7db2dcc703c0e500b653ca82273b7bfad8045d85a470").to_vec(),
     ];
     let root = hex!("024c056bc5db60d71c7908c5fad6050646bd70fd772ff222702d577e2af2e56b").to_vec();
-    let base_dir = env::current_dir().unwrap().parent().unwrap().display().to_string();
-    let mut runner = Runner::new(PathBuf::from(&base_dir));
-    let mut contract = runner.deploy("MerklePatriciaTest").await;
+    let mut env = TestEnv::shared().await;
+    let mut contract = env.contract("MerklePatriciaTest").await;
 
     let result = contract
         .call::<_, Vec<(Vec<u8>, Vec<u8>)>>(
@@ -630,8 +1385,276 @@ async fn test_merkle_patricia_trie_ethereum_verify_storage_trie() {
         )
         .await
         .unwrap();
-    assert_eq!(
-        result[0].1,
-        hex!("f84b10874ef05b2fe9d8c8a056e81f171bcc55a6ff8345e692c0f86e5b48e01b996cadc001622fb5e363b421a0c5d2460186f7233c927e7db2dcc703c0e500b653ca82273b7bfad8045d85a470").to_vec()
-    );
+    let account = Account {
+        nonce: 16,
+        balance: U256::from(0x4ef05b2fe9d8c8u64),
+        storage_root: EMPTY_ROOT,
+        code_hash: EMPTY_CODE_HASH,
+    };
+    assert_eq!(decode_account(&result[0].1).unwrap(), Some(account));
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_merkle_patricia_trie_ethereum_verify_generated_state_trie() {
+    let mut builder = StateTrieBuilder::new();
+    for i in 1..=20u64 {
+        let address = Address::from_low_u64_be(i);
+        let balance = U256::from(i) * U256::exp10(18);
+        builder = builder.account(address, Account { nonce: i, balance, ..Default::default() });
+        for slot in 0..i {
+            builder = builder.storage(address, H256::from_low_u64_be(slot), U256::from(slot + 1));
+        }
+    }
+    let state = builder.build();
+
+    let mut env = TestEnv::shared().await;
+    let mut contract = env.contract("MerklePatriciaTest").await;
+
+    // the last address has no account
+    for i in 1..=21u64 {
+        let address = Address::from_low_u64_be(i);
+        let slots =
+            vec![H256::from_low_u64_be(0), H256::from_low_u64_be(i - 1), H256::repeat_byte(1)];
+        let proof = state.get_proof(address, &slots);
+
+        let result = contract
+            .call::<_, Vec<(Vec<u8>, Vec<u8>)>>(
+                "VerifyEthereum",
+                (
+                    Token::FixedBytes(state.root().as_bytes().to_vec()),
+                    Token::Array(proof.account_nodes().into_iter().map(Token::Bytes).collect()),
+                    Token::Array(vec![Token::Bytes(secure_key(address.as_bytes()))]),
+                ),
+            )
+            .await
+            .unwrap();
+        let account = decode_account(&result[0].1).unwrap();
+        assert_eq!(account, state.account(address).copied());
+        if account.is_none() {
+            continue
+        }
+
+        for storage_proof in proof.storage_proof {
+            let result = contract
+                .call::<_, Vec<(Vec<u8>, Vec<u8>)>>(
+                    "VerifyEthereum",
+                    (
+                        Token::FixedBytes(proof.storage_hash.as_bytes().to_vec()),
+                        Token::Array(
+                            storage_proof.nodes().into_iter().map(Token::Bytes).collect(),
+                        ),
+                        Token::Array(vec![Token::Bytes(secure_key(
+                            storage_proof.slot().as_bytes(),
+                        ))]),
+                    ),
+                )
+                .await
+                .unwrap();
+            assert_eq!(decode_slot(&result[0].1).unwrap(), storage_proof.value);
+        }
+    }
+}
+
+/// Reads raw storage words out of a contract deriving from `SlotReader`.
+async fn read_slots(contract: &mut Contract<'_>, slots: &[H256]) -> Vec<(H256, U256)> {
+    let tokens = slots.iter().map(|slot| Token::FixedBytes(slot.as_bytes().to_vec())).collect();
+    let words = contract
+        .call::<_, Vec<[u8; 32]>>("readSlots", (Token::Array(tokens)))
+        .await
+        .unwrap();
+
+    slots.iter().copied().zip(words.iter().map(|word| U256::from_big_endian(word))).collect()
+}
+
+/// Reads the words at `expected` and checks them against the values computed for them.
+async fn read_expected(
+    contract: &mut Contract<'_>,
+    expected: &[(StorageSlot, U256)],
+) -> Vec<(H256, U256)> {
+    let slots = expected.iter().map(|(slot, _)| slot.slot()).collect::<Vec<_>>();
+    let storage = read_slots(contract, &slots).await;
+    for ((slot, value), (_, word)) in expected.iter().zip(&storage) {
+        assert_eq!(word, value, "slot {:?}", slot.slot());
+    }
+
+    storage
+}
+
+/// Rebuilds the storage of the contract at `address` from the words read out of the EVM, then
+/// proves every word with `VerifyEthereum`.
+async fn verify_storage(env: &mut TestEnv, address: Address, storage: &[(H256, U256)]) {
+    let mut builder = StateTrieBuilder::new();
+    for (slot, value) in storage {
+        builder = builder.storage(address, *slot, *value);
+    }
+    let state = builder.build();
+    let slots = storage.iter().map(|(slot, _)| *slot).collect::<Vec<_>>();
+    let proof = state.get_proof(address, &slots);
+
+    let mut contract = env.contract("MerklePatriciaTest").await;
+    let result = contract
+        .call::<_, Vec<(Vec<u8>, Vec<u8>)>>(
+            "VerifyEthereum",
+            (
+                Token::FixedBytes(state.root().as_bytes().to_vec()),
+                Token::Array(proof.account_nodes().into_iter().map(Token::Bytes).collect()),
+                Token::Array(vec![Token::Bytes(secure_key(address.as_bytes()))]),
+            ),
+        )
+        .await
+        .unwrap();
+    let account = decode_account(&result[0].1).unwrap().unwrap();
+    assert_eq!(account.storage_root, proof.storage_hash);
+
+    for (storage_proof, (_, value)) in proof.storage_proof.iter().zip(storage) {
+        let result = contract
+            .call::<_, Vec<(Vec<u8>, Vec<u8>)>>(
+                "VerifyEthereum",
+                (
+                    Token::FixedBytes(proof.storage_hash.as_bytes().to_vec()),
+                    Token::Array(storage_proof.nodes().into_iter().map(Token::Bytes).collect()),
+                    Token::Array(vec![Token::Bytes(secure_key(storage_proof.slot().as_bytes()))]),
+                ),
+            )
+            .await
+            .unwrap();
+        assert_eq!(decode_slot(&result[0].1).unwrap(), *value);
+    }
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_merkle_patricia_trie_ethereum_verify_token_storage() {
+    let alice = Address::from_low_u64_be(0xa11ce);
+    let bob = Address::from_low_u64_be(0xb0b);
+    let bridge = Address::from_low_u64_be(0xb41d6e);
+    let ether = U256::exp10(18);
+
+    // the slots of the OpenZeppelin ERC20 that `BridgeableToken` builds on
+    let mut env = TestEnv::shared().await;
+    let mut contract = env.contract("BridgeableTokenStorageTest").await;
+    let token = contract.address;
+
+    let expected = [
+        (StorageSlot::new(0).mapping(&alice), ether * 1000),
+        (StorageSlot::new(0).mapping(&bob), ether * 250),
+        (StorageSlot::new(1).mapping(&alice).mapping(&bridge), U256::MAX),
+        (StorageSlot::new(2), ether * 1250),
+    ];
+    let mut storage = read_expected(&mut contract, &expected).await;
+
+    let strings: [(u64, &[u8]); 2] = [(3, b"Hyperbridge Bridgeable Test Token"), (4, b"BTK")];
+    for (slot, expected) in strings {
+        let slot = StorageSlot::new(slot);
+        let head = read_slots(&mut contract, &[slot.slot()]).await;
+        let data = read_slots(&mut contract, &bytes_data_slots(slot, head[0].1).unwrap()).await;
+        let words = data.iter().map(|(_, word)| *word).collect::<Vec<_>>();
+        assert_eq!(decode_bytes(head[0].1, &words).as_deref(), Some(expected));
+
+        let read = [head, data].concat();
+        assert_eq!(encode_bytes(slot, expected), read);
+        storage.extend(read);
+    }
+
+    verify_storage(&mut env, token, &storage).await;
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_merkle_patricia_trie_ethereum_verify_storage_layout() {
+    let ether = U256::exp10(18);
+
+    let mut env = TestEnv::shared().await;
+    let mut contract = env.contract("StorageLayoutTest").await;
+    let address = contract.address;
+
+    let transfer = StorageSlot::new(0).mapping(&H256(keccak256(b"first")));
+    let commitments = StorageSlot::new(1);
+    let commitment = |i: u64| U256::from_big_endian(&keccak256(<[u8; 32]>::from(U256::from(i))));
+    let expected = [
+        (transfer.field(1), ether * 42),
+        (commitments, U256::from(3)),
+        (commitments.array_element(0, 1), commitment(0)),
+        (commitments.array_element(2, 1), commitment(2)),
+        (StorageSlot::new(2).mapping("EVM-11155111"), ether / 1000),
+        (StorageSlot::new(2).mapping("POLKADOT-3367"), ether / 500),
+    ];
+    let mut storage = read_expected(&mut contract, &expected).await;
+
+    // address to; uint64 timeout; bool redeemed;
+    let (_, word) = read_slots(&mut contract, &[transfer.slot()]).await[0];
+    assert_eq!(PackedField::new(0, 20).unwrap().read(word), U256::from(0xb0b));
+    assert_eq!(PackedField::new(20, 8).unwrap().read(word), U256::from(1_700_000_000u64));
+    assert_eq!(PackedField::new(28, 1).unwrap().read(word), U256::one());
+    storage.push((transfer.slot(), word));
+
+    // uint64 nonce; bool paused; address owner;
+    let (_, word) = read_slots(&mut contract, &[StorageSlot::new(3).slot()]).await[0];
+    assert_eq!(PackedField::new(0, 8).unwrap().read(word), U256::from(7));
+    assert_eq!(PackedField::new(8, 1).unwrap().read(word), U256::one());
+    assert_eq!(PackedField::new(9, 20).unwrap().read(word), U256::from(0xa11ce));
+    storage.push((StorageSlot::new(3).slot(), word));
+
+    verify_storage(&mut env, address, &storage).await;
+}
+
+fn receipt(tx_type: u8, cumulative_gas_used: u64) -> Vec<u8> {
+    let mut stream = RlpStream::new_list(4);
+    stream.append(&1u8).append(&cumulative_gas_used).append(&vec![0u8; 256]).begin_list(0);
+    let receipt = stream.out().to_vec();
+
+    match tx_type {
+        0 => receipt,
+        _ => [vec![tx_type], receipt].concat(),
+    }
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_merkle_patricia_trie_ethereum_verify_generated_index_tries() {
+    let signature = Signature { r: U256::from(1), s: U256::from(2), v: 37 };
+    let transactions = (0..200u64)
+        .map(|i| {
+            let to = Address::from_low_u64_be(i);
+            let request =
+                TransactionRequest::new().nonce(i).to(to).value(i).gas(21_000).gas_price(1);
+            let transaction: TypedTransaction = match i % 3 {
+                0 => request.into(),
+                1 => Eip2930TransactionRequest::new(request, Default::default()).into(),
+                _ => Eip1559TransactionRequest::new()
+                    .nonce(i)
+                    .to(to)
+                    .value(i)
+                    .gas(21_000)
+                    .max_fee_per_gas(2)
+                    .max_priority_fee_per_gas(1)
+                    .into(),
+            };
+            transaction.rlp_signed(&signature).to_vec()
+        })
+        .collect::<Vec<_>>();
+    let receipts = (0..200u64).map(|i| receipt((i % 3) as u8, (i + 1) * 21_000));
+
+    let mut env = TestEnv::shared().await;
+    let mut contract = env.contract("MerklePatriciaTest").await;
+
+    for trie in [IndexTrie::new(transactions).unwrap(), IndexTrie::new(receipts).unwrap()] {
+        // the root a block header would commit to
+        let root = trie.root();
+
+        for index in [0, 1, 0x7f, 0x80, 0x81, trie.len() - 1] {
+            let proof = trie.prove(index).unwrap();
+            assert!(proof.verify(root));
+
+            let result = contract
+                .call::<_, Vec<(Vec<u8>, Vec<u8>)>>(
+                    "VerifyEthereum",
+                    (
+                        Token::FixedBytes(root.as_bytes().to_vec()),
+                        Token::Array(proof.proof.into_iter().map(Token::Bytes).collect()),
+                        Token::Array(vec![Token::Bytes(proof.key)]),
+                    ),
+                )
+                .await
+                .unwrap();
+            assert_eq!(result[0].1, proof.value);
+        }
+    }
 }
@@ -0,0 +1,266 @@
+//! Smallest proofs for a set of keys, in the substrate and ethereum trie layouts.
+//!
+//! Every key is looked up on its own, so the nodes each key needs are known. The proof is the
+//! union of those nodes without duplicates, ordered from the root by first use, which keeps
+//! calldata to what the verifier actually reads. Minimising a proof that was supplied from
+//! elsewhere also reports the nodes no key needs.
+
+use crate::ethereum_trie::EthereumLayout;
+use primitive_types::H256;
+use std::{collections::HashMap, fmt};
+use trie_db::{DBValue, HashDBRef, Recorder, Trie, TrieDBBuilder, TrieError, TrieHash, TrieLayout};
+
+/// Error types for proof minimisation
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MinimalProofError {
+    /// A node on the path to the key is missing
+    IncompleteProof { key: Vec<u8> },
+    /// A node on the path to the key could not be decoded
+    InvalidNode { key: Vec<u8> },
+}
+
+impl fmt::Display for MinimalProofError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            MinimalProofError::IncompleteProof { key } =>
+                write!(f, "proof is missing a node on the path to {}", hex::encode(key)),
+            MinimalProofError::InvalidNode { key } =>
+                write!(f, "invalid node on the path to {}", hex::encode(key)),
+        }
+    }
+}
+
+/// A proof holding only the nodes needed to look up its keys
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct MinimalProof {
+    /// Proof nodes without duplicates, ordered from the root by first use
+    pub nodes: Vec<Vec<u8>>,
+    /// Indices into `nodes` used by each key, in the order of the keys
+    pub nodes_per_key: Vec<Vec<usize>>,
+    /// Indices into the supplied proof of the nodes no key needs, duplicates included
+    pub redundant: Vec<usize>,
+    /// Bytes taken up by the redundant nodes
+    pub redundant_bytes: usize,
+}
+
+impl MinimalProof {
+    /// Collects the nodes visited by each key, given as a lookup that returns the nodes visited
+    /// for a key, ordered from the root.
+    fn new<E>(
+        keys: &[Vec<u8>],
+        mut lookup: impl FnMut(&[u8]) -> Result<Vec<Vec<u8>>, E>,
+    ) -> Result<Self, E> {
+        let mut proof = MinimalProof::default();
+        let mut indices = HashMap::<Vec<u8>, usize>::new();
+
+        for key in keys {
+            let mut used = vec![];
+            for node in lookup(key)? {
+                let index = *indices.entry(node.clone()).or_insert_with(|| {
+                    proof.nodes.push(node);
+                    proof.nodes.len() - 1
+                });
+                if !used.contains(&index) {
+                    used.push(index);
+                }
+            }
+            proof.nodes_per_key.push(used);
+        }
+
+        Ok(proof)
+    }
+
+    /// Marks the nodes of the `supplied` proof that are not part of this proof, along with
+    /// repeated nodes.
+    fn with_supplied(mut self, supplied: &[Vec<u8>]) -> Self {
+        let mut seen = vec![false; self.nodes.len()];
+        for (index, node) in supplied.iter().enumerate() {
+            match self.nodes.iter().position(|used| used == node) {
+                Some(position) if !seen[position] => seen[position] = true,
+                _ => {
+                    self.redundant.push(index);
+                    self.redundant_bytes += node.len();
+                },
+            }
+        }
+
+        self
+    }
+
+    /// Describes the redundant nodes of the supplied proof, if it had any.
+    pub fn warning(&self) -> Option<String> {
+        if self.redundant.is_empty() {
+            return None
+        }
+
+        Some(format!(
+            "proof holds {} redundant nodes ({} bytes) at indices {:?}",
+            self.redundant.len(),
+            self.redundant_bytes,
+            self.redundant
+        ))
+    }
+
+    /// Bytes taken up by the proof nodes
+    pub fn len_bytes(&self) -> usize {
+        self.nodes.iter().map(Vec::len).sum()
+    }
+}
+
+/// Smallest proof of `keys` in the substrate trie at `root`, built out of the nodes in `db`.
+pub fn prove_substrate<L: TrieLayout>(
+    db: &dyn HashDBRef<L::Hash, DBValue>,
+    root: &TrieHash<L>,
+    keys: &[Vec<u8>],
+) -> Result<MinimalProof, MinimalProofError> {
+    MinimalProof::new(keys, |key| {
+        let mut recorder = Recorder::<L>::new();
+        {
+            let trie = TrieDBBuilder::<L>::new(db, root).with_recorder(&mut recorder).build();
+            trie.get(key).map_err(|err| match *err {
+                TrieError::InvalidStateRoot(_) | TrieError::IncompleteDatabase(_) =>
+                    MinimalProofError::IncompleteProof { key: key.to_vec() },
+                _ => MinimalProofError::InvalidNode { key: key.to_vec() },
+            })?;
+        }
+        Ok(recorder.drain().into_iter().map(|record| record.data).collect())
+    })
+}
+
+/// Minimises a substrate proof of `keys` in the trie at `root`, reporting its redundant nodes.
+pub fn minimise_substrate<L: TrieLayout>(
+    root: &TrieHash<L>,
+    proof: &[Vec<u8>],
+    keys: &[Vec<u8>],
+) -> Result<MinimalProof, MinimalProofError> {
+    let db = sp_trie::StorageProof::new(proof.to_vec()).into_memory_db::<L::Hash>();
+    Ok(prove_substrate::<L>(&db, root, keys)?.with_supplied(proof))
+}
+
+/// Smallest proof of `keys` in the ethereum trie at `root`, built out of the nodes in `db`.
+pub fn prove_ethereum(
+    db: &dyn eth_trie_db::HashDBRef<patricia_merkle_trie::keccak::KeccakHasher, Vec<u8>>,
+    root: H256,
+    keys: &[Vec<u8>],
+) -> Result<MinimalProof, MinimalProofError> {
+    let mut hash = eth_trie_db::TrieHash::<EthereumLayout>::default();
+    hash.as_mut().copy_from_slice(root.as_bytes());
+
+    MinimalProof::new(keys, |key| {
+        let mut recorder = eth_trie_db::Recorder::<EthereumLayout>::new();
+        {
+            let trie = eth_trie_db::TrieDBBuilder::<EthereumLayout>::new(db, &hash)
+                .with_recorder(&mut recorder)
+                .build();
+            eth_trie_db::Trie::get(&trie, key).map_err(|err| match *err {
+                eth_trie_db::TrieError::InvalidStateRoot(_) |
+                eth_trie_db::TrieError::IncompleteDatabase(_) =>
+                    MinimalProofError::IncompleteProof { key: key.to_vec() },
+                _ => MinimalProofError::InvalidNode { key: key.to_vec() },
+            })?;
+        }
+        Ok(recorder.drain().into_iter().map(|record| record.data).collect())
+    })
+}
+
+/// Minimises an ethereum proof of `keys` in the trie at `root`, reporting its redundant nodes.
+pub fn minimise_ethereum(
+    root: H256,
+    proof: &[Vec<u8>],
+    keys: &[Vec<u8>],
+) -> Result<MinimalProof, MinimalProofError> {
+    let db = patricia_merkle_trie::StorageProof::new(proof.to_vec())
+        .into_memory_db::<patricia_merkle_trie::keccak::KeccakHasher>();
+    Ok(prove_ethereum(&db, root, keys)?.with_supplied(proof))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ethereum_trie::{read_proof, EthereumTrie};
+    use sp_core::KeccakHasher;
+    use sp_trie::{LayoutV1, MemoryDB};
+    use trie_db::{TrieDBMutBuilder, TrieMut};
+
+    type Layout = LayoutV1<KeccakHasher>;
+
+    /// Values on both sides of the inline threshold of `LayoutV1`
+    fn entries() -> Vec<(Vec<u8>, Vec<u8>)> {
+        (0u32..200)
+            .map(|i| {
+                let key = sp_core::keccak_256(&i.to_le_bytes()).to_vec();
+                (key, vec![i as u8; 1 + i as usize % 50])
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_minimise_substrate_proof() {
+        let entries = entries();
+        let mut db = MemoryDB::<KeccakHasher>::default();
+        let mut root = Default::default();
+        {
+            let mut trie = TrieDBMutBuilder::<Layout>::new(&mut db, &mut root).build();
+            for (key, value) in &entries {
+                trie.insert(key, value).unwrap();
+            }
+        }
+
+        // a proof of every key, narrowed down to a few of them and a key that is absent
+        let all = entries.iter().map(|(key, _)| key.clone()).collect::<Vec<_>>();
+        let supplied = prove_substrate::<Layout>(&db, &root, &all).unwrap().nodes;
+        let mut keys = all.iter().step_by(40).cloned().collect::<Vec<_>>();
+        keys.push(vec![0xff; 32]);
+
+        let minimal = minimise_substrate::<Layout>(&root, &supplied, &keys).unwrap();
+        assert_eq!(minimal.nodes_per_key.len(), keys.len());
+        let last = minimal.nodes.len() - 1;
+        assert_eq!(minimal.nodes_per_key.iter().flatten().max(), Some(&last));
+        assert!(minimal.nodes_per_key.iter().all(|used| used[0] == 0));
+        assert_eq!(minimal.redundant.len(), supplied.len() - minimal.nodes.len());
+        assert!(minimal.warning().is_some());
+
+        // nothing left to remove
+        let again = minimise_substrate::<Layout>(&root, &minimal.nodes, &keys).unwrap();
+        assert_eq!(again.nodes, minimal.nodes);
+        assert_eq!(again.warning(), None);
+
+        // repeated nodes are redundant too
+        let mut repeated = minimal.nodes.clone();
+        repeated.push(minimal.nodes[0].clone());
+        let again = minimise_substrate::<Layout>(&root, &repeated, &keys).unwrap();
+        assert_eq!(again.redundant, vec![minimal.nodes.len()]);
+
+        assert_eq!(
+            minimise_substrate::<Layout>(&root, &minimal.nodes[1..], &keys),
+            Err(MinimalProofError::IncompleteProof { key: keys[0].clone() })
+        );
+    }
+
+    #[test]
+    fn test_minimise_ethereum_proof() {
+        let entries = entries();
+        let trie = EthereumTrie::new(entries.clone());
+        let root = trie.root();
+
+        let all = entries.iter().map(|(key, _)| key.clone()).collect::<Vec<_>>();
+        let supplied = trie.prove(&all);
+        let mut keys = all.iter().step_by(40).cloned().collect::<Vec<_>>();
+        keys.push(vec![0xff; 32]);
+
+        let minimal = minimise_ethereum(root, &supplied, &keys).unwrap();
+        assert_eq!(minimal.nodes, trie.prove(&keys));
+        assert_eq!(minimal.redundant.len(), supplied.len() - minimal.nodes.len());
+        assert!(minimal.len_bytes() < supplied.iter().map(Vec::len).sum::<usize>());
+
+        for (key, used) in keys.iter().zip(&minimal.nodes_per_key) {
+            let nodes = used.iter().map(|index| minimal.nodes[*index].clone()).collect::<Vec<_>>();
+            assert_eq!(read_proof(root, &nodes, key).unwrap(), trie.get(key));
+        }
+
+        assert_eq!(
+            minimise_ethereum(root, &minimal.nodes[1..], &keys),
+            Err(MinimalProofError::IncompleteProof { key: keys[0].clone() })
+        );
+    }
+}
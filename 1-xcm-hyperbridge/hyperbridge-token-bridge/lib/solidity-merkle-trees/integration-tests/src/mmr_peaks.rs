@@ -0,0 +1,232 @@
+use crate::{keccak256, mmr_utils::subtree_heights};
+
+/// Error types for the peaks-only merkle mountain range
+#[derive(Debug, PartialEq)]
+pub enum MmrPeaksError {
+    /// The number of peaks does not match the number of leaves
+    PeakCountMismatch { expected: usize, actual: usize },
+    /// Leaf index is not part of the accumulator
+    InvalidIndex(u64),
+    /// Proof path does not match the height of the leaf's mountain
+    InvalidProofLength { expected: usize, actual: usize },
+}
+
+/// A merkle mountain range accumulator that only keeps its peaks around.
+///
+/// This is the state a light client needs to follow an MMR: it can accept new leaves, produce the
+/// same bagged root as `MerkleMountainRange.CalculateRoot` and verify leaf proofs against its
+/// peaks, all without storing any of the inner nodes.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct MmrPeaks {
+    /// Number of leaves appended so far
+    leaf_count: u64,
+    /// Peaks of the mountains as `(height, hash)`, ordered from left to right
+    peaks: Vec<(u32, [u8; 32])>,
+}
+
+impl MmrPeaks {
+    /// Creates an empty accumulator
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Restores an accumulator from its leaf count and the peak hashes, ordered from left to
+    /// right. There must be exactly one peak for every set bit in `leaf_count`.
+    pub fn from_peaks(leaf_count: u64, peaks: Vec<[u8; 32]>) -> Result<Self, MmrPeaksError> {
+        let heights = subtree_heights(leaf_count);
+        if heights.len() != peaks.len() {
+            return Err(MmrPeaksError::PeakCountMismatch {
+                expected: heights.len(),
+                actual: peaks.len(),
+            })
+        }
+
+        Ok(Self { leaf_count, peaks: heights.into_iter().zip(peaks).collect() })
+    }
+
+    /// Number of leaves appended so far
+    pub fn leaf_count(&self) -> u64 {
+        self.leaf_count
+    }
+
+    /// Peak hashes, ordered from left to right
+    pub fn peaks(&self) -> Vec<[u8; 32]> {
+        self.peaks.iter().map(|(_, hash)| *hash).collect()
+    }
+
+    /// Appends a leaf hash, merging every mountain of equal height on the right. Returns the
+    /// index of the new leaf.
+    pub fn append(&mut self, leaf: [u8; 32]) -> u64 {
+        let mut height = 0;
+        let mut hash = leaf;
+
+        while let Some((peak_height, peak)) = self.peaks.last().copied() {
+            if peak_height != height {
+                break
+            }
+            self.peaks.pop();
+            hash = merge(peak, hash);
+            height += 1;
+        }
+        self.peaks.push((height, hash));
+
+        let index = self.leaf_count;
+        self.leaf_count += 1;
+        index
+    }
+
+    /// Bags the peaks from right to left, exactly like `MerkleMountainRange.CalculateRoot`.
+    /// Returns `None` if no leaves have been appended.
+    pub fn root(&self) -> Option<[u8; 32]> {
+        let mut peaks = self.peaks();
+        let mut root = peaks.pop()?;
+        while let Some(left) = peaks.pop() {
+            root = merge(root, left);
+        }
+
+        Some(root)
+    }
+
+    /// Verifies a leaf against the peaks. `path` holds the sibling hashes from the leaf up to,
+    /// but excluding, the peak of the mountain that contains it.
+    pub fn verify_leaf(
+        &self,
+        leaf_index: u64,
+        leaf: [u8; 32],
+        path: &[[u8; 32]],
+    ) -> Result<bool, MmrPeaksError> {
+        if leaf_index >= self.leaf_count {
+            return Err(MmrPeaksError::InvalidIndex(leaf_index))
+        }
+
+        let mut offset = 0;
+        for (height, peak) in &self.peaks {
+            let size = 1u64 << height;
+            if leaf_index >= offset + size {
+                offset += size;
+                continue
+            }
+
+            if path.len() != *height as usize {
+                return Err(MmrPeaksError::InvalidProofLength {
+                    expected: *height as usize,
+                    actual: path.len(),
+                })
+            }
+
+            let mut position = leaf_index - offset;
+            let mut hash = leaf;
+            for sibling in path {
+                hash =
+                    if position % 2 == 0 { merge(hash, *sibling) } else { merge(*sibling, hash) };
+                position /= 2;
+            }
+
+            return Ok(&hash == peak)
+        }
+
+        unreachable!("leaf_index < leaf_count, so some mountain must contain it; qed")
+    }
+}
+
+fn merge(left: [u8; 32], right: [u8; 32]) -> [u8; 32] {
+    keccak256(&[left, right].concat())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{MergeKeccak, NumberHash};
+    use ckb_merkle_mountain_range::{util::MemStore, MMR};
+
+    fn leaf_hash(leaf: &NumberHash) -> [u8; 32] {
+        leaf.0.clone().try_into().expect("keccak hashes are 32 bytes")
+    }
+
+    #[test]
+    fn test_root_matches_ckb_after_every_append() {
+        let store = MemStore::default();
+        let mut mmr = MMR::<_, MergeKeccak, _>::new(0, &store);
+        let mut peaks = MmrPeaks::new();
+
+        assert_eq!(peaks.root(), None);
+
+        for i in 0u32..300 {
+            let leaf = NumberHash::from(i);
+            mmr.push(leaf.clone()).unwrap();
+            assert_eq!(peaks.append(leaf_hash(&leaf)), i as u64);
+
+            let root = mmr.get_root().unwrap();
+            let root = Some(leaf_hash(&root));
+            assert_eq!(peaks.root(), root, "root mismatch after {} leaves", i + 1);
+            assert_eq!(peaks.peaks().len(), (i + 1).count_ones() as usize);
+        }
+    }
+
+    #[test]
+    fn test_restore_from_peaks() {
+        let mut peaks = MmrPeaks::new();
+        for i in 0u32..11 {
+            peaks.append(leaf_hash(&NumberHash::from(i)));
+        }
+
+        let mut restored = MmrPeaks::from_peaks(11, peaks.peaks()).unwrap();
+        assert_eq!(restored, peaks);
+
+        restored.append(leaf_hash(&NumberHash::from(11)));
+        peaks.append(leaf_hash(&NumberHash::from(11)));
+        assert_eq!(restored.root(), peaks.root());
+
+        assert_eq!(
+            MmrPeaks::from_peaks(11, vec![[0u8; 32]]),
+            Err(MmrPeaksError::PeakCountMismatch { expected: 3, actual: 1 })
+        );
+    }
+
+    #[test]
+    fn test_verify_leaf() {
+        let count = 100u32;
+        let store = MemStore::default();
+        let mut mmr = MMR::<_, MergeKeccak, _>::new(0, &store);
+        let mut peaks = MmrPeaks::new();
+
+        let positions = (0..count)
+            .map(|i| {
+                peaks.append(leaf_hash(&NumberHash::from(i)));
+                mmr.push(NumberHash::from(i)).unwrap()
+            })
+            .collect::<Vec<_>>();
+
+        let heights = subtree_heights(count as u64);
+        for i in 0..count {
+            // the in-mountain siblings come first in a single-leaf ckb proof, followed by the
+            // other peaks.
+            let mut offset = 0;
+            let height = heights
+                .iter()
+                .find(|height| {
+                    offset += 1 << **height;
+                    (i as u64) < offset
+                })
+                .copied()
+                .unwrap();
+            let proof = mmr.gen_proof(vec![positions[i as usize]]).unwrap();
+            let path = proof.proof_items()[..height as usize]
+                .iter()
+                .map(leaf_hash)
+                .collect::<Vec<_>>();
+
+            let leaf = leaf_hash(&NumberHash::from(i));
+            assert!(peaks.verify_leaf(i as u64, leaf, &path).unwrap());
+            assert!(!peaks.verify_leaf(i as u64, [0u8; 32], &path).unwrap());
+            if height > 0 {
+                assert!(peaks.verify_leaf(i as u64, leaf, &[]).is_err());
+            }
+        }
+
+        assert_eq!(
+            peaks.verify_leaf(count as u64, [0u8; 32], &[]),
+            Err(MmrPeaksError::InvalidIndex(count as u64))
+        );
+    }
+}
@@ -0,0 +1,96 @@
+//! Proofs of merkle mountain ranges generated with the ckb implementation, in the shape
+//! `MerkleMountainRange.CalculateRoot` takes them.
+
+use crate::{mmr_utils, proof_mutation::MmrProof, MergeKeccak, NumberHash, Token};
+use ckb_merkle_mountain_range::{mmr_position_to_k_index, util::MemStore, MerkleProof, MMR};
+use primitive_types::U256;
+use std::panic;
+
+/// Abi-encodes the arguments of `CalculateRoot`
+pub fn calculate_root_args(
+    custom_leaves: Vec<(u32, usize, [u8; 32])>,
+    proof_items: Vec<Vec<u8>>,
+    mmr_size: u64,
+) -> (Vec<Token>, Vec<Token>, Token) {
+    let token_leaves = custom_leaves
+        .into_iter()
+        .map(|(pos, index, hash)| {
+            Token::Tuple(vec![
+                Token::Uint(U256::from(index)),
+                Token::Uint(U256::from(pos)),
+                Token::FixedBytes(hash.to_vec()),
+            ])
+        })
+        .collect::<Vec<_>>();
+
+    let nodes = proof_items.iter().map(|n| Token::FixedBytes(n.clone())).collect::<Vec<_>>();
+
+    (nodes, token_leaves, Token::Uint(mmr_size.into()))
+}
+
+/// Builds an mmr of `count` leaves and proves `proof_elem` using the ckb implementation. Returns
+/// the root, the proof items and the leaves as `(leaf_index, k_index, hash)`.
+pub fn generate_mmr_proof(
+    count: u32,
+    mut proof_elem: Vec<u32>,
+) -> ([u8; 32], Vec<Vec<u8>>, Vec<(u32, usize, [u8; 32])>) {
+    proof_elem.sort();
+    let store = MemStore::default();
+    let mut mmr = MMR::<_, MergeKeccak, _>::new(0, &store);
+
+    let positions: Vec<u64> =
+        (0u32..count).map(|i| mmr.push(NumberHash::from(i)).unwrap()).collect();
+
+    let root = mmr.get_root().expect("get root");
+    let proof = mmr
+        .gen_proof(proof_elem.iter().map(|elem| positions[*elem as usize]).collect())
+        .expect("gen proof");
+    mmr.commit().expect("commit changes");
+
+    let leaves = proof_elem
+        .iter()
+        .map(|elem| (positions[*elem as usize], NumberHash::from(*elem)))
+        .collect::<Vec<_>>();
+    let result = proof.verify(root.clone(), leaves.clone()).unwrap();
+    assert!(result);
+
+    // simplified proof verification
+
+    let mut custom_leaves = leaves
+        .into_iter()
+        .zip(proof_elem.clone().into_iter())
+        .map(|((pos, leaf), index)| {
+            let k_index = mmr_position_to_k_index(vec![pos], proof.mmr_size())[0].1;
+            let mut hash = [0u8; 32];
+            hash.copy_from_slice(&leaf.0);
+            (index, k_index, hash)
+        })
+        .collect::<Vec<_>>();
+
+    custom_leaves.dedup_by(|a, b| a.0 == b.0);
+    custom_leaves.sort_by(|a, b| a.0.cmp(&b.0));
+
+    let mut root_hash = [0u8; 32];
+    root_hash.copy_from_slice(&root.0);
+    let proof_items = proof.proof_items().to_vec().into_iter().map(|n| n.0).collect();
+
+    (root_hash, proof_items, custom_leaves)
+}
+
+/// Calculates the root of a proof with the ckb implementation, `None` if it fails or panics
+pub fn native_calculate_root(proof: &MmrProof) -> Option<[u8; 32]> {
+    let mmr_size = mmr_utils::leaf_count_to_mmr_size(proof.leaf_count);
+    let items = proof.items.iter().cloned().map(NumberHash).collect::<Vec<_>>();
+    let leaves = proof
+        .leaves
+        .iter()
+        .map(|(index, _, hash)| {
+            (mmr_utils::leaf_index_to_pos(*index as u64), NumberHash(hash.to_vec()))
+        })
+        .collect::<Vec<_>>();
+
+    let root = panic::catch_unwind(move || {
+        MerkleProof::<_, MergeKeccak>::new(mmr_size, items).calculate_root(leaves)
+    });
+    root.ok()?.ok()?.0.try_into().ok()
+}
@@ -0,0 +1,237 @@
+//! Native ports of the index helpers in `MerkleMountainRange.sol`, along with conversions between
+//! mmr positions, leaf indices and k-indices.
+
+use crate::Token;
+use primitive_types::U256;
+
+/// Mirror of the `MmrLeaf` struct in `Types.sol`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct MmrLeaf {
+    /// Index of the leaf within its mountain, counted from the left
+    pub k_index: u64,
+    /// Index of the leaf within the whole mmr
+    pub leaf_index: u64,
+    /// Hash of the leaf
+    pub hash: [u8; 32],
+}
+
+impl MmrLeaf {
+    /// Abi-encodes the leaf in the field order of the solidity struct
+    pub fn to_token(&self) -> Token {
+        Token::Tuple(vec![
+            Token::Uint(U256::from(self.k_index)),
+            Token::Uint(U256::from(self.leaf_index)),
+            Token::FixedBytes(self.hash.to_vec()),
+        ])
+    }
+}
+
+impl From<(u64, u64, [u8; 32])> for MmrLeaf {
+    fn from((k_index, leaf_index, hash): (u64, u64, [u8; 32])) -> Self {
+        MmrLeaf { k_index, leaf_index, hash }
+    }
+}
+
+/// Returns the elements of `left` that are not in `right`, preserving their order.
+/// Mirrors `MerkleMountainRange.difference`.
+pub fn difference(left: &[u64], right: &[u64]) -> Vec<u64> {
+    left.iter().filter(|index| !right.contains(index)).copied().collect()
+}
+
+/// Returns the index of the sibling of every node. Mirrors `MerkleMountainRange.siblingIndices`.
+pub fn sibling_indices(indices: &[u64]) -> Vec<u64> {
+    indices.iter().map(|index| if index % 2 == 0 { index + 1 } else { index - 1 }).collect()
+}
+
+/// Returns the index of the parent of every node, collapsing consecutive duplicates.
+/// Mirrors `MerkleMountainRange.parentIndices`.
+pub fn parent_indices(indices: &[u64]) -> Vec<u64> {
+    let mut parents = indices.iter().map(|index| index / 2).collect::<Vec<_>>();
+    parents.dedup();
+    parents
+}
+
+/// Heights of the mountains for an mmr with `leaf_count` leaves, ordered from left to right.
+/// Mirrors `MerkleMountainRange.subtreeHeights`.
+pub fn subtree_heights(leaf_count: u64) -> Vec<u32> {
+    (0..u64::BITS).rev().filter(|bit| leaf_count & (1u64 << bit) != 0).collect()
+}
+
+/// Converts leaves to `(k_index, hash)` nodes and their indices.
+/// Mirrors `MerkleMountainRange.mmrLeafToNode`.
+pub fn mmr_leaf_to_node(leaves: &[MmrLeaf]) -> (Vec<(u64, [u8; 32])>, Vec<u64>) {
+    leaves.iter().map(|leaf| ((leaf.k_index, leaf.hash), leaf.k_index)).unzip()
+}
+
+/// Splits sorted leaves into those before `leaf_index` and the rest.
+/// Mirrors `MerkleMountainRange.leavesForSubtree`.
+pub fn leaves_for_subtree(leaves: &[MmrLeaf], leaf_index: u64) -> (&[MmrLeaf], &[MmrLeaf]) {
+    let p = leaves.iter().position(|leaf| leaf_index <= leaf.leaf_index).unwrap_or(leaves.len());
+    leaves.split_at(p)
+}
+
+/// Number of nodes in an mmr with `leaf_count` leaves
+pub fn leaf_count_to_mmr_size(leaf_count: u64) -> u64 {
+    // `2 * leaf_count` overflows for 2^63 leaves, whose mmr is `u64::MAX` nodes
+    leaf_count - leaf_count.count_ones() as u64 + leaf_count
+}
+
+/// Number of leaves in an mmr with `mmr_size` nodes. Sizes that don't describe a complete mmr
+/// are rounded down to the largest complete mmr that fits.
+pub fn mmr_size_to_leaf_count(mut mmr_size: u64) -> u64 {
+    let mut leaf_count = 0;
+    for height in (0..u64::BITS).rev() {
+        let mountain_size = u64::MAX >> (u64::BITS - 1 - height);
+        if mountain_size <= mmr_size {
+            mmr_size -= mountain_size;
+            leaf_count += 1 << height;
+        }
+    }
+    leaf_count
+}
+
+/// Position of a leaf in the mmr
+pub fn leaf_index_to_pos(leaf_index: u64) -> u64 {
+    leaf_count_to_mmr_size(leaf_index + 1) - (leaf_index + 1).trailing_zeros() as u64 - 1
+}
+
+/// Height of the node at `pos`, leaves are at height 0
+pub fn pos_height_in_tree(pos: u64) -> u32 {
+    let mut pos = pos + 1;
+    let all_ones = |num: u64| num != 0 && num.count_zeros() == num.leading_zeros();
+    while !all_ones(pos) {
+        let most_significant_bit = 1u64 << (u64::BITS - pos.leading_zeros() - 1);
+        pos -= most_significant_bit - 1;
+    }
+    u64::BITS - pos.leading_zeros() - 1
+}
+
+/// Leaf index of the leaf at `pos`, or `None` if `pos` is not a leaf
+pub fn pos_to_leaf_index(pos: u64) -> Option<u64> {
+    if pos_height_in_tree(pos) != 0 {
+        return None
+    }
+
+    // every node before a leaf belongs to a complete mmr.
+    Some(mmr_size_to_leaf_count(pos))
+}
+
+/// The k-index of a leaf, i.e. its index within its own mountain, or `None` if the leaf is not
+/// part of an mmr with `leaf_count` leaves.
+pub fn leaf_index_to_k_index(leaf_index: u64, leaf_count: u64) -> Option<u64> {
+    if leaf_index >= leaf_count {
+        return None
+    }
+
+    let mut offset = 0;
+    for height in subtree_heights(leaf_count) {
+        let size = 1u64 << height;
+        if leaf_index < offset + size {
+            return Some(leaf_index - offset)
+        }
+        offset += size;
+    }
+
+    None
+}
+
+/// Leaf index of the leaf with `k_index` in the `mountain`-th mountain from the left, or `None`
+/// if there is no such leaf in an mmr with `leaf_count` leaves.
+pub fn k_index_to_leaf_index(k_index: u64, mountain: usize, leaf_count: u64) -> Option<u64> {
+    let heights = subtree_heights(leaf_count);
+    let height = *heights.get(mountain)?;
+    if k_index >= 1 << height {
+        return None
+    }
+
+    let offset = heights[..mountain].iter().map(|height| 1u64 << height).sum::<u64>();
+    Some(offset + k_index)
+}
+
+/// The k-index of the leaf at `pos` in an mmr of `mmr_size` nodes. Agrees with
+/// `ckb_merkle_mountain_range::mmr_position_to_k_index`.
+pub fn pos_to_k_index(pos: u64, mmr_size: u64) -> Option<u64> {
+    leaf_index_to_k_index(pos_to_leaf_index(pos)?, mmr_size_to_leaf_count(mmr_size))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ckb_merkle_mountain_range::{
+        leaf_index_to_mmr_size, leaf_index_to_pos as ckb_leaf_index_to_pos,
+        mmr_position_to_k_index,
+    };
+    use proptest::{prop_assert_eq, prop_oneof, proptest, strategy::Just};
+
+    #[test]
+    fn test_index_helpers() {
+        assert_eq!(difference(&[3, 4], &[2, 5]), vec![3, 4]);
+        assert_eq!(difference(&[2, 3, 4, 5], &[2, 5]), vec![3, 4]);
+        assert_eq!(sibling_indices(&[0, 2, 5]), vec![1, 3, 4]);
+        assert_eq!(parent_indices(&[2, 3, 4, 7]), vec![1, 2, 3]);
+        assert_eq!(subtree_heights(11), vec![3, 1, 0]);
+        assert_eq!(subtree_heights(0), Vec::<u32>::new());
+    }
+
+    #[test]
+    fn test_position_conversions() {
+        let positions = vec![0, 1, 3, 4, 7, 8, 10, 11, 15, 16, 18, 19, 22, 23, 25];
+        for (leaf_index, pos) in positions.into_iter().enumerate() {
+            assert_eq!(leaf_index_to_pos(leaf_index as u64), pos);
+            assert_eq!(pos_to_leaf_index(pos), Some(leaf_index as u64));
+        }
+
+        assert_eq!(pos_to_leaf_index(2), None);
+        assert_eq!(leaf_count_to_mmr_size(11), 19);
+        assert_eq!(mmr_size_to_leaf_count(19), 11);
+
+        // 11 leaves: mountains of 8, 2 and 1 leaves
+        assert_eq!(leaf_index_to_k_index(9, 11), Some(1));
+        assert_eq!(leaf_index_to_k_index(10, 11), Some(0));
+        assert_eq!(leaf_index_to_k_index(11, 11), None);
+        assert_eq!(k_index_to_leaf_index(1, 1, 11), Some(9));
+        assert_eq!(k_index_to_leaf_index(2, 1, 11), None);
+        assert_eq!(k_index_to_leaf_index(0, 3, 11), None);
+    }
+
+    proptest! {
+        #[test]
+        fn test_conversions_match_ckb(leaf_count in 1u64..100_000, seed in 0u64..u64::MAX) {
+            let leaf_index = seed % leaf_count;
+            let mmr_size = leaf_index_to_mmr_size(leaf_count - 1);
+            let pos = ckb_leaf_index_to_pos(leaf_index);
+
+            prop_assert_eq!(leaf_count_to_mmr_size(leaf_count), mmr_size);
+            prop_assert_eq!(mmr_size_to_leaf_count(mmr_size), leaf_count);
+            prop_assert_eq!(leaf_index_to_pos(leaf_index), pos);
+            prop_assert_eq!(pos_to_leaf_index(pos), Some(leaf_index));
+
+            let k_index = mmr_position_to_k_index(vec![pos], mmr_size)[0].1 as u64;
+            prop_assert_eq!(pos_to_k_index(pos, mmr_size), Some(k_index));
+            prop_assert_eq!(leaf_index_to_k_index(leaf_index, leaf_count), Some(k_index));
+
+            let mountain = subtree_heights(leaf_count)
+                .iter()
+                .scan(0u64, |offset, height| {
+                    *offset += 1 << height;
+                    Some(*offset)
+                })
+                .position(|end| leaf_index < end)
+                .unwrap();
+            prop_assert_eq!(k_index_to_leaf_index(k_index, mountain, leaf_count), Some(leaf_index));
+        }
+
+        #[test]
+        fn test_sizes_round_trip_up_to_u64_max(
+            leaf_count in prop_oneof![Just(1u64 << 63), (1u64 << 62)..(1u64 << 63)],
+        ) {
+            let mmr_size = leaf_count_to_mmr_size(leaf_count);
+            prop_assert_eq!(mmr_size_to_leaf_count(mmr_size), leaf_count);
+            // sizes between two complete mmrs round down
+            prop_assert_eq!(mmr_size_to_leaf_count(mmr_size + (leaf_count & 1)), leaf_count);
+            if leaf_count == 1 << 63 {
+                prop_assert_eq!(mmr_size, u64::MAX);
+            }
+        }
+    }
+}
@@ -0,0 +1,927 @@
+//! Native mirrors of `MerklePatricia.VerifySubstrateProof` and `VerifyEthereumProof`.
+//!
+//! Both follow the contract step by step, so they return what the contract would without running
+//! the EVM, quirks included:
+//!
+//! - keys that are absent and keys holding an empty value both resolve to an empty value.
+//! - ethereum branches treat any child that is not a 32 byte hash as absent. Keys below an inline
+//!   node resolve to an empty value, and an extension pointing to an inline node is rejected.
+//! - an ethereum branch value starting with `0x80` or `0xc0` is treated as no value.
+//! - ethereum leaves are matched on whole bytes, from the byte after the last nibble consumed.
+//!
+//! [`read_substrate_proof`] and [`read_ethereum_proof`] walk the proof the same way, but tell a
+//! key the proof shows to be absent apart from one the proof doesn't show: its path leaves the
+//! proof, which the contract reverts on, or goes through an inline node, which the contract reads
+//! as absent whether or not the key is in the trie.
+//!
+//! The substrate functions are generic over the trie's hasher, so proofs of parachains hashing
+//! their state with [`Blake2Hasher`](sp_core::Blake2Hasher) are read the way the contract reads
+//! keccak proofs, e.g. `verify_substrate_proof::<Blake2Hasher>(root, &proof, &keys)`. Only the
+//! native verifier reads them: `VerifySubstrateProof` hashes with keccak, and there is no
+//! on-chain verification of blake2 proofs.
+//!
+//! Proofs the contract reverts on are rejected with the matching [`VerifyError`]. The contract's
+//! RLP reader does not check item lengths against the node, so RLP that it would read past the
+//! end of a node is rejected as [`VerifyError::InvalidRlp`] rather than decoded from whatever
+//! memory follows. RLP that is only non-canonical is read the way the contract reads it.
+
+use crate::ethereum_trie::EMPTY_ROOT;
+use primitive_types::H256;
+use serde::Serialize;
+use sp_core::KeccakHasher;
+use std::{cell::Cell, fmt};
+use trie_db::Hasher;
+
+/// Reasons the contract reverts
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum VerifyError {
+    /// `Incomplete Proof!`: the node with this hash, on the path to a key, is not in the proof
+    IncompleteProof(H256),
+    /// `Unallowed encoding`: a substrate node header is not one of the known kinds
+    UnallowedEncoding,
+    /// `Bad Format!`: the padding nibble of an odd substrate partial key is not zero
+    BadFormat,
+    /// `unexpected prefix decoding Compact<Uint>`: a compact length is longer than 8 bytes
+    InvalidCompact,
+    /// A field runs past the end of its node
+    OutOfRange,
+    /// `Invalid data`: an ethereum node is not a list of 0, 2 or 17 items
+    InvalidData,
+    /// An ethereum node is not well formed RLP
+    InvalidRlp,
+    /// `Bytes:: toBytes32: data is to short.`: an ethereum extension points to an inline node
+    ShortHash,
+}
+
+impl fmt::Display for VerifyError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let reason = match self {
+            VerifyError::IncompleteProof(_) => "Incomplete Proof!",
+            VerifyError::UnallowedEncoding => "Unallowed encoding",
+            VerifyError::BadFormat => "Bad Format!",
+            VerifyError::InvalidCompact => "unexpected prefix decoding Compact<Uint>",
+            VerifyError::OutOfRange => "Out of range",
+            VerifyError::InvalidData => "Invalid data",
+            VerifyError::InvalidRlp => "invalid rlp",
+            VerifyError::ShortHash => "Bytes:: toBytes32: data is to short.",
+        };
+        write!(f, "{reason}")
+    }
+}
+
+/// Mirror of `NibbleSlice` and `NibbleSliceOps`: the nibbles of `data`, starting `offset`
+/// nibbles in. An offset past the end of `data` makes the length underflow, which the contract
+/// reverts on, so it is reported as `OutOfRange`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Nibbles<'a> {
+    pub data: &'a [u8],
+    pub offset: usize,
+}
+
+impl<'a> Nibbles<'a> {
+    pub fn new(data: &'a [u8], offset: usize) -> Self {
+        Nibbles { data, offset }
+    }
+
+    pub fn len(&self) -> Result<usize, VerifyError> {
+        (self.data.len() * 2).checked_sub(self.offset).ok_or(VerifyError::OutOfRange)
+    }
+
+    pub fn is_empty(&self) -> Result<bool, VerifyError> {
+        Ok(self.len()? == 0)
+    }
+
+    pub fn mid(&self, i: usize) -> Self {
+        Nibbles { data: self.data, offset: self.offset + i }
+    }
+
+    /// The nibble at `i`. Panics if it is past the end, where the contract reverts.
+    pub fn at(&self, i: usize) -> u8 {
+        let byte = self.data[(self.offset + i) / 2];
+        if (self.offset + i) % 2 == 1 {
+            byte & 0x0f
+        } else {
+            byte >> 4
+        }
+    }
+
+    pub fn equals(&self, other: &Nibbles<'_>) -> Result<bool, VerifyError> {
+        Ok(self.len()? == other.len()? && self.starts_with(other)?)
+    }
+
+    pub fn starts_with(&self, other: &Nibbles<'_>) -> Result<bool, VerifyError> {
+        Ok(self.common_prefix(other)? == other.len()?)
+    }
+
+    /// Mirrors `NibbleSliceOps.commonPrefix`, which compares whole bytes when both slices have
+    /// the same alignment.
+    pub fn common_prefix(&self, other: &Nibbles<'_>) -> Result<usize, VerifyError> {
+        let max = self.len()?.min(other.len()?);
+        let self_align = self.offset % 2;
+        if self_align != other.offset % 2 {
+            return Ok((0..max).take_while(|i| self.at(*i) == other.at(*i)).count())
+        }
+        if max == 0 {
+            return Ok(0)
+        }
+
+        let mut self_start = self.offset / 2;
+        let mut other_start = other.offset / 2;
+        let mut first = 0;
+        if self_align != 0 {
+            if self.data[self_start] & 0x0f != other.data[other_start] & 0x0f {
+                return Ok(0)
+            }
+            self_start += 1;
+            other_start += 1;
+            first = 1;
+        }
+
+        Ok(biggest_depth(&self.data[self_start..], &other.data[other_start..]) + first)
+    }
+}
+
+fn biggest_depth(a: &[u8], b: &[u8]) -> usize {
+    for (i, (a, b)) in a.iter().zip(b).enumerate() {
+        if a != b {
+            let left_common = if a & 0xf0 == b & 0xf0 { 1 } else { 0 };
+            return i * 2 + left_common
+        }
+    }
+
+    a.len().min(b.len()) * 2
+}
+
+/// Mirror of `NodeHandle`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Handle<'a> {
+    Hash(&'a [u8]),
+    Inline(&'a [u8]),
+}
+
+/// The work the contract does to look up some keys, which is what its gas cost grows with
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize)]
+pub struct Traversal {
+    /// Nodes decoded on the way to the keys, inline nodes and repeated visits included
+    pub steps: usize,
+    /// Proof nodes compared against a hash by `TrieDB.get`, which scans the proof linearly
+    pub scanned: usize,
+    /// Total length of the decoded nodes
+    pub decoded_bytes: usize,
+}
+
+/// The proof nodes along with their hashes, like the `TrieNode` list the contract builds
+struct Nodes<'a> {
+    nodes: Vec<([u8; 32], &'a [u8])>,
+    traversal: Cell<Traversal>,
+}
+
+impl<'a> Nodes<'a> {
+    fn new<H: Hasher<Out = H256>>(proof: &'a [Vec<u8>]) -> Self {
+        Nodes {
+            nodes: proof.iter().map(|node| (H::hash(node).0, node.as_slice())).collect(),
+            traversal: Cell::default(),
+        }
+    }
+
+    /// Mirrors `TrieDB.get`
+    fn get(&self, hash: &[u8]) -> Result<&'a [u8], VerifyError> {
+        let position = self.nodes.iter().position(|(node_hash, _)| node_hash.as_slice() == hash);
+        let scanned = position.map_or(self.nodes.len(), |i| i + 1);
+        let node = position
+            .map(|i| self.nodes[i].1)
+            .ok_or_else(|| VerifyError::IncompleteProof(H256::from_slice(hash)))?;
+        self.visit(node, scanned);
+        Ok(node)
+    }
+
+    /// Mirrors `TrieDB.load`
+    fn load(&self, handle: Handle<'a>) -> Result<&'a [u8], VerifyError> {
+        match handle {
+            Handle::Hash(hash) => self.get(hash),
+            Handle::Inline(data) => {
+                self.visit(data, 0);
+                Ok(data)
+            },
+        }
+    }
+
+    fn visit(&self, node: &[u8], scanned: usize) {
+        let mut traversal = self.traversal.get();
+        traversal.steps += 1;
+        traversal.scanned += scanned;
+        traversal.decoded_bytes += node.len();
+        self.traversal.set(traversal);
+    }
+}
+
+/// What a proof shows about a key
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum KeyProof {
+    /// The key holds this value
+    Present(Vec<u8>),
+    /// The proof shows the key is not in the trie
+    ProvenAbsent,
+    /// The proof doesn't show the key: the node with this hash, on the path to the key, is not
+    /// in the proof, or is an inline node the contract doesn't follow
+    Incomplete(H256),
+}
+
+impl KeyProof {
+    fn new(lookup: Result<Option<Vec<u8>>, VerifyError>) -> Result<Self, VerifyError> {
+        match lookup {
+            Ok(Some(value)) => Ok(KeyProof::Present(value)),
+            Ok(None) => Ok(KeyProof::ProvenAbsent),
+            Err(VerifyError::IncompleteProof(hash)) => Ok(KeyProof::Incomplete(hash)),
+            Err(err) => Err(err),
+        }
+    }
+}
+
+/// Mirror of `VerifySubstrateProof` for a trie hashed with `H`, returning the key and value of
+/// every key.
+pub fn verify_substrate_proof<H: Hasher<Out = H256>>(
+    root: H256,
+    proof: &[Vec<u8>],
+    keys: &[Vec<u8>],
+) -> Result<Vec<(Vec<u8>, Vec<u8>)>, VerifyError> {
+    let nodes = Nodes::new::<H>(proof);
+    keys.iter()
+        .map(|key| Ok((key.clone(), substrate_lookup(&nodes, root, key)?.unwrap_or_default())))
+        .collect()
+}
+
+/// Looks up `keys` in a substrate proof of a trie hashed with `H`. Fails only where the contract
+/// would revert for a reason other than a missing node.
+pub fn read_substrate_proof<H: Hasher<Out = H256>>(
+    root: H256,
+    proof: &[Vec<u8>],
+    keys: &[Vec<u8>],
+) -> Result<Vec<KeyProof>, VerifyError> {
+    let nodes = Nodes::new::<H>(proof);
+    keys.iter().map(|key| KeyProof::new(substrate_lookup(&nodes, root, key))).collect()
+}
+
+/// Walks a substrate proof of a trie hashed with `H` like `VerifySubstrateProof` does and returns
+/// the work it takes.
+pub fn substrate_traversal<H: Hasher<Out = H256>>(
+    root: H256,
+    proof: &[Vec<u8>],
+    keys: &[Vec<u8>],
+) -> Result<Traversal, VerifyError> {
+    let nodes = Nodes::new::<H>(proof);
+    for key in keys {
+        substrate_lookup(&nodes, root, key)?;
+    }
+    Ok(nodes.traversal.get())
+}
+
+fn substrate_lookup(
+    nodes: &Nodes<'_>,
+    root: H256,
+    key: &[u8],
+) -> Result<Option<Vec<u8>>, VerifyError> {
+    let mut key = Nibbles::new(key, 0);
+    let mut encoded = nodes.get(root.as_bytes())?;
+
+    loop {
+        let next = match SubstrateNode::decode(encoded)? {
+            SubstrateNode::Empty => return Ok(None),
+            SubstrateNode::Leaf { key: partial, value } => {
+                if !partial.equals(&key)? {
+                    return Ok(None)
+                }
+                return Ok(Some(nodes.load(value)?.to_vec()))
+            },
+            SubstrateNode::Branch { key: partial, value, children } => {
+                if !key.starts_with(&partial)? {
+                    return Ok(None)
+                }
+                if key.len()? == partial.len()? {
+                    return match value {
+                        Some(value) => Ok(Some(nodes.load(value)?.to_vec())),
+                        None => Ok(None),
+                    }
+                }
+                match children[key.at(partial.len()?) as usize] {
+                    Some(child) => {
+                        key = key.mid(partial.len()? + 1);
+                        child
+                    },
+                    None => return Ok(None),
+                }
+            },
+        };
+
+        encoded = nodes.load(next)?;
+    }
+}
+
+/// Mirror of `ByteSlice` and the reads of `Bytes` and `ScaleCodec`
+struct Input<'a> {
+    data: &'a [u8],
+    offset: usize,
+}
+
+impl<'a> Input<'a> {
+    fn read_byte(&mut self) -> Result<u8, VerifyError> {
+        let byte = *self.data.get(self.offset).ok_or(VerifyError::OutOfRange)?;
+        self.offset += 1;
+        Ok(byte)
+    }
+
+    fn read(&mut self, len: usize) -> Result<&'a [u8], VerifyError> {
+        let end = self.offset.checked_add(len).ok_or(VerifyError::OutOfRange)?;
+        let slice = self.data.get(self.offset..end).ok_or(VerifyError::OutOfRange)?;
+        self.offset = end;
+        Ok(slice)
+    }
+
+    /// Mirrors `ScaleCodec.decodeUintCompact`
+    fn compact(&mut self) -> Result<usize, VerifyError> {
+        let b = self.read_byte()?;
+        let value = match b % 4 {
+            0 => (b >> 2) as u64,
+            1 => ((self.read_byte()? as u64) << 6) + (b >> 2) as u64,
+            2 => {
+                let rest = self.read(3)?;
+                u32::from_le_bytes([b, rest[0], rest[1], rest[2]]) as u64 >> 2
+            },
+            _ => {
+                let len = (b >> 2) as usize + 4;
+                if len > 8 {
+                    return Err(VerifyError::InvalidCompact)
+                }
+                let mut bytes = [0u8; 8];
+                bytes[..len].copy_from_slice(self.read(len)?);
+                u64::from_le_bytes(bytes)
+            },
+        };
+
+        usize::try_from(value).map_err(|_| VerifyError::OutOfRange)
+    }
+
+    /// Mirrors `SubstrateTrieDB.decodeSize`
+    fn size(&mut self, first: u8, prefix_bits: u32) -> Result<usize, VerifyError> {
+        const NIBBLE_SIZE_BOUND: usize = u16::MAX as usize;
+
+        let max = (255u8 >> prefix_bits) as usize;
+        let mut result = first as usize & max;
+        if result < max {
+            return Ok(result)
+        }
+
+        result -= 1;
+        while result <= NIBBLE_SIZE_BOUND {
+            let n = self.read_byte()? as usize;
+            if n < 255 {
+                return Ok(result + n + 1)
+            }
+            result += 255;
+        }
+
+        Ok(NIBBLE_SIZE_BOUND)
+    }
+
+    /// A partial key of `nibble_size` nibbles, padded on the left
+    fn partial_key(&mut self, nibble_size: usize) -> Result<Nibbles<'a>, VerifyError> {
+        if nibble_size % 2 == 1 {
+            let first = *self.data.get(self.offset).ok_or(VerifyError::OutOfRange)?;
+            if first & 0xf0 != 0 {
+                return Err(VerifyError::BadFormat)
+            }
+        }
+
+        Ok(Nibbles::new(self.read((nibble_size + 1) / 2)?, nibble_size % 2))
+    }
+
+    fn hash(&mut self) -> Result<Handle<'a>, VerifyError> {
+        Ok(Handle::Hash(self.read(32)?))
+    }
+
+    fn inline(&mut self) -> Result<Handle<'a>, VerifyError> {
+        let len = self.compact()?;
+        Ok(Handle::Inline(self.read(len)?))
+    }
+}
+
+/// A substrate node, decoded like `SubstrateTrieDB` does
+enum SubstrateNode<'a> {
+    Empty,
+    Leaf { key: Nibbles<'a>, value: Handle<'a> },
+    Branch { key: Nibbles<'a>, value: Option<Handle<'a>>, children: [Option<Handle<'a>>; 16] },
+}
+
+impl<'a> SubstrateNode<'a> {
+    fn decode(encoded: &'a [u8]) -> Result<Self, VerifyError> {
+        let mut input = Input { data: encoded, offset: 0 };
+        let first = input.read_byte()?;
+        if first == 0 {
+            return Ok(SubstrateNode::Empty)
+        }
+
+        // (is a leaf, has a hashed value, prefix bits)
+        let (is_leaf, hashed_value, prefix_bits) = match first >> 6 {
+            0b01 => (true, false, 2),
+            0b11 | 0b10 => (false, false, 2),
+            _ if first >> 5 == 0b001 => (true, true, 3),
+            _ if first >> 4 == 0b0001 => (false, true, 4),
+            _ => return Err(VerifyError::UnallowedEncoding),
+        };
+        let nibble_size = input.size(first, prefix_bits)?;
+        let key = input.partial_key(nibble_size)?;
+
+        if is_leaf {
+            let value = if hashed_value { input.hash()? } else { input.inline()? };
+            return Ok(SubstrateNode::Leaf { key, value })
+        }
+
+        let bitmap = input.read(2)?;
+        let bitmap = u16::from_le_bytes([bitmap[0], bitmap[1]]);
+        let value = match (hashed_value, first >> 6) {
+            (true, _) => Some(input.hash()?),
+            (false, 0b11) => Some(input.inline()?),
+            _ => None,
+        };
+
+        let mut children = [None; 16];
+        for (i, child) in children.iter_mut().enumerate() {
+            if bitmap & (1 << i) == 0 {
+                continue
+            }
+            let len = input.compact()?;
+            let data = input.read(len)?;
+            *child = Some(if len == 32 { Handle::Hash(data) } else { Handle::Inline(data) });
+        }
+
+        Ok(SubstrateNode::Branch { key, value, children })
+    }
+}
+
+/// Mirror of `VerifyEthereumProof`, returning the key and value of every key.
+pub fn verify_ethereum_proof(
+    root: H256,
+    proof: &[Vec<u8>],
+    keys: &[Vec<u8>],
+) -> Result<Vec<(Vec<u8>, Vec<u8>)>, VerifyError> {
+    let nodes = Nodes::new::<KeccakHasher>(proof);
+    keys.iter()
+        .map(|key| {
+            let value = ethereum_lookup(&nodes, root, key)?.value().unwrap_or_default();
+            Ok((key.clone(), value))
+        })
+        .collect()
+}
+
+/// Looks up `keys` in an ethereum proof. Fails only where the contract would revert for a
+/// reason other than a missing node.
+pub fn read_ethereum_proof(
+    root: H256,
+    proof: &[Vec<u8>],
+    keys: &[Vec<u8>],
+) -> Result<Vec<KeyProof>, VerifyError> {
+    let nodes = Nodes::new::<KeccakHasher>(proof);
+    keys.iter()
+        .map(|key| match ethereum_lookup(&nodes, root, key) {
+            Ok(EthereumLookup::BelowInlineNode(hash)) => Ok(KeyProof::Incomplete(hash)),
+            lookup => KeyProof::new(lookup.map(EthereumLookup::value)),
+        })
+        .collect()
+}
+
+/// Walks an ethereum proof like `VerifyEthereumProof` does and returns the work it takes.
+pub fn ethereum_traversal(
+    root: H256,
+    proof: &[Vec<u8>],
+    keys: &[Vec<u8>],
+) -> Result<Traversal, VerifyError> {
+    let nodes = Nodes::new::<KeccakHasher>(proof);
+    for key in keys {
+        ethereum_lookup(&nodes, root, key)?;
+    }
+    Ok(nodes.traversal.get())
+}
+
+/// Where a lookup in an ethereum proof ends
+enum EthereumLookup {
+    /// The value of the key, `None` if it is absent
+    Value(Option<Vec<u8>>),
+    /// An inline node with this hash on the path to the key, which the contract reads as absent
+    BelowInlineNode(H256),
+}
+
+impl EthereumLookup {
+    /// The value the contract reads
+    fn value(self) -> Option<Vec<u8>> {
+        match self {
+            EthereumLookup::Value(value) => value,
+            EthereumLookup::BelowInlineNode(_) => None,
+        }
+    }
+}
+
+fn ethereum_lookup(
+    nodes: &Nodes<'_>,
+    root: H256,
+    key: &[u8],
+) -> Result<EthereumLookup, VerifyError> {
+    let mut key = Nibbles::new(key, 0);
+    let mut encoded = nodes.get(root.as_bytes())?;
+
+    loop {
+        let next = match EthereumNode::decode(encoded)? {
+            EthereumNode::Empty => return Ok(EthereumLookup::Value(None)),
+            EthereumNode::Leaf { key: partial, value } => {
+                let rest = key.data.get((key.offset + 1) / 2..).ok_or(VerifyError::OutOfRange)?;
+                return Ok(EthereumLookup::Value((partial == rest).then(|| value.to_vec())))
+            },
+            EthereumNode::Extension { key: partial, child } => {
+                if !key.starts_with(&partial)? {
+                    return Ok(EthereumLookup::Value(None))
+                }
+                let cut = key.offset + partial.len()?;
+                let data = key.data.get(cut / 2..).ok_or(VerifyError::OutOfRange)?;
+                key = Nibbles::new(data, cut % 2);
+                child
+            },
+            EthereumNode::Branch { value, children } => {
+                if key.is_empty()? {
+                    return Ok(EthereumLookup::Value(value.map(<[u8]>::to_vec)))
+                }
+                match children[key.at(0) as usize] {
+                    Some(child) if child.len() == 32 => {
+                        key = key.mid(1);
+                        child
+                    },
+                    Some(node) =>
+                        return Ok(EthereumLookup::BelowInlineNode(KeccakHasher::hash(node))),
+                    None => return Ok(EthereumLookup::Value(None)),
+                }
+            },
+        };
+
+        encoded = nodes.get(next)?;
+    }
+}
+
+/// An ethereum node, decoded like `EthereumTrieDB` does. Extension children are always hashes,
+/// branch children are hashes or whole inline nodes.
+enum EthereumNode<'a> {
+    Empty,
+    /// The key holds the bytes of the compact encoded path after its first byte
+    Leaf { key: &'a [u8], value: &'a [u8] },
+    Extension { key: Nibbles<'a>, child: &'a [u8] },
+    Branch { value: Option<&'a [u8]>, children: [Option<&'a [u8]>; 16] },
+}
+
+impl<'a> EthereumNode<'a> {
+    fn decode(encoded: &'a [u8]) -> Result<Self, VerifyError> {
+        if encoded == EMPTY_ROOT.as_bytes() {
+            return Ok(EthereumNode::Empty)
+        }
+
+        let items = rlp_list(encoded)?;
+        match items.len() {
+            0 => Ok(EthereumNode::Empty),
+            2 => {
+                let path = rlp_payload(items[0])?;
+                let first = *path.first().ok_or(VerifyError::OutOfRange)?;
+                let value = rlp_payload(items[1])?;
+                if matches!(first >> 4, 2 | 3) {
+                    return Ok(EthereumNode::Leaf { key: &path[1..], value })
+                }
+
+                let is_odd = (first >> 4) & 1;
+                let key = Nibbles::new(&path[(is_odd as usize + 1) % 2..], is_odd as usize);
+                let child = value.get(..32).ok_or(VerifyError::ShortHash)?;
+                Ok(EthereumNode::Extension { key, child })
+            },
+            17 => {
+                let mut children = [None; 16];
+                for (child, item) in children.iter_mut().zip(&items) {
+                    let data = rlp_payload(item)?;
+                    *child = match data.len() {
+                        0 => None,
+                        32 => Some(data),
+                        _ => Some(*item),
+                    };
+                }
+                // the contract reads an empty value too, but only treats these as no value
+                let value = rlp_payload(items[16])?;
+                let is_empty = matches!(value.first(), None | Some(0x80 | 0xc0));
+                Ok(EthereumNode::Branch { value: (!is_empty).then_some(value), children })
+            },
+            _ => Err(VerifyError::InvalidData),
+        }
+    }
+}
+
+/// Mirrors `RLPReader.toList`. Items run until the end of `encoded`, whatever the length in the
+/// list header.
+fn rlp_list(encoded: &[u8]) -> Result<Vec<&[u8]>, VerifyError> {
+    match encoded.first() {
+        Some(first) if *first >= 0xc0 => {},
+        _ => return Err(VerifyError::InvalidRlp),
+    }
+
+    let mut items = vec![];
+    let mut offset = rlp_payload_offset(encoded[0]);
+    while offset < encoded.len() {
+        let len = rlp_item_len(&encoded[offset..])?;
+        items.push(encoded.get(offset..offset.saturating_add(len)).ok_or(VerifyError::InvalidRlp)?);
+        offset += len;
+    }
+
+    Ok(items)
+}
+
+/// Mirrors `RLPReader.toBytes`: the item without its header
+fn rlp_payload(item: &[u8]) -> Result<&[u8], VerifyError> {
+    let first = *item.first().ok_or(VerifyError::InvalidRlp)?;
+    item.get(rlp_payload_offset(first)..).ok_or(VerifyError::InvalidRlp)
+}
+
+/// Mirrors `RLPReader._payloadOffset`
+fn rlp_payload_offset(first: u8) -> usize {
+    match first {
+        0x00..=0x7f => 0,
+        0x80..=0xb7 | 0xc0..=0xf7 => 1,
+        0xb8..=0xbf => (first - 0xb7) as usize + 1,
+        0xf8..=0xff => (first - 0xf7) as usize + 1,
+    }
+}
+
+/// Mirrors `RLPReader._itemLength`
+fn rlp_item_len(item: &[u8]) -> Result<usize, VerifyError> {
+    let first = item[0];
+    let long_len = |len_bytes: usize| {
+        let len = item.get(1..1 + len_bytes).ok_or(VerifyError::InvalidRlp)?;
+        let len = len.iter().fold(0usize, |len, byte| len << 8 | *byte as usize);
+        len.checked_add(len_bytes + 1).ok_or(VerifyError::InvalidRlp)
+    };
+
+    match first {
+        0x00..=0x7f => Ok(1),
+        0x80..=0xb7 => Ok((first - 0x80) as usize + 1),
+        0xb8..=0xbf => long_len((first - 0xb7) as usize),
+        0xc0..=0xf7 => Ok((first - 0xc0) as usize + 1),
+        0xf8..=0xff => long_len((first - 0xf7) as usize),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ethereum_trie::{secure_key, EthereumTrie};
+    use sp_core::Blake2Hasher;
+    use sp_trie::{LayoutV0, LayoutV1, MemoryDB};
+    use std::collections::HashSet;
+    use trie_db::{Recorder, Trie, TrieDBBuilder, TrieDBMutBuilder, TrieLayout, TrieMut};
+
+    /// Builds a substrate trie and proves `keys`, returning the root, the proof and the value of
+    /// every key according to `trie_db`.
+    fn substrate_proof<L: TrieLayout>(
+        entries: &[(Vec<u8>, Vec<u8>)],
+        keys: &[Vec<u8>],
+    ) -> (H256, Vec<Vec<u8>>, Vec<Option<Vec<u8>>>)
+    where
+        L::Hash: Hasher<Out = H256>,
+    {
+        let mut db = MemoryDB::<L::Hash>::default();
+        let mut root = Default::default();
+        {
+            let mut trie = TrieDBMutBuilder::<L>::new(&mut db, &mut root).build();
+            for (key, value) in entries {
+                trie.insert(key, value).unwrap();
+            }
+        }
+
+        let mut recorder = Recorder::<L>::new();
+        let values = {
+            let trie = TrieDBBuilder::<L>::new(&db, &root).with_recorder(&mut recorder).build();
+            keys.iter().map(|key| trie.get(key).unwrap()).collect()
+        };
+        // the recorder records the nodes of every lookup, the root included
+        let mut seen = HashSet::new();
+        let proof = recorder
+            .drain()
+            .into_iter()
+            .map(|record| record.data)
+            .filter(|node| seen.insert(node.clone()))
+            .collect();
+
+        (root, proof, values)
+    }
+
+    /// Keys of 1 to `max_key_len` bytes, so some keys are prefixes of others
+    fn random_entries(count: usize, max_key_len: usize) -> Vec<(Vec<u8>, Vec<u8>)> {
+        (0..count)
+            .map(|i| {
+                let key_len = 1 + rand::random::<usize>() % max_key_len;
+                let key = (0..key_len).map(|_| rand::random::<u8>()).collect();
+                let value = (0..[1, 8, 32, 33, 100][i % 5]).map(|_| rand::random::<u8>()).collect();
+                (key, value)
+            })
+            .collect()
+    }
+
+    fn check_substrate<L: TrieLayout>(max_key_len: usize)
+    where
+        L::Hash: Hasher<Out = H256>,
+    {
+        let entries = random_entries(200, max_key_len);
+        let mut keys = entries.iter().step_by(3).map(|(key, _)| key.clone()).collect::<Vec<_>>();
+        keys.extend(random_entries(20, max_key_len).into_iter().map(|(key, _)| key));
+
+        let (root, proof, values) = substrate_proof::<L>(&entries, &keys);
+        let result = verify_substrate_proof::<L::Hash>(root, &proof, &keys).unwrap();
+        for ((key, expected), (result_key, value)) in keys.iter().zip(values).zip(result) {
+            assert_eq!(key, &result_key);
+            assert_eq!(value, expected.unwrap_or_default(), "value of {}", hex::encode(key));
+        }
+
+        assert_eq!(
+            verify_substrate_proof::<L::Hash>(root, &proof[1..], &keys),
+            Err(VerifyError::IncompleteProof(root))
+        );
+    }
+
+    #[test]
+    fn test_substrate_matches_trie_db() {
+        // short keys make for inline nodes
+        for max_key_len in [2, 32] {
+            check_substrate::<LayoutV0<KeccakHasher>>(max_key_len);
+            check_substrate::<LayoutV1<KeccakHasher>>(max_key_len);
+            check_substrate::<LayoutV0<Blake2Hasher>>(max_key_len);
+            check_substrate::<LayoutV1<Blake2Hasher>>(max_key_len);
+        }
+    }
+
+    #[test]
+    fn test_substrate_proofs_need_their_hasher() {
+        let entries = random_entries(50, 32);
+        let keys = entries.iter().map(|(key, _)| key.clone()).collect::<Vec<_>>();
+
+        let (root, proof, _) = substrate_proof::<LayoutV1<Blake2Hasher>>(&entries, &keys);
+        assert!(verify_substrate_proof::<Blake2Hasher>(root, &proof, &keys).is_ok());
+        assert_eq!(
+            verify_substrate_proof::<KeccakHasher>(root, &proof, &keys),
+            Err(VerifyError::IncompleteProof(root))
+        );
+
+        let (root, proof, _) = substrate_proof::<LayoutV1<KeccakHasher>>(&entries, &keys);
+        assert_eq!(
+            verify_substrate_proof::<Blake2Hasher>(root, &proof, &keys),
+            Err(VerifyError::IncompleteProof(root))
+        );
+    }
+
+    #[test]
+    fn test_ethereum_matches_trie_db() {
+        let entries = random_entries(300, 20)
+            .into_iter()
+            .map(|(key, value)| (secure_key(&key), value))
+            .collect::<Vec<_>>();
+        let trie = EthereumTrie::new(entries.clone());
+
+        let mut keys = entries.iter().step_by(3).map(|(key, _)| key.clone()).collect::<Vec<_>>();
+        keys.push(secure_key(b"absent"));
+        let proof = trie.prove(&keys);
+
+        let result = verify_ethereum_proof(trie.root(), &proof, &keys).unwrap();
+        for (key, (_, value)) in keys.iter().zip(result) {
+            assert_eq!(value, trie.get(key).unwrap_or_default());
+        }
+    }
+
+    #[test]
+    fn test_ethereum_keys_below_inline_nodes() {
+        // single nibble keys below a single branch: every leaf is small enough to be inlined
+        let entries = (1u8..16).map(|i| (vec![i], vec![i])).collect::<Vec<_>>();
+        let trie = EthereumTrie::new(entries.clone());
+        let keys = entries.iter().map(|(key, _)| key.clone()).collect::<Vec<_>>();
+        let proof = trie.prove(&keys);
+
+        // the contract reads them as absent, but the proof doesn't show that they are
+        let branch = proof.iter().map(|node| rlp_list(node).unwrap()).find(|n| n.len() == 17);
+        let branch = branch.unwrap();
+        let result = verify_ethereum_proof(trie.root(), &proof, &keys).unwrap();
+        let outcomes = read_ethereum_proof(trie.root(), &proof, &keys).unwrap();
+        for ((key, (_, value)), outcome) in keys.iter().zip(result).zip(outcomes) {
+            assert!(trie.get(key).is_some());
+            assert!(value.is_empty());
+            let inline = branch[(key[0] & 0x0f) as usize];
+            assert_eq!(outcome, KeyProof::Incomplete(KeccakHasher::hash(inline)));
+        }
+
+        // next to a hashed leaf: keys below an inline leaf are never proven absent, keys below
+        // the hashed leaf or an empty child are
+        let mut entries = entries;
+        entries.push((vec![0x10; 32], vec![0x10; 32]));
+        let trie = EthereumTrie::new(entries);
+        let keys = [vec![0x10; 32], vec![0x05], vec![0x01, 0x05], vec![0x11], vec![0xf0]];
+        let proof = trie.prove(&keys);
+        let outcomes = read_ethereum_proof(trie.root(), &proof, &keys).unwrap();
+        assert_eq!(outcomes[0], KeyProof::Present(vec![0x10; 32]));
+        for outcome in &outcomes[1..3] {
+            // inline nodes are not in the proof on their own
+            let KeyProof::Incomplete(hash) = outcome else { panic!("{outcome:?}") };
+            assert!(proof.iter().all(|node| KeccakHasher::hash(node) != *hash));
+        }
+        assert_eq!(outcomes[3..], [KeyProof::ProvenAbsent, KeyProof::ProvenAbsent]);
+
+        // a branch small enough to be inlined in the extension above it
+        let trie = EthereumTrie::new(vec![(vec![1], vec![1]), (vec![2], vec![2])]);
+        let proof = trie.prove(&[vec![1]]);
+        assert_eq!(
+            verify_ethereum_proof(trie.root(), &proof, &[vec![1]]),
+            Err(VerifyError::ShortHash)
+        );
+    }
+
+    type ReadProof = fn(H256, &[Vec<u8>], &[Vec<u8>]) -> Result<Vec<KeyProof>, VerifyError>;
+
+    /// Drops every node of the proof in turn. Keys whose path goes through the dropped node
+    /// are incomplete, the others keep their outcome.
+    fn check_truncated<H: Hasher<Out = H256>>(
+        read: ReadProof,
+        root: H256,
+        proof: &[Vec<u8>],
+        keys: &[Vec<u8>],
+        values: &[Option<Vec<u8>>],
+    ) {
+        let expected = values
+            .iter()
+            .map(|value| value.clone().map_or(KeyProof::ProvenAbsent, KeyProof::Present))
+            .collect::<Vec<_>>();
+        assert_eq!(read(root, proof, keys).unwrap(), expected);
+
+        for i in 0..proof.len() {
+            let mut truncated = proof.to_vec();
+            let missing = H::hash(&truncated.remove(i));
+
+            let outcomes = read(root, &truncated, keys).unwrap();
+            for (outcome, expected) in outcomes.iter().zip(&expected) {
+                assert!(outcome == expected || *outcome == KeyProof::Incomplete(missing));
+            }
+            assert!(outcomes.contains(&KeyProof::Incomplete(missing)));
+        }
+    }
+
+    #[test]
+    fn test_truncated_substrate_proofs() {
+        let entries = random_entries(50, 32);
+        let mut keys = entries.iter().step_by(4).map(|(key, _)| key.clone()).collect::<Vec<_>>();
+        keys.extend(random_entries(5, 32).into_iter().map(|(key, _)| key));
+
+        let (root, proof, values) = substrate_proof::<LayoutV0<KeccakHasher>>(&entries, &keys);
+        let read = read_substrate_proof::<KeccakHasher>;
+        check_truncated::<KeccakHasher>(read, root, &proof, &keys, &values);
+
+        // hashed values are nodes of their own
+        let (root, proof, values) = substrate_proof::<LayoutV1<KeccakHasher>>(&entries, &keys);
+        check_truncated::<KeccakHasher>(read, root, &proof, &keys, &values);
+
+        let (root, proof, values) = substrate_proof::<LayoutV1<Blake2Hasher>>(&entries, &keys);
+        let read = read_substrate_proof::<Blake2Hasher>;
+        check_truncated::<Blake2Hasher>(read, root, &proof, &keys, &values);
+    }
+
+    #[test]
+    fn test_truncated_ethereum_proofs() {
+        let entries = random_entries(50, 20)
+            .into_iter()
+            .map(|(key, value)| (secure_key(&key), value))
+            .collect::<Vec<_>>();
+        let trie = EthereumTrie::new(entries.clone());
+
+        let mut keys = entries.iter().step_by(4).map(|(key, _)| key.clone()).collect::<Vec<_>>();
+        keys.push(secure_key(b"absent"));
+        let values = keys.iter().map(|key| trie.get(key)).collect::<Vec<_>>();
+
+        let proof = trie.prove(&keys);
+        check_truncated::<KeccakHasher>(read_ethereum_proof, trie.root(), &proof, &keys, &values);
+    }
+
+    #[test]
+    fn test_nibbles_common_prefix() {
+        let a = Nibbles::new(&[0x12, 0x34, 0x56], 0);
+        let b = Nibbles::new(&[0x12, 0x35], 0);
+        assert_eq!(a.common_prefix(&b), Ok(3));
+        assert_eq!(a.mid(1).common_prefix(&b.mid(1)), Ok(2));
+        assert_eq!(a.mid(1).common_prefix(&Nibbles::new(&[0x23], 0)), Ok(2));
+        assert_eq!(a.starts_with(&Nibbles::new(&[0x01, 0x23], 1)), Ok(true));
+        assert_eq!(a.starts_with(&Nibbles::new(&[0x13], 0)), Ok(false));
+        assert_eq!(a.mid(1).starts_with(&Nibbles::new(&[0x02, 0x34], 1)), Ok(true));
+        assert_eq!(a.mid(4).equals(&Nibbles::new(&[0x56], 0)), Ok(true));
+    }
+
+    #[test]
+    fn test_nibbles_past_the_end() {
+        // an odd extension path of one byte leaves no data behind an offset of one
+        let empty = Nibbles::new(&[], 1);
+        assert_eq!(empty.len(), Err(VerifyError::OutOfRange));
+        assert_eq!(Nibbles::new(&[0x12], 0).mid(3).is_empty(), Err(VerifyError::OutOfRange));
+        assert_eq!(Nibbles::new(&[0x12], 0).starts_with(&empty), Err(VerifyError::OutOfRange));
+    }
+}
@@ -0,0 +1,966 @@
+//! Corrupted variants of valid proofs, for checking that verifiers never return a wrong value.
+//!
+//! A [`Mutation`] changes a proof in one way: a flipped bit, a dropped, duplicated or reordered
+//! node, an inline child swapped for a hashed one or back, a shortened partial key or another
+//! root. Mutations that change the structure of a trie re-hash every node above the one they
+//! change, so the proof stays complete and proves a different trie, whose root replaces the old
+//! one.
+//!
+//! Every variant is judged against what the proof actually shows:
+//!
+//! - trie proofs are read with `trie_db`, and a verifier must return what it reads for every key.
+//!   Proofs of tries that no canonical trie could hash to, such as inline children of 32 bytes or
+//!   more, are read the same way. The one difference is that a verifier may return an empty value
+//!   for a key such a proof holds, as the contract does for keys below inline nodes.
+//! - MMR and multiproofs must not produce the root for leaves or a root that don't belong together.
+//!
+//! ```ignore
+//! let mut report = MutationReport::new("merkle_patricia");
+//! for (mutation, variant) in proof.variants() {
+//!     let values = verify(&variant);
+//!     report.record("VerifyKeys", &mutation, variant.to_json(), variant.judge(values));
+//! }
+//! report.finish();
+//! ```
+
+use crate::{
+    ethereum_trie::read_proof,
+    keccak256,
+    rlp_item::RlpItem,
+    trie_node::{Leaf, NibbleSlice, NibbledBranch},
+};
+use codec::{Compact, CompactLen, Encode};
+use primitive_types::{H256, U256};
+use serde::Serialize;
+use serde_json::{json, Value};
+use sp_core::KeccakHasher;
+use sp_trie::{LayoutV1, NodeCodec, StorageProof};
+use std::{collections::HashSet, env, fmt, fs, path::PathBuf};
+use trie_db::{
+    node::{NodeHandlePlan, NodePlan, ValuePlan},
+    NodeCodec as NodeCodecT, Trie, TrieDBBuilder,
+};
+
+/// A single change to a valid proof
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Mutation {
+    /// Flips bit `bit % 8` of byte `bit / 8` of a node
+    FlipBit { node: usize, bit: usize },
+    /// Flips bit `bit % 8` of byte `bit / 8` of the hash of a leaf
+    FlipLeafBit { leaf: usize, bit: usize },
+    /// Removes a node
+    DropNode { node: usize },
+    /// Repeats a node right after itself
+    DuplicateNode { node: usize },
+    /// Swaps two nodes
+    SwapNodes { a: usize, b: usize },
+    /// Replaces the hash of a child of a trie node with the child itself. The child of an
+    /// ethereum extension is child 0.
+    InlineChild { node: usize, child: u8 },
+    /// Replaces an inline child of a trie node with its hash, adding the child to the proof
+    HashChild { node: usize, child: u8 },
+    /// Drops the first nibbles of the partial key of a trie node
+    TruncatePath { node: usize, nibbles: usize },
+    /// Claims the proof is for another root
+    SubstituteRoot { root: H256 },
+}
+
+impl fmt::Display for Mutation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Mutation::FlipBit { node, bit } => write!(f, "flip bit {bit} of node {node}"),
+            Mutation::FlipLeafBit { leaf, bit } => write!(f, "flip bit {bit} of leaf {leaf}"),
+            Mutation::DropNode { node } => write!(f, "drop node {node}"),
+            Mutation::DuplicateNode { node } => write!(f, "duplicate node {node}"),
+            Mutation::SwapNodes { a, b } => write!(f, "swap nodes {a} and {b}"),
+            Mutation::InlineChild { node, child } =>
+                write!(f, "inline child {child} of node {node}"),
+            Mutation::HashChild { node, child } => write!(f, "hash child {child} of node {node}"),
+            Mutation::TruncatePath { node, nibbles } =>
+                write!(f, "drop {nibbles} nibbles from the partial key of node {node}"),
+            Mutation::SubstituteRoot { root } => write!(f, "substitute root {root:?}"),
+        }
+    }
+}
+
+/// What a verifier made of a mutated proof
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Outcome {
+    /// The verifier reverted or returned an error
+    Rejected,
+    /// The verifier accepted the proof without returning anything the proof doesn't show
+    Accepted,
+    /// The verifier returned a value the proof doesn't show, for this reason
+    Wrong(String),
+}
+
+/// The trie codec of a [`TrieProof`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TrieKind {
+    /// The substrate codec, with `LayoutV0` or `LayoutV1`
+    Substrate,
+    /// Ethereum's RLP encoded nodes
+    Ethereum,
+}
+
+/// A merkle patricia proof of some keys
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TrieProof {
+    pub kind: TrieKind,
+    pub root: H256,
+    pub nodes: Vec<Vec<u8>>,
+    pub keys: Vec<Vec<u8>>,
+    /// Whether every node is encoded the way the trie itself would encode it
+    pub canonical: bool,
+}
+
+impl TrieProof {
+    pub fn new(kind: TrieKind, root: H256, nodes: Vec<Vec<u8>>, keys: Vec<Vec<u8>>) -> Self {
+        Self { kind, root, nodes, keys, canonical: true }
+    }
+
+    /// Every mutation of the proof that applies to it, along with the mutated proof
+    pub fn variants(&self) -> Vec<(Mutation, TrieProof)> {
+        self.mutations()
+            .into_iter()
+            .filter_map(|mutation| Some((mutation, self.apply(&mutation)?)))
+            .collect()
+    }
+
+    /// A few bit flips and the dropping, duplication and reordering of every node, along with
+    /// the handle swaps and shortened partial keys the nodes allow, and roots of subtries.
+    pub fn mutations(&self) -> Vec<Mutation> {
+        let values = self.value_hashes();
+        let mut mutations = list_mutations(self.nodes.len());
+        for (node, encoded) in self.nodes.iter().enumerate() {
+            let len = encoded.len();
+            for bit in [0, len / 2 * 8 + 3, len * 8 - 1] {
+                mutations.push(Mutation::FlipBit { node, bit });
+            }
+
+            let hash = H256(keccak256(encoded));
+            if values.contains(&hash) {
+                continue
+            }
+            let Some((children, partial)) = self.shape(encoded) else { continue };
+            for (child, is_hash) in children {
+                mutations.push(match is_hash {
+                    true => Mutation::InlineChild { node, child },
+                    false => Mutation::HashChild { node, child },
+                });
+            }
+            for nibbles in [1, 2] {
+                if partial > nibbles {
+                    mutations.push(Mutation::TruncatePath { node, nibbles });
+                }
+            }
+            if hash != self.root {
+                mutations.push(Mutation::SubstituteRoot { root: hash });
+            }
+        }
+        mutations.push(Mutation::SubstituteRoot { root: H256(keccak256(b"substituted root")) });
+
+        mutations
+    }
+
+    /// Applies `mutation`, returning `None` if it doesn't apply to this proof.
+    pub fn apply(&self, mutation: &Mutation) -> Option<TrieProof> {
+        let mut proof = self.clone();
+        match *mutation {
+            Mutation::FlipBit { node, bit } => flip_bit(proof.nodes.get_mut(node)?, bit)?,
+            Mutation::DropNode { .. } |
+            Mutation::DuplicateNode { .. } |
+            Mutation::SwapNodes { .. } => mutate_list(&mut proof.nodes, mutation)?,
+            Mutation::InlineChild { node, child } => {
+                let encoded = proof.inline_child(proof.nodes.get(node)?, child)?;
+                proof.canonical = false;
+                proof.replace_node(node, encoded);
+            },
+            Mutation::HashChild { node, child } => {
+                let (encoded, child) = proof.hash_child(proof.nodes.get(node)?, child)?;
+                proof.canonical = false;
+                proof.nodes.push(child);
+                proof.replace_node(node, encoded);
+            },
+            Mutation::TruncatePath { node, nibbles } => {
+                let encoded = proof.truncate_path(proof.nodes.get(node)?, nibbles)?;
+                // keys are whole bytes, so no key ends up in a leaf at an odd number of nibbles
+                proof.canonical &= nibbles % 2 == 0;
+                proof.replace_node(node, encoded);
+            },
+            Mutation::SubstituteRoot { root } => proof.root = root,
+            Mutation::FlipLeafBit { .. } => return None,
+        }
+
+        Some(proof)
+    }
+
+    /// Reads every key from the proof with `trie_db`
+    pub fn lookup(&self) -> Vec<Result<Option<Vec<u8>>, String>> {
+        match self.kind {
+            TrieKind::Substrate => {
+                let db = StorageProof::new(self.nodes.clone()).into_memory_db::<KeccakHasher>();
+                let trie = TrieDBBuilder::<LayoutV1<KeccakHasher>>::new(&db, &self.root).build();
+                self.keys
+                    .iter()
+                    .map(|key| trie.get(key).map_err(|err| format!("{err:?}")))
+                    .collect()
+            },
+            TrieKind::Ethereum => self
+                .keys
+                .iter()
+                .map(|key| {
+                    read_proof(self.root, &self.nodes, key).map_err(|err| format!("{err:?}"))
+                })
+                .collect(),
+        }
+    }
+
+    /// Judges the values a verifier returned for the keys, in order, or `None` if it rejected
+    /// the proof. Absent keys have an empty value, and keys on a path that leaves the proof must
+    /// not have any other. Keys of a proof that isn't canonical may also have an empty value.
+    pub fn judge(&self, values: Option<&[Vec<u8>]>) -> Outcome {
+        let Some(values) = values else { return Outcome::Rejected };
+        if values.len() != self.keys.len() {
+            return Outcome::Wrong(format!("{} values for {} keys", values.len(), self.keys.len()))
+        }
+
+        for ((key, value), expected) in self.keys.iter().zip(values).zip(self.lookup()) {
+            let expected = expected.ok().flatten().unwrap_or_default();
+            if *value != expected && (self.canonical || !value.is_empty()) {
+                return Outcome::Wrong(format!(
+                    "{} holds 0x{}, not 0x{}",
+                    hex::encode(key),
+                    hex::encode(expected),
+                    hex::encode(value)
+                ))
+            }
+        }
+
+        Outcome::Accepted
+    }
+
+    pub fn to_json(&self) -> Value {
+        json!({
+            "kind": format!("{:?}", self.kind),
+            "root": format!("{:?}", self.root),
+            "proof": hex_list(&self.nodes),
+            "keys": hex_list(&self.keys),
+        })
+    }
+
+    /// Hashes of the values that substrate nodes hold by hash, which are not nodes themselves
+    fn value_hashes(&self) -> HashSet<H256> {
+        if self.kind == TrieKind::Ethereum {
+            return HashSet::new()
+        }
+
+        let value = |node: &Vec<u8>| match NodeCodec::<KeccakHasher>::decode_plan(node).ok()? {
+            NodePlan::Leaf { value: ValuePlan::Node(range), .. } |
+            NodePlan::NibbledBranch { value: Some(ValuePlan::Node(range)), .. } =>
+                Some(H256::from_slice(&node[range])),
+            _ => None,
+        };
+        self.nodes.iter().filter_map(value).collect()
+    }
+
+    fn position(&self, hash: &[u8]) -> Option<usize> {
+        self.nodes.iter().position(|node| keccak256(node) == hash)
+    }
+
+    /// The children of a node as `(index, is_hash)`, and the number of nibbles in its partial
+    /// key. Returns `None` for nodes that don't decode.
+    fn shape(&self, node: &[u8]) -> Option<(Vec<(u8, bool)>, usize)> {
+        match self.kind {
+            TrieKind::Substrate => match NodeCodec::<KeccakHasher>::decode_plan(node).ok()? {
+                NodePlan::Leaf { partial, .. } => Some((vec![], partial.len())),
+                NodePlan::NibbledBranch { partial, children, .. } => {
+                    let children = children.iter().enumerate().filter_map(|(i, child)| {
+                        Some((i as u8, matches!(child.as_ref()?, NodeHandlePlan::Hash(_))))
+                    });
+                    Some((children.collect(), partial.len()))
+                },
+                _ => None,
+            },
+            TrieKind::Ethereum => {
+                let items = rlp_items(node)?;
+                if items.len() == 17 {
+                    let children = items[..16]
+                        .iter()
+                        .enumerate()
+                        .filter_map(|(i, item)| Some((i as u8, rlp_is_hash(item)?)));
+                    return Some((children.collect(), 0))
+                }
+
+                let (nibbles, is_leaf) = decode_hex_prefix(rlp_bytes(items.first()?)?)?;
+                if is_leaf {
+                    return Some((vec![], nibbles.len()))
+                }
+                Some((vec![(0, rlp_is_hash(items.get(1)?)?)], nibbles.len()))
+            },
+        }
+    }
+
+    /// `node` with the hashed `child` replaced by the child node
+    fn inline_child(&self, node: &[u8], child: u8) -> Option<Vec<u8>> {
+        match self.kind {
+            TrieKind::Substrate => {
+                let NodePlan::NibbledBranch { children, .. } =
+                    NodeCodec::<KeccakHasher>::decode_plan(node).ok()?
+                else {
+                    return None
+                };
+                let Some(NodeHandlePlan::Hash(range)) = children[child as usize].clone() else {
+                    return None
+                };
+                let encoded = &self.nodes[self.position(&node[range.clone()])?];
+
+                // the hash is preceded by its length, `Compact(32)`, which is a single byte
+                let len = Compact(encoded.len() as u32).encode();
+                Some([&node[..range.start - 1], &len, encoded, &node[range.end..]].concat())
+            },
+            TrieKind::Ethereum => {
+                let mut items = rlp_items(node)?;
+                let item = items.get_mut(ethereum_child(items.len(), child)?)?;
+                let encoded =
+                    &self.nodes[self.position(rlp_bytes(item).filter(|hash| hash.len() == 32)?)?];
+                *item = RlpItem::decode(encoded).ok()?;
+                Some(rlp::encode(&RlpItem::List(items)).to_vec())
+            },
+        }
+    }
+
+    /// `node` with the inline `child` replaced by its hash, along with the child node
+    fn hash_child(&self, node: &[u8], child: u8) -> Option<(Vec<u8>, Vec<u8>)> {
+        match self.kind {
+            TrieKind::Substrate => {
+                let NodePlan::NibbledBranch { children, .. } =
+                    NodeCodec::<KeccakHasher>::decode_plan(node).ok()?
+                else {
+                    return None
+                };
+                let Some(NodeHandlePlan::Inline(range)) = children[child as usize].clone() else {
+                    return None
+                };
+                let encoded = node[range.clone()].to_vec();
+                let prefix = Compact::<u32>::compact_len(&(range.len() as u32));
+
+                let handle = [Compact(32u32).encode(), keccak256(&encoded).to_vec()].concat();
+                let node = [&node[..range.start - prefix], &handle, &node[range.end..]].concat();
+                Some((node, encoded))
+            },
+            TrieKind::Ethereum => {
+                let mut items = rlp_items(node)?;
+                let item = items.get_mut(ethereum_child(items.len(), child)?)?;
+                if !matches!(item, RlpItem::List(_)) {
+                    return None
+                }
+                let encoded = rlp::encode(&*item).to_vec();
+                *item = RlpItem::Bytes(keccak256(&encoded).to_vec());
+                Some((rlp::encode(&RlpItem::List(items)).to_vec(), encoded))
+            },
+        }
+    }
+
+    /// `node` with the first `nibbles` nibbles of its partial key dropped
+    fn truncate_path(&self, node: &[u8], nibbles: usize) -> Option<Vec<u8>> {
+        match self.kind {
+            TrieKind::Substrate => {
+                let plan = NodeCodec::<KeccakHasher>::decode_plan(node).ok()?;
+                if let Some(mut leaf) = Leaf::from_plan(&plan, node) {
+                    leaf.key = drop_nibbles(&leaf.key, nibbles)?;
+                    return Some(leaf.to_plan::<KeccakHasher>().1)
+                }
+
+                let mut branch = NibbledBranch::from_plan(&plan, node)?;
+                branch.key = drop_nibbles(&branch.key, nibbles)?;
+                Some(branch.to_plan::<KeccakHasher>().1)
+            },
+            TrieKind::Ethereum => {
+                let mut items = rlp_items(node)?;
+                if items.len() != 2 {
+                    return None
+                }
+                let (path, is_leaf) = decode_hex_prefix(rlp_bytes(&items[0])?)?;
+                items[0] = RlpItem::Bytes(encode_hex_prefix(path.get(nibbles..)?, is_leaf));
+                Some(rlp::encode(&RlpItem::List(items)).to_vec())
+            },
+        }
+    }
+
+    /// Replaces a node, then the hash of the node in its parent, and so on up to the root.
+    fn replace_node(&mut self, index: usize, node: Vec<u8>) {
+        let old = keccak256(&self.nodes[index]);
+        let new = keccak256(&node);
+        self.nodes[index] = node;
+        if self.root.0 == old {
+            self.root = H256(new);
+        }
+
+        for parent in 0..self.nodes.len() {
+            let Some(at) = self.nodes[parent].windows(32).position(|window| window == old) else {
+                continue
+            };
+            let mut encoded = self.nodes[parent].clone();
+            encoded[at..at + 32].copy_from_slice(&new);
+            self.replace_node(parent, encoded);
+        }
+    }
+}
+
+/// A proof of some leaves of a merkle mountain range, as taken by `CalculateRoot`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MmrProof {
+    pub root: [u8; 32],
+    pub leaf_count: u64,
+    /// Leaves as `(leaf_index, k_index, hash)`
+    pub leaves: Vec<(u32, usize, [u8; 32])>,
+    pub items: Vec<Vec<u8>>,
+    /// Whether the leaves or the root no longer belong together, so the proof must be rejected
+    pub forged: bool,
+}
+
+impl MmrProof {
+    pub fn new(
+        root: [u8; 32],
+        leaf_count: u64,
+        leaves: Vec<(u32, usize, [u8; 32])>,
+        items: Vec<Vec<u8>>,
+    ) -> Self {
+        Self { root, leaf_count, leaves, items, forged: false }
+    }
+
+    /// Every mutation of the proof that applies to it, along with the mutated proof
+    pub fn variants(&self) -> Vec<(Mutation, MmrProof)> {
+        hash_mutations(self.items.len(), self.leaves.len())
+            .into_iter()
+            .filter_map(|mutation| Some((mutation, self.apply(&mutation)?)))
+            .collect()
+    }
+
+    /// Applies `mutation`, returning `None` if it doesn't apply to this proof.
+    pub fn apply(&self, mutation: &Mutation) -> Option<MmrProof> {
+        let mut proof = self.clone();
+        match *mutation {
+            Mutation::FlipBit { node, bit } => flip_bit(proof.items.get_mut(node)?, bit)?,
+            Mutation::FlipLeafBit { leaf, bit } => {
+                flip_bit(&mut proof.leaves.get_mut(leaf)?.2, bit)?;
+                proof.forged = true;
+            },
+            Mutation::DropNode { .. } |
+            Mutation::DuplicateNode { .. } |
+            Mutation::SwapNodes { .. } => mutate_list(&mut proof.items, mutation)?,
+            Mutation::SubstituteRoot { root } => {
+                proof.root = root.0;
+                proof.forged = true;
+            },
+            _ => return None,
+        }
+
+        Some(proof)
+    }
+
+    /// Judges the root a verifier calculated from the proof, `None` if it rejected the proof.
+    pub fn judge(&self, root: Option<[u8; 32]>) -> Outcome {
+        judge_root(self.root, self.forged, root)
+    }
+
+    pub fn to_json(&self) -> Value {
+        let leaves = self.leaves.iter().map(|(index, k_index, hash)| {
+            json!({ "index": index, "k_index": k_index, "hash": hex::encode(hash) })
+        });
+        json!({
+            "root": hex::encode(self.root),
+            "leaf_count": self.leaf_count,
+            "leaves": leaves.collect::<Vec<_>>(),
+            "proof": hex_list(&self.items),
+        })
+    }
+}
+
+/// A proof of some leaves of a binary merkle tree, as taken by `MerkleMultiProof.CalculateRoot`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MultiProof {
+    pub root: [u8; 32],
+    /// Nodes of each layer of the tree, from the leaves up, as `(index, hash)`
+    pub layers: Vec<Vec<(usize, [u8; 32])>>,
+    /// Leaves as `(index, hash)`
+    pub leaves: Vec<(usize, [u8; 32])>,
+    /// Whether the leaves or the root no longer belong together, so the proof must be rejected
+    pub forged: bool,
+}
+
+impl MultiProof {
+    pub fn new(
+        root: [u8; 32],
+        layers: Vec<Vec<(usize, [u8; 32])>>,
+        leaves: Vec<(usize, [u8; 32])>,
+    ) -> Self {
+        Self { root, layers, leaves, forged: false }
+    }
+
+    /// Every mutation of the proof that applies to it, along with the mutated proof. Nodes are
+    /// numbered across layers, from the first node of the lowest layer on.
+    pub fn variants(&self) -> Vec<(Mutation, MultiProof)> {
+        hash_mutations(self.layers.iter().map(Vec::len).sum(), self.leaves.len())
+            .into_iter()
+            .filter_map(|mutation| Some((mutation, self.apply(&mutation)?)))
+            .collect()
+    }
+
+    /// Applies `mutation`, returning `None` if it doesn't apply to this proof. Nodes that are
+    /// duplicated stay in their layer, swapped nodes trade places across layers.
+    pub fn apply(&self, mutation: &Mutation) -> Option<MultiProof> {
+        let mut proof = self.clone();
+        let (mut layers, mut nodes): (Vec<_>, Vec<_>) = self
+            .layers
+            .iter()
+            .enumerate()
+            .flat_map(|(layer, nodes)| nodes.iter().map(move |node| (layer, *node)))
+            .unzip();
+        match *mutation {
+            Mutation::FlipBit { node, bit } => flip_bit(&mut nodes.get_mut(node)?.1, bit)?,
+            Mutation::FlipLeafBit { leaf, bit } => {
+                flip_bit(&mut proof.leaves.get_mut(leaf)?.1, bit)?;
+                proof.forged = true;
+            },
+            Mutation::DropNode { .. } | Mutation::DuplicateNode { .. } => {
+                mutate_list(&mut layers, mutation)?;
+                mutate_list(&mut nodes, mutation)?;
+            },
+            Mutation::SwapNodes { .. } => mutate_list(&mut nodes, mutation)?,
+            Mutation::SubstituteRoot { root } => {
+                proof.root = root.0;
+                proof.forged = true;
+            },
+            _ => return None,
+        }
+
+        proof.layers = vec![vec![]; self.layers.len()];
+        for (layer, node) in layers.into_iter().zip(nodes) {
+            proof.layers[layer].push(node);
+        }
+        Some(proof)
+    }
+
+    /// Judges the root a verifier calculated from the proof, `None` if it rejected the proof.
+    pub fn judge(&self, root: Option<[u8; 32]>) -> Outcome {
+        judge_root(self.root, self.forged, root)
+    }
+
+    pub fn to_json(&self) -> Value {
+        let node = |(index, hash): &(usize, [u8; 32])| json!([index, hex::encode(hash)]);
+        let layers = self.layers.iter().map(|layer| layer.iter().map(node).collect::<Vec<_>>());
+        json!({
+            "root": hex::encode(self.root),
+            "layers": layers.collect::<Vec<_>>(),
+            "leaves": self.leaves.iter().map(node).collect::<Vec<_>>(),
+        })
+    }
+}
+
+/// A mutated proof that a verifier accepted, or returned a wrong value for
+#[derive(Debug, Clone, Serialize)]
+pub struct Reproducer {
+    pub verifier: String,
+    pub mutation: String,
+    /// The mutated proof
+    pub proof: Value,
+    /// Why the outcome is wrong, if it is
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub wrong: Option<String>,
+}
+
+impl fmt::Display for Reproducer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.wrong {
+            Some(reason) =>
+                write!(f, "{} is wrong after `{}`: {reason}", self.verifier, self.mutation)?,
+            None => write!(f, "{} accepts `{}`", self.verifier, self.mutation)?,
+        }
+        write!(f, "\n{}", self.proof)
+    }
+}
+
+/// Collects the outcomes of the mutated proofs of a test.
+///
+/// Accepted mutations are printed with a reproducer and written to `<dir>/<name>.json`, where
+/// `<dir>` is `MUTATION_REPORT_DIR` or `target/mutation-report` by default.
+/// [`MutationReport::finish`] fails if any verifier returned a wrong value.
+pub struct MutationReport {
+    name: String,
+    rejected: usize,
+    accepted: Vec<Reproducer>,
+    wrong: Vec<Reproducer>,
+}
+
+impl MutationReport {
+    pub fn new(name: &str) -> Self {
+        Self { name: name.to_string(), rejected: 0, accepted: vec![], wrong: vec![] }
+    }
+
+    pub fn record(&mut self, verifier: &str, mutation: &Mutation, proof: Value, outcome: Outcome) {
+        let reproducer = |wrong| Reproducer {
+            verifier: verifier.to_string(),
+            mutation: mutation.to_string(),
+            proof,
+            wrong,
+        };
+        match outcome {
+            Outcome::Rejected => self.rejected += 1,
+            Outcome::Accepted => self.accepted.push(reproducer(None)),
+            Outcome::Wrong(reason) => self.wrong.push(reproducer(Some(reason))),
+        }
+    }
+
+    /// Reports the accepted mutations and returns their reproducers, or panics with the
+    /// reproducers of the wrong outcomes.
+    pub fn finish(self) -> Vec<Reproducer> {
+        for reproducer in &self.accepted {
+            println!("{reproducer}");
+        }
+        println!(
+            "{}: {} rejected, {} accepted, {} wrong",
+            self.name,
+            self.rejected,
+            self.accepted.len(),
+            self.wrong.len()
+        );
+
+        let dir = env::var("MUTATION_REPORT_DIR").map(PathBuf::from).unwrap_or_else(|_| {
+            PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("target/mutation-report")
+        });
+        fs::create_dir_all(&dir).unwrap();
+        let report = json!({
+            "rejected": self.rejected,
+            "accepted": self.accepted,
+            "wrong": self.wrong,
+        });
+        let report = serde_json::to_string_pretty(&report).unwrap();
+        fs::write(dir.join(format!("{}.json", self.name)), report).unwrap();
+
+        let wrong = self.wrong.iter().map(ToString::to_string).collect::<Vec<_>>();
+        assert!(wrong.is_empty(), "{}", wrong.join("\n"));
+        self.accepted
+    }
+}
+
+/// Dropping, duplicating and swapping the nodes of a list of `len` nodes
+fn list_mutations(len: usize) -> Vec<Mutation> {
+    let mut mutations = vec![];
+    for node in 0..len {
+        mutations.push(Mutation::DropNode { node });
+        mutations.push(Mutation::DuplicateNode { node });
+        if node + 1 < len {
+            mutations.push(Mutation::SwapNodes { a: node, b: node + 1 });
+        }
+    }
+    if len > 2 {
+        mutations.push(Mutation::SwapNodes { a: 0, b: len - 1 });
+    }
+
+    mutations
+}
+
+/// Mutations of a proof made of 32 byte hashes, for `nodes` nodes and `leaves` leaves
+fn hash_mutations(nodes: usize, leaves: usize) -> Vec<Mutation> {
+    let mut mutations = list_mutations(nodes);
+    for bit in [0, 255] {
+        mutations.extend((0..nodes).map(|node| Mutation::FlipBit { node, bit }));
+        mutations.extend((0..leaves).map(|leaf| Mutation::FlipLeafBit { leaf, bit }));
+    }
+    mutations.push(Mutation::SubstituteRoot { root: H256(keccak256(b"substituted root")) });
+
+    mutations
+}
+
+fn judge_root(expected: [u8; 32], forged: bool, root: Option<[u8; 32]>) -> Outcome {
+    match root {
+        Some(root) if root == expected && forged =>
+            Outcome::Wrong(format!("calculated the root 0x{}", hex::encode(root))),
+        Some(root) if root == expected => Outcome::Accepted,
+        _ => Outcome::Rejected,
+    }
+}
+
+fn flip_bit(data: &mut [u8], bit: usize) -> Option<()> {
+    *data.get_mut(bit / 8)? ^= 1 << (bit % 8);
+    Some(())
+}
+
+fn mutate_list<T: Clone>(list: &mut Vec<T>, mutation: &Mutation) -> Option<()> {
+    match *mutation {
+        Mutation::DropNode { node } if node < list.len() => {
+            list.remove(node);
+        },
+        Mutation::DuplicateNode { node } if node < list.len() =>
+            list.insert(node, list[node].clone()),
+        Mutation::SwapNodes { a, b } if a < list.len() && b < list.len() && a != b =>
+            list.swap(a, b),
+        _ => return None,
+    }
+
+    Some(())
+}
+
+fn hex_list(items: &[Vec<u8>]) -> Vec<String> {
+    items.iter().map(hex::encode).collect()
+}
+
+/// `key` without its first `count` nibbles, as long as at least one nibble is left
+fn drop_nibbles(key: &NibbleSlice, count: usize) -> Option<NibbleSlice> {
+    if count >= key.len() {
+        return None
+    }
+
+    let offset = key.offset.as_usize();
+    let nibbles = (offset + count..key.data.len() * 2)
+        .map(|i| (key.data[i / 2] >> (4 * (1 - i % 2))) & 0x0f)
+        .collect::<Vec<_>>();
+    // padded on the left to a whole number of bytes
+    let padding = nibbles.len() % 2;
+    let data = [vec![0; padding], nibbles]
+        .concat()
+        .chunks(2)
+        .map(|pair| pair[0] << 4 | pair[1])
+        .collect();
+    Some(NibbleSlice { data, offset: U256::from(padding) })
+}
+
+fn rlp_items(node: &[u8]) -> Option<Vec<RlpItem>> {
+    match RlpItem::decode(node).ok()? {
+        RlpItem::List(items) => Some(items),
+        RlpItem::Bytes(_) => None,
+    }
+}
+
+fn rlp_bytes(item: &RlpItem) -> Option<&[u8]> {
+    match item {
+        RlpItem::Bytes(data) => Some(data),
+        RlpItem::List(_) => None,
+    }
+}
+
+/// Whether a child is a hash or inline, `None` if there is no child
+fn rlp_is_hash(item: &RlpItem) -> Option<bool> {
+    match item {
+        RlpItem::Bytes(data) if data.len() == 32 => Some(true),
+        RlpItem::List(_) => Some(false),
+        RlpItem::Bytes(_) => None,
+    }
+}
+
+/// The item holding `child` in an ethereum branch or extension of `len` items
+fn ethereum_child(len: usize, child: u8) -> Option<usize> {
+    match (len, child) {
+        (17, child) if child < 16 => Some(child as usize),
+        (2, 0) => Some(1),
+        _ => None,
+    }
+}
+
+/// Decodes a hex prefix encoded path into its nibbles and whether it belongs to a leaf
+fn decode_hex_prefix(path: &[u8]) -> Option<(Vec<u8>, bool)> {
+    let flag = path.first()? >> 4;
+    if flag > 3 {
+        return None
+    }
+
+    let mut nibbles = vec![];
+    if flag & 1 == 1 {
+        nibbles.push(path[0] & 0x0f);
+    }
+    nibbles.extend(path[1..].iter().flat_map(|byte| [byte >> 4, byte & 0x0f]));
+    Some((nibbles, flag & 2 == 2))
+}
+
+fn encode_hex_prefix(nibbles: &[u8], is_leaf: bool) -> Vec<u8> {
+    let is_odd = nibbles.len() % 2 == 1;
+    let flag = (is_leaf as u8) << 1 | is_odd as u8;
+    let first = match is_odd {
+        true => flag << 4 | nibbles[0],
+        false => flag << 4,
+    };
+    let rest = nibbles[is_odd as usize..].chunks(2).map(|pair| pair[0] << 4 | pair[1]);
+    std::iter::once(first).chain(rest).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        ethereum_trie::{secure_key, EthereumTrie},
+        native_verifier::{verify_ethereum_proof, verify_substrate_proof},
+    };
+    use sp_trie::{LayoutV0, MemoryDB};
+    use trie_db::{Recorder, TrieDBMutBuilder, TrieLayout, TrieMut};
+
+    /// Entries with 32 byte keys, and single byte keys small enough to be inlined
+    fn entries() -> Vec<(Vec<u8>, Vec<u8>)> {
+        let hashed = (0u8..12).map(|i| (keccak256([i]).to_vec(), vec![i; 1 + i as usize * 8]));
+        hashed.chain((1u8..6).map(|i| (vec![i], vec![i]))).collect()
+    }
+
+    fn substrate_proof<L: TrieLayout<Hash = KeccakHasher>>() -> TrieProof {
+        let entries = entries();
+        let mut db = MemoryDB::<KeccakHasher>::default();
+        let mut root = Default::default();
+        {
+            let mut trie = TrieDBMutBuilder::<L>::new(&mut db, &mut root).build();
+            for (key, value) in &entries {
+                trie.insert(key, value).unwrap();
+            }
+        }
+
+        let mut recorder = Recorder::<L>::new();
+        {
+            let trie = TrieDBBuilder::<L>::new(&db, &root).with_recorder(&mut recorder).build();
+            for (key, _) in &entries {
+                trie.get(key).unwrap();
+            }
+        }
+        let mut seen = HashSet::new();
+        let nodes = recorder.drain().into_iter().map(|record| record.data);
+        let nodes = nodes.filter(|node| seen.insert(node.clone())).collect();
+
+        let keys = entries.into_iter().map(|(key, _)| key).chain([vec![0xff; 32]]).collect();
+        TrieProof::new(TrieKind::Substrate, root, nodes, keys)
+    }
+
+    fn ethereum_proof() -> TrieProof {
+        let entries = entries().into_iter().map(|(key, value)| (secure_key(&key), value));
+        let entries = entries.collect::<Vec<_>>();
+        let trie = EthereumTrie::new(entries.clone());
+
+        let keys = entries.into_iter().map(|(key, _)| key).chain([secure_key(&[0xff])]);
+        let keys = keys.collect::<Vec<_>>();
+        TrieProof::new(TrieKind::Ethereum, trie.root(), trie.prove(&keys), keys)
+    }
+
+    #[test]
+    fn test_trie_mutations() {
+        for proof in [
+            substrate_proof::<LayoutV0<KeccakHasher>>(),
+            substrate_proof::<LayoutV1<KeccakHasher>>(),
+            ethereum_proof(),
+        ] {
+            // the unmutated proof reads every key
+            let values = proof.lookup().into_iter().map(|value| value.unwrap().unwrap_or_default());
+            let values = values.collect::<Vec<_>>();
+            assert_eq!(proof.judge(Some(&values)), Outcome::Accepted);
+            assert!(matches!(proof.judge(Some(&values[1..])), Outcome::Wrong(_)));
+            assert_eq!(proof.judge(None), Outcome::Rejected);
+
+            let variants = proof.variants();
+            for mutation in variants.iter().map(|(mutation, _)| mutation) {
+                assert!(proof.apply(mutation).is_some(), "{mutation}");
+            }
+            let has = |kind: fn(&Mutation) -> bool| variants.iter().any(|(m, _)| kind(m));
+            assert!(has(|m| matches!(m, Mutation::FlipBit { .. })));
+            assert!(has(|m| matches!(m, Mutation::InlineChild { .. })));
+            assert!(has(|m| matches!(m, Mutation::TruncatePath { .. })));
+            assert!(has(|m| matches!(m, Mutation::SubstituteRoot { .. })));
+
+            // structural mutations keep the proof complete, under the new root
+            for (mutation, variant) in &variants {
+                if matches!(mutation, Mutation::InlineChild { .. } | Mutation::HashChild { .. }) {
+                    assert_ne!(variant.root, proof.root);
+                    assert!(variant.lookup().iter().all(Result::is_ok), "{mutation}");
+
+                    // no canonical trie hashes to the new root, a forged value is still wrong
+                    let forged = vec![b"forged".to_vec(); variant.keys.len()];
+                    assert!(
+                        matches!(variant.judge(Some(&forged)), Outcome::Wrong(_)),
+                        "{mutation}"
+                    );
+                }
+            }
+        }
+
+        // only the substrate tries inline the small leaves
+        let proof = substrate_proof::<LayoutV1<KeccakHasher>>();
+        assert!(proof.variants().iter().any(|(m, _)| matches!(m, Mutation::HashChild { .. })));
+    }
+
+    #[test]
+    fn test_native_verifiers_on_mutated_proofs() {
+        let mut report = MutationReport::new("native_verifier");
+        for proof in [
+            substrate_proof::<LayoutV0<KeccakHasher>>(),
+            substrate_proof::<LayoutV1<KeccakHasher>>(),
+            ethereum_proof(),
+        ] {
+            for (mutation, variant) in proof.variants() {
+                let (verifier, result) = match variant.kind {
+                    TrieKind::Substrate => (
+                        "verify_substrate_proof",
+                        verify_substrate_proof::<KeccakHasher>(
+                            variant.root,
+                            &variant.nodes,
+                            &variant.keys,
+                        ),
+                    ),
+                    TrieKind::Ethereum => (
+                        "verify_ethereum_proof",
+                        verify_ethereum_proof(variant.root, &variant.nodes, &variant.keys),
+                    ),
+                };
+                let values = result
+                    .ok()
+                    .map(|result| result.into_iter().map(|(_, value)| value).collect::<Vec<_>>());
+                let outcome = variant.judge(values.as_deref());
+                report.record(verifier, &mutation, variant.to_json(), outcome);
+            }
+        }
+        report.finish();
+    }
+
+    #[test]
+    fn test_hash_proof_mutations() {
+        let leaves = vec![(0, 0, [1; 32]), (3, 1, [2; 32])];
+        let proof = MmrProof::new([9; 32], 7, leaves, vec![vec![3; 32], vec![4; 32]]);
+        let variants = proof.variants();
+        assert!(variants.iter().all(|(mutation, variant)| {
+            variant.forged ==
+                matches!(
+                    mutation,
+                    Mutation::FlipLeafBit { .. } | Mutation::SubstituteRoot { .. }
+                )
+        }));
+
+        let forged = proof.apply(&Mutation::FlipLeafBit { leaf: 1, bit: 255 }).unwrap();
+        assert_eq!(forged.leaves[1].2[31], 0x82);
+        assert!(matches!(forged.judge(Some([9; 32])), Outcome::Wrong(_)));
+        assert_eq!(forged.judge(Some([8; 32])), Outcome::Rejected);
+        assert_eq!(proof.judge(Some([9; 32])), Outcome::Accepted);
+        assert_eq!(proof.apply(&Mutation::TruncatePath { node: 0, nibbles: 1 }), None);
+
+        let layers = vec![vec![(1, [1; 32]), (4, [2; 32])], vec![(3, [3; 32])]];
+        let proof = MultiProof::new([9; 32], layers, vec![(0, [5; 32])]);
+        let swapped = proof.apply(&Mutation::SwapNodes { a: 1, b: 2 }).unwrap();
+        assert_eq!(swapped.layers, vec![vec![(1, [1; 32]), (3, [3; 32])], vec![(4, [2; 32])]]);
+        let duplicated = proof.apply(&Mutation::DuplicateNode { node: 2 }).unwrap();
+        assert_eq!(duplicated.layers[1], vec![(3, [3; 32]); 2]);
+        let dropped = proof.apply(&Mutation::DropNode { node: 0 }).unwrap();
+        assert_eq!(dropped.layers, vec![vec![(4, [2; 32])], vec![(3, [3; 32])]]);
+    }
+
+    #[test]
+    fn test_path_encodings() {
+        let key = NibbleSlice { data: vec![0x01, 0x23, 0x45], offset: U256::one() };
+        let dropped = drop_nibbles(&key, 1).unwrap();
+        assert_eq!(dropped, NibbleSlice { data: vec![0x23, 0x45], offset: U256::zero() });
+        let dropped = drop_nibbles(&key, 2).unwrap();
+        assert_eq!(dropped, NibbleSlice { data: vec![0x03, 0x45], offset: U256::one() });
+        assert_eq!(drop_nibbles(&key, 5), None);
+
+        for (nibbles, is_leaf, encoded) in [
+            (vec![1, 2, 3], false, vec![0x11, 0x23]),
+            (vec![1, 2], true, vec![0x20, 0x12]),
+            (vec![0xf], true, vec![0x3f]),
+        ] {
+            assert_eq!(encode_hex_prefix(&nibbles, is_leaf), encoded);
+            assert_eq!(decode_hex_prefix(&encoded), Some((nibbles, is_leaf)));
+        }
+    }
+}
@@ -0,0 +1,132 @@
+//! A tree of RLP strings and lists, for differential testing of `RLPReader.sol`.
+
+use primitive_types::U256;
+use rlp::{DecoderError, Encodable, Rlp, RlpStream};
+
+/// A decoded RLP item
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RlpItem {
+    /// A string of bytes
+    Bytes(Vec<u8>),
+    /// A list of items
+    List(Vec<RlpItem>),
+}
+
+impl RlpItem {
+    /// Decodes exactly one canonical item from `encoded`.
+    ///
+    /// Unlike `rlp::Rlp`, which stops iterating a list at the first item it can't read, this
+    /// rejects lists whose items don't add up to the length in their header, as well as any bytes
+    /// after the item.
+    pub fn decode(encoded: &[u8]) -> Result<Self, DecoderError> {
+        let rlp = Rlp::new(encoded);
+        let info = rlp.payload_info()?;
+        if info.total() != encoded.len() {
+            return Err(DecoderError::RlpInconsistentLengthAndData)
+        }
+
+        if rlp.is_data() {
+            return rlp.decoder().decode_value(|data| Ok(RlpItem::Bytes(data.to_vec())))
+        }
+
+        let mut payload = &encoded[info.header_len..];
+        let mut items = vec![];
+        while !payload.is_empty() {
+            let len = Rlp::new(payload).payload_info()?.total();
+            items.push(RlpItem::decode(&payload[..len])?);
+            payload = &payload[len..];
+        }
+
+        Ok(RlpItem::List(items))
+    }
+
+    /// Serializes the item the way `decodeRlp` in `MerklePatriciaTest` does: in pre-order, each
+    /// string as `(0u8, len, bytes)` and each list as `(1u8, item count)`, with lengths and counts
+    /// as 32 byte big endian integers.
+    pub fn flatten(&self) -> Vec<u8> {
+        let mut out = vec![];
+        self.flatten_into(&mut out);
+        out
+    }
+
+    fn flatten_into(&self, out: &mut Vec<u8>) {
+        let word = |len: usize| <[u8; 32]>::from(U256::from(len));
+        match self {
+            RlpItem::Bytes(data) => {
+                out.push(0);
+                out.extend(word(data.len()));
+                out.extend(data);
+            },
+            RlpItem::List(items) => {
+                out.push(1);
+                out.extend(word(items.len()));
+                for item in items {
+                    item.flatten_into(out);
+                }
+            },
+        }
+    }
+}
+
+impl Encodable for RlpItem {
+    fn rlp_append(&self, s: &mut RlpStream) {
+        match self {
+            RlpItem::Bytes(data) => {
+                s.append(data);
+            },
+            RlpItem::List(items) => {
+                s.begin_list(items.len());
+                for item in items {
+                    s.append(item);
+                }
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_decode_round_trip() {
+        let item = RlpItem::List(vec![
+            RlpItem::Bytes(vec![]),
+            RlpItem::Bytes(vec![0x7f]),
+            RlpItem::Bytes(vec![0x80]),
+            RlpItem::Bytes(vec![1; 56]),
+            RlpItem::List(vec![RlpItem::Bytes(vec![2; 55]); 2]),
+        ]);
+        let encoded = rlp::encode(&item);
+
+        assert_eq!(RlpItem::decode(&encoded), Ok(item));
+    }
+
+    #[test]
+    fn test_decode_rejects_malformed() {
+        let malformed: [&[u8]; 9] = [
+            // empty input
+            &[],
+            // single byte with a header
+            &[0x81, 0x05],
+            // long form for a short string
+            &[0xb8, 0x01, 0x05],
+            // length with a leading zero
+            &[0xb9, 0x00, 0x38],
+            // string shorter than its header says
+            &[0x83, 0x01, 0x02],
+            // trailing bytes
+            &[0x82, 0x01, 0x02, 0x03],
+            // list items longer than the list
+            &[0xc2, 0x82, 0x01],
+            // list items shorter than the list
+            &[0xc3, 0x01, 0x02],
+            // malformed item inside a list
+            &[0xc2, 0x81, 0x05],
+        ];
+
+        for encoded in malformed {
+            assert!(RlpItem::decode(encoded).is_err(), "{encoded:?}");
+        }
+    }
+}
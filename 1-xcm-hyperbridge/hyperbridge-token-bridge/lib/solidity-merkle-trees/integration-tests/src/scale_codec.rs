@@ -0,0 +1,188 @@
+#![cfg(test)]
+
+//! Differential tests of `ScaleCodec.sol` against `parity-scale-codec`.
+//!
+//! The decoder only supports big-integer compacts of up to 8 bytes, i.e. values up to
+//! `u64::MAX`, and reverts on longer ones. The encoder supports every `uint256`.
+
+use crate::test_env::{self, TestEnv};
+use codec::{Compact, Encode};
+use ethers::abi::{Token, Uint};
+use forge_testsuite::Contract;
+use proptest::{
+    collection::vec, prelude::any, prop_assert_eq, prop_oneof, proptest, strategy::Strategy,
+    test_runner::TestCaseError,
+};
+
+/// Values on either side of every mode switch of the compact encoding
+const BOUNDARIES: [u128; 14] = [
+    0,
+    (1 << 6) - 1,
+    1 << 6,
+    (1 << 14) - 1,
+    1 << 14,
+    (1 << 30) - 1,
+    1 << 30,
+    (1 << 32) - 1,
+    1 << 32,
+    (1 << 56) - 1,
+    1 << 56,
+    u64::MAX as u128,
+    1 << 64,
+    u128::MAX,
+];
+
+/// Decodes `encoded` followed by some trailing bytes on-chain, returning the value and the
+/// number of bytes consumed, or `None` if the contract reverts.
+async fn decode_compact(contract: &mut Contract<'_>, encoded: &[u8]) -> Option<(Uint, usize)> {
+    let mut data = encoded.to_vec();
+    data.extend([0xff; 4]);
+    let (value, offset) = contract
+        .call::<_, (Uint, Uint)>("decodeUintCompact", (Token::Bytes(data)))
+        .await
+        .ok()?;
+
+    Some((value, offset.as_usize()))
+}
+
+/// Checks that the contract encodes `value` like `Compact<u128>`, and decodes it unless it is
+/// too big for the decoder.
+async fn check_compact(contract: &mut Contract<'_>, value: u128) -> Result<(), TestCaseError> {
+    let encoded = Compact(value).encode();
+
+    let expected = (value <= u64::MAX as u128).then(|| (Uint::from(value), encoded.len()));
+    prop_assert_eq!(decode_compact(contract, &encoded).await, expected);
+
+    let result = contract
+        .call::<_, Vec<u8>>("encodeUintCompact", (Token::Uint(Uint::from(value))))
+        .await
+        .unwrap();
+    prop_assert_eq!(result, encoded);
+
+    Ok(())
+}
+
+/// Checks that the contract reads the length prefix of `data` and encodes it like `Vec<u8>`.
+async fn check_bytes(contract: &mut Contract<'_>, data: Vec<u8>) -> Result<(), TestCaseError> {
+    let encoded = data.encode();
+
+    let (len, offset) = decode_compact(contract, &encoded).await.unwrap();
+    prop_assert_eq!(len, Uint::from(data.len()));
+    prop_assert_eq!(&encoded[offset..], &data[..]);
+
+    let result = contract
+        .call::<_, Vec<u8>>("encodeBytes", (Token::Bytes(data.clone())))
+        .await
+        .unwrap();
+    prop_assert_eq!(result, encoded);
+
+    Ok(())
+}
+
+/// Checks that the contract decodes the little endian encoding of a fixed width integer.
+async fn check_fixed(
+    contract: &mut Contract<'_>,
+    encoded: Vec<u8>,
+    value: Uint,
+) -> Result<(), TestCaseError> {
+    let result = contract
+        .call::<_, Uint>("decodeUint256", (Token::Bytes(encoded)))
+        .await
+        .unwrap();
+    prop_assert_eq!(result, value);
+
+    Ok(())
+}
+
+/// A random value of one of the unsigned integer types, widened to `u128`.
+///
+/// The compact encoding of a value doesn't depend on its type, so the narrower types only serve
+/// to weight the values towards the smaller modes.
+fn compact_value() -> impl Strategy<Value = u128> {
+    prop_oneof![
+        any::<u8>().prop_map(u128::from),
+        any::<u16>().prop_map(u128::from),
+        any::<u32>().prop_map(u128::from),
+        any::<u64>().prop_map(u128::from),
+        any::<u128>(),
+    ]
+}
+
+/// The little endian encoding of a random fixed width integer, along with its value.
+fn fixed_value() -> impl Strategy<Value = (Vec<u8>, Uint)> {
+    prop_oneof![
+        any::<u8>().prop_map(|v| (v.encode(), Uint::from(v))),
+        any::<u16>().prop_map(|v| (v.encode(), Uint::from(v))),
+        any::<u32>().prop_map(|v| (v.encode(), Uint::from(v))),
+        any::<u64>().prop_map(|v| (v.encode(), Uint::from(v))),
+        any::<u128>().prop_map(|v| (v.encode(), Uint::from(v))),
+        any::<[u8; 32]>().prop_map(|v| (v.to_vec(), Uint::from_little_endian(&v))),
+    ]
+}
+
+#[test]
+fn test_compact_encoding_modes() {
+    let lengths = BOUNDARIES.map(|value| Compact(value).encode().len());
+    assert_eq!(lengths, [1, 1, 2, 2, 4, 4, 5, 5, 6, 8, 9, 9, 10, 17]);
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_scale_codec_boundaries() {
+    let mut env = TestEnv::shared().await;
+    let mut contract = env.contract("MerklePatriciaTest").await;
+
+    for value in BOUNDARIES {
+        check_compact(&mut contract, value).await.unwrap();
+    }
+
+    for len in [0, 63, 64, 16383, 16384] {
+        check_bytes(&mut contract, vec![0xab; len]).await.unwrap();
+    }
+
+    check_fixed(&mut contract, u128::MAX.encode(), Uint::from(u128::MAX))
+        .await
+        .unwrap();
+    check_fixed(&mut contract, vec![0xff; 32], Uint::MAX).await.unwrap();
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_scale_codec_rejects_oversized_compact() {
+    let mut env = TestEnv::shared().await;
+    let mut contract = env.contract("MerklePatriciaTest").await;
+
+    // big-integer mode with more than 8 bytes is unsupported
+    let mut encoded = vec![((9 - 4) << 2) | 0b11];
+    encoded.extend([0xff; 9]);
+    let result = contract
+        .call::<_, (Uint, Uint)>("decodeUintCompact", (Token::Bytes(encoded)))
+        .await;
+    assert!(result.is_err());
+
+    // the prefix promises more bytes than there are
+    let mut encoded = Compact(u64::MAX).encode();
+    encoded.pop();
+    let result = contract
+        .call::<_, (Uint, Uint)>("decodeUintCompact", (Token::Bytes(encoded)))
+        .await;
+    assert!(result.is_err());
+}
+
+proptest! {
+    #[test]
+    fn test_random_scale_codec(
+        value in compact_value(),
+        data in vec(any::<u8>(), 0..200),
+        (encoded, fixed) in fixed_value(),
+    ) {
+        test_env::block_on(async move {
+            let mut env = TestEnv::shared().await;
+            let mut contract = env.contract("MerklePatriciaTest").await;
+
+            check_compact(&mut contract, value).await?;
+            check_bytes(&mut contract, data).await?;
+            check_fixed(&mut contract, encoded, fixed).await?;
+
+            Ok::<_, TestCaseError>(())
+        })?;
+    }
+}
@@ -0,0 +1,339 @@
+//! Imports a geth genesis `alloc` or a `debug_dumpBlock` state dump into a [`StateTrie`], so that
+//! account and storage proofs can be tested against a full state offline.
+//!
+//! A genesis file holds its accounts under `alloc`, a dump under `accounts`, and a dump may be
+//! the whole JSON-RPC response. The state root is read from the dump's `root`. Genesis files
+//! don't record one, so their `stateRoot` has to be copied in from the header of the genesis
+//! block.
+//!
+//! Storage keys must be slots rather than hashed slots, i.e. dumps have to be taken from a node
+//! that keeps preimages.
+//!
+//! ```ignore
+//! let dump = StateDump::load(Path::new("fixtures/state_dump/genesis.json"))?;
+//! dump.check_root()?;
+//! let proof = dump.state.get_proof(address, &dump.slots(address));
+//! ```
+
+use crate::{
+    eth_proof_fixture::result,
+    ethereum_trie::{Account, StateTrie, StateTrieBuilder, EMPTY_CODE_HASH, EMPTY_ROOT},
+    keccak256,
+};
+use ethers::types::Address;
+use primitive_types::{H256, U256};
+use serde_json::Value;
+use std::{
+    collections::BTreeMap,
+    fmt, fs, io,
+    path::{Path, PathBuf},
+};
+
+/// Error types for importing state dumps
+#[derive(Debug)]
+pub enum DumpError {
+    /// The dump could not be read
+    Io(PathBuf, io::Error),
+    /// The dump is not valid JSON
+    Json(serde_json::Error),
+    /// There is neither an `alloc` nor an `accounts` object
+    MissingAccounts,
+    /// A field does not hold the value it should, e.g. an address that isn't 20 bytes
+    InvalidField(String),
+    /// The dump records no state root
+    MissingRoot,
+    /// The state root computed from the accounts is not the one recorded in the dump
+    RootMismatch { recorded: H256, computed: H256 },
+    /// The storage root computed from an account's storage is not the one recorded for it
+    StorageRootMismatch { address: Address, recorded: H256, computed: H256 },
+}
+
+impl fmt::Display for DumpError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DumpError::Io(path, err) => write!(f, "failed to read {}: {err}", path.display()),
+            DumpError::Json(err) => write!(f, "invalid dump: {err}"),
+            DumpError::MissingAccounts => write!(f, "dump has neither alloc nor accounts"),
+            DumpError::InvalidField(field) => write!(f, "invalid {field}"),
+            DumpError::MissingRoot => write!(f, "dump has no state root"),
+            DumpError::RootMismatch { recorded, computed } =>
+                write!(f, "state root is {computed:?}, dump records {recorded:?}"),
+            DumpError::StorageRootMismatch { address, recorded, computed } => write!(
+                f,
+                "storage root of {address:?} is {computed:?}, dump records {recorded:?}"
+            ),
+        }
+    }
+}
+
+impl From<serde_json::Error> for DumpError {
+    fn from(err: serde_json::Error) -> Self {
+        DumpError::Json(err)
+    }
+}
+
+/// The accounts of a genesis file or state dump, hashed into a state trie
+pub struct StateDump {
+    /// The state root recorded in the dump, if any
+    pub recorded_root: Option<H256>,
+    pub state: StateTrie,
+    /// Every storage slot of every account, zero slots excluded
+    storage: BTreeMap<Address, BTreeMap<H256, U256>>,
+}
+
+impl StateDump {
+    /// Loads the dump at `path`.
+    pub fn load(path: &Path) -> Result<Self, DumpError> {
+        let json = fs::read_to_string(path).map_err(|err| DumpError::Io(path.into(), err))?;
+        Self::from_json(&json)
+    }
+
+    /// Parses a genesis file or a state dump and builds its state trie. Fails if an account
+    /// records a storage root that differs from the root of its storage.
+    pub fn from_json(json: &str) -> Result<Self, DumpError> {
+        let json = result(serde_json::from_str(json)?);
+        let recorded_root = match json.get("root").or_else(|| json.get("stateRoot")) {
+            Some(root) => {
+                let root = parse_h256(root);
+                Some(root.ok_or_else(|| DumpError::InvalidField("state root".into()))?)
+            },
+            None => None,
+        };
+        let accounts = match json.get("alloc").or_else(|| json.get("accounts")) {
+            Some(Value::Object(accounts)) => accounts,
+            _ => return Err(DumpError::MissingAccounts),
+        };
+
+        let mut builder = StateTrieBuilder::new();
+        let mut storage = BTreeMap::new();
+        let mut storage_roots = BTreeMap::new();
+        for (address, fields) in accounts {
+            let address = parse_fixed(address, 20)
+                .map(|address| Address::from_slice(&address))
+                .ok_or_else(|| DumpError::InvalidField(format!("address {address}")))?;
+            let (account, slots) = parse_account(fields)
+                .map_err(|field| DumpError::InvalidField(format!("{field} of {address:?}")))?;
+
+            builder = builder.account(address, account);
+            for (slot, value) in &slots {
+                builder = builder.storage(address, *slot, *value);
+            }
+            if fields.get("root").is_some() {
+                storage_roots.insert(address, account.storage_root);
+            }
+            let slots = slots.into_iter().filter(|(_, value)| !value.is_zero());
+            storage.insert(address, slots.collect());
+        }
+        let state = builder.build();
+
+        for (address, recorded) in storage_roots {
+            let computed = state.account(address).expect("every account is added; qed");
+            let computed = computed.storage_root;
+            if computed != recorded {
+                return Err(DumpError::StorageRootMismatch { address, recorded, computed })
+            }
+        }
+
+        Ok(Self { recorded_root, state, storage })
+    }
+
+    /// Checks the root of the imported state against the one recorded in the dump, returning it
+    /// if they match.
+    pub fn check_root(&self) -> Result<H256, DumpError> {
+        let recorded = self.recorded_root.ok_or(DumpError::MissingRoot)?;
+        let computed = self.state.root();
+        if computed != recorded {
+            return Err(DumpError::RootMismatch { recorded, computed })
+        }
+
+        Ok(computed)
+    }
+
+    /// Addresses of every account in the dump
+    pub fn addresses(&self) -> impl Iterator<Item = Address> + '_ {
+        self.storage.keys().copied()
+    }
+
+    /// The slots that are set in the storage of `address`, and their values
+    pub fn storage(&self, address: Address) -> Option<&BTreeMap<H256, U256>> {
+        self.storage.get(&address)
+    }
+
+    /// The slots that are set in the storage of `address`, to pass to
+    /// [`StateTrie::get_proof`]
+    pub fn slots(&self, address: Address) -> Vec<H256> {
+        self.storage(address).into_iter().flat_map(|storage| storage.keys().copied()).collect()
+    }
+}
+
+/// Parses the fields of an account, returning the name of the first invalid field on failure.
+///
+/// Accounts whose storage was left out of a dump keep their recorded `root`, but their slots
+/// can't be proven.
+fn parse_account(fields: &Value) -> Result<(Account, Vec<(H256, U256)>), &'static str> {
+    let nonce = match fields.get("nonce") {
+        Some(nonce) => parse_quantity(nonce).filter(|nonce| nonce.bits() <= 64).ok_or("nonce")?,
+        None => U256::zero(),
+    };
+    let balance = match fields.get("balance") {
+        Some(balance) => parse_quantity(balance).ok_or("balance")?,
+        None => U256::zero(),
+    };
+    let code = match fields.get("code") {
+        Some(code) => {
+            let code = code.as_str().ok_or("code")?;
+            hex::decode(code.strip_prefix("0x").unwrap_or(code)).or(Err("code"))?
+        },
+        None => vec![],
+    };
+    let code_hash = match fields.get("codeHash") {
+        _ if !code.is_empty() => H256(keccak256(&code)),
+        Some(hash) => parse_h256(hash).ok_or("codeHash")?,
+        None => EMPTY_CODE_HASH,
+    };
+    let storage_root = match fields.get("root") {
+        Some(root) => parse_h256(root).ok_or("root")?,
+        None => EMPTY_ROOT,
+    };
+
+    let mut slots = vec![];
+    if let Some(storage) = fields.get("storage") {
+        let storage = storage.as_object().ok_or("storage")?;
+        for (slot, value) in storage {
+            let slot = parse_padded(slot, 32).ok_or("storage slot")?;
+            let value = value.as_str().and_then(|value| parse_padded(value, 32));
+            let value = value.ok_or("storage value")?;
+            slots.push((H256::from_slice(&slot), U256::from_big_endian(&value)));
+        }
+    }
+
+    let account = Account { nonce: nonce.as_u64(), balance, storage_root, code_hash };
+    Ok((account, slots))
+}
+
+/// Parses a JSON number, a decimal string or a `0x` prefixed hex string. Genesis files and dumps
+/// use all three.
+fn parse_quantity(value: &Value) -> Option<U256> {
+    match value {
+        Value::Number(number) => number.as_u64().map(U256::from),
+        Value::String(string) => match string.strip_prefix("0x") {
+            Some(hex) => U256::from_str_radix(hex, 16).ok(),
+            None => U256::from_dec_str(string).ok(),
+        },
+        _ => None,
+    }
+}
+
+/// Parses a 32 byte hash
+fn parse_h256(value: &Value) -> Option<H256> {
+    let bytes = parse_fixed(value.as_str()?, 32)?;
+    Some(H256::from_slice(&bytes))
+}
+
+/// Decodes exactly `len` bytes of hex, with or without a `0x` prefix. Genesis files often leave
+/// the prefix off addresses.
+fn parse_fixed(value: &str, len: usize) -> Option<Vec<u8>> {
+    let digits = value.strip_prefix("0x").unwrap_or(value);
+    if digits.len() != len * 2 {
+        return None
+    }
+
+    hex::decode(digits).ok()
+}
+
+/// Decodes up to `len` bytes of hex, left padded to `len` bytes. Dumps trim the leading zeros
+/// of storage values and leave off their prefix.
+fn parse_padded(value: &str, len: usize) -> Option<Vec<u8>> {
+    let digits = value.strip_prefix("0x").unwrap_or(value);
+    if digits.len() > len * 2 {
+        return None
+    }
+
+    hex::decode(format!("{digits:0>width$}", width = len * 2)).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn fixture(name: &str) -> PathBuf {
+        PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("fixtures/state_dump").join(name)
+    }
+
+    #[test]
+    fn test_import_fixtures() {
+        for name in ["genesis.json", "dump_block.json", "holesky_genesis.json"] {
+            let dump = StateDump::load(&fixture(name)).unwrap();
+            let root = dump.check_root().unwrap();
+            assert_eq!(Some(root), dump.recorded_root);
+
+            // every account and slot is in the trie
+            for address in dump.addresses() {
+                let slots = dump.slots(address);
+                let proof = dump.state.get_proof(address, &slots);
+                assert_eq!(Some(&proof.account()), dump.state.account(address), "{name}");
+                for storage_proof in proof.storage_proof {
+                    let value = dump.storage(address).unwrap()[&storage_proof.slot()];
+                    assert_eq!(storage_proof.value, value, "{name}");
+                }
+            }
+        }
+    }
+
+    /// A genesis file with accounts at `0xbeef` and `0xcafe`
+    fn genesis(state_root: &str) -> String {
+        r#"{
+            "stateRoot": "STATE_ROOT",
+            "alloc": {
+                "000000000000000000000000000000000000beef": {
+                    "balance": "1000000000000000000",
+                    "nonce": "0x2a",
+                    "storage": { "0x01": "0de0b6b3a7640000", "0x02": "0x00" }
+                },
+                "0x000000000000000000000000000000000000cafe": {
+                    "balance": "0xde0b6b3a7640000",
+                    "nonce": 3
+                }
+            }
+        }"#
+        .replace("STATE_ROOT", state_root)
+    }
+
+    #[test]
+    fn test_quantities() {
+        // the state root has to be 32 bytes, slots and storage values don't
+        let json = genesis("0x01");
+        assert!(matches!(StateDump::from_json(&json), Err(DumpError::InvalidField(_))));
+
+        let json = genesis(&format!("0x{}", "01".repeat(32)));
+        let dump = StateDump::from_json(&json).unwrap();
+        assert!(matches!(dump.check_root(), Err(DumpError::RootMismatch { .. })));
+
+        let beef = dump.state.account(Address::from_low_u64_be(0xbeef)).unwrap();
+        let cafe = dump.state.account(Address::from_low_u64_be(0xcafe)).unwrap();
+        assert_eq!((beef.nonce, beef.balance), (42, U256::exp10(18)));
+        assert_eq!((cafe.nonce, cafe.balance), (3, U256::exp10(18)));
+
+        let storage = dump.storage(Address::from_low_u64_be(0xbeef)).unwrap();
+        assert_eq!(storage.len(), 1);
+        assert_eq!(storage[&H256::from_low_u64_be(1)], U256::exp10(18));
+    }
+
+    #[test]
+    fn test_storage_root_mismatch() {
+        let json = format!(
+            r#"{{ "accounts": {{ "0x000000000000000000000000000000000000beef": {{
+                "root": "{EMPTY_ROOT:?}",
+                "storage": {{ "0x{}": "01" }}
+            }} }} }}"#,
+            "00".repeat(32)
+        );
+        assert!(matches!(
+            StateDump::from_json(&json),
+            Err(DumpError::StorageRootMismatch { .. })
+        ));
+
+        let dump = StateDump::from_json(&json.replace("01\"", "00\"")).unwrap();
+        assert!(matches!(dump.check_root(), Err(DumpError::MissingRoot)));
+    }
+}
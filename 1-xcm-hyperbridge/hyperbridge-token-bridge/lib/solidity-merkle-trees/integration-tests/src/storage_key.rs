@@ -0,0 +1,105 @@
+//! Keys of FRAME storage items, built by name instead of pasted in as hex.
+//!
+//! ```ignore
+//! // System::Account(alice)
+//! let key = StorageKey::new("System", "Account")
+//!     .key(StorageHasher::Blake2_128Concat, &alice)
+//!     .build();
+//! ```
+
+use crate::Token;
+use codec::Encode;
+use sp_core::hashing::{blake2_128, twox_128, twox_64};
+
+/// Hashers that FRAME applies to the keys of storage maps
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StorageHasher {
+    /// `blake2_128(key) ++ key`
+    Blake2_128Concat,
+    /// `twox_64(key) ++ key`
+    Twox64Concat,
+    /// The key itself
+    Identity,
+}
+
+impl StorageHasher {
+    /// Hashes a SCALE encoded map key.
+    pub fn hash(&self, encoded: &[u8]) -> Vec<u8> {
+        let hash = match self {
+            StorageHasher::Blake2_128Concat => blake2_128(encoded).to_vec(),
+            StorageHasher::Twox64Concat => twox_64(encoded).to_vec(),
+            StorageHasher::Identity => vec![],
+        };
+
+        [hash, encoded.to_vec()].concat()
+    }
+}
+
+/// Builds the key of a storage item: `twox_128(pallet) ++ twox_128(item)`, followed by the
+/// hashed keys of a map.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StorageKey(Vec<u8>);
+
+impl StorageKey {
+    /// The key of a storage value, or the prefix shared by every entry of a map.
+    pub fn new(pallet: &str, item: &str) -> Self {
+        Self([twox_128(pallet.as_bytes()), twox_128(item.as_bytes())].concat())
+    }
+
+    /// Appends a map key hashed with `hasher`. Double maps and n-maps take one call per key, in
+    /// the order they are declared.
+    pub fn key(mut self, hasher: StorageHasher, key: &impl Encode) -> Self {
+        self.0.extend(hasher.hash(&key.encode()));
+        self
+    }
+
+    /// The raw key, as looked up in the trie
+    pub fn build(self) -> Vec<u8> {
+        self.0
+    }
+}
+
+/// The `bytes[] keys` argument of `VerifyKeys`.
+pub fn keys_token(keys: &[Vec<u8>]) -> Token {
+    Token::Array(keys.iter().cloned().map(Token::Bytes).collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use hex_literal::hex;
+
+    #[test]
+    fn test_storage_value_key() {
+        assert_eq!(
+            StorageKey::new("Timestamp", "Now").build(),
+            hex!("f0c365c3cf59d671eb72da0e7a4113c49f1f0515f462cdcf84e0f1d6045dfcbb")
+        );
+    }
+
+    #[test]
+    fn test_storage_map_keys() {
+        // System::Account of //Alice
+        let alice = hex!("d43593c715fdd31c61141abd04a99fd6822c8558854ccde39a5684e7a56da27d");
+        assert_eq!(
+            StorageKey::new("System", "Account")
+                .key(StorageHasher::Blake2_128Concat, &alice)
+                .build(),
+            hex!(
+                "26aa394eea5630e07c48ae0c9558cef7b99d880ec681799c0cf30e8886371da9"
+                "de1e86a9a8c739864cf3cc5ec2bea59f"
+                "d43593c715fdd31c61141abd04a99fd6822c8558854ccde39a5684e7a56da27d"
+            )
+        );
+
+        let prefix = StorageKey::new("Assets", "Account").build();
+        let key = StorageKey::new("Assets", "Account")
+            .key(StorageHasher::Twox64Concat, &7u32)
+            .key(StorageHasher::Identity, &alice)
+            .build();
+        assert_eq!(key[..32], prefix[..]);
+        assert_eq!(key[32..40], twox_64(&7u32.encode()));
+        assert_eq!(key[40..44], 7u32.encode()[..]);
+        assert_eq!(key[44..], alice);
+    }
+}
@@ -0,0 +1,360 @@
+//! Slots of Solidity state variables, computed from the storage layout rules instead of worked
+//! out by hand. The slots feed straight into [`StateTrie::get_proof`] and `VerifyEthereum`.
+//!
+//! ```ignore
+//! // allowance[owner][spender], with `allowance` declared in slot 1
+//! let slot = StorageSlot::new(1).mapping(&owner).mapping(&spender).slot();
+//! let proof = state.get_proof(token, &[slot]);
+//! ```
+//!
+//! [`StateTrie::get_proof`]: crate::ethereum_trie::StateTrie::get_proof
+
+use crate::keccak256;
+use ethers::types::Address;
+use primitive_types::{H256, U256};
+use std::fmt;
+
+/// Reasons a packed field can't be laid out
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SlotError {
+    /// A field of `size` bytes, `offset` bytes into its slot, is empty or runs past the slot
+    FieldOutOfSlot { offset: usize, size: usize },
+}
+
+impl fmt::Display for SlotError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SlotError::FieldOutOfSlot { offset, size } =>
+                write!(f, "field of {size} bytes at offset {offset} doesn't fit in a slot"),
+        }
+    }
+}
+
+/// A key of a Solidity mapping, in the form it is hashed with the slot of the mapping
+pub trait MappingKey {
+    /// Value types are left padded to 32 bytes, `string` and `bytes` keys are used as they are.
+    fn encode_key(&self) -> Vec<u8>;
+}
+
+impl MappingKey for H256 {
+    fn encode_key(&self) -> Vec<u8> {
+        self.as_bytes().to_vec()
+    }
+}
+
+impl MappingKey for U256 {
+    fn encode_key(&self) -> Vec<u8> {
+        <[u8; 32]>::from(*self).to_vec()
+    }
+}
+
+impl MappingKey for u64 {
+    fn encode_key(&self) -> Vec<u8> {
+        U256::from(*self).encode_key()
+    }
+}
+
+impl MappingKey for bool {
+    fn encode_key(&self) -> Vec<u8> {
+        U256::from(*self as u8).encode_key()
+    }
+}
+
+impl MappingKey for Address {
+    fn encode_key(&self) -> Vec<u8> {
+        let mut key = [0u8; 32];
+        key[12..].copy_from_slice(self.as_bytes());
+        key.to_vec()
+    }
+}
+
+impl MappingKey for [u8] {
+    fn encode_key(&self) -> Vec<u8> {
+        self.to_vec()
+    }
+}
+
+impl MappingKey for str {
+    fn encode_key(&self) -> Vec<u8> {
+        self.as_bytes().to_vec()
+    }
+}
+
+/// The slot of a state variable, or of a value nested inside one.
+///
+/// Slots are numbers modulo `2 ** 256`, so offsets past the last slot wrap around just like they
+/// do in the EVM.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct StorageSlot(U256);
+
+impl StorageSlot {
+    /// The slot of a state variable, as listed by `forge inspect <contract> storageLayout`.
+    pub fn new(slot: u64) -> Self {
+        Self(U256::from(slot))
+    }
+
+    /// The value at `key` of the mapping in this slot, `keccak256(key . slot)`. Nested mappings
+    /// take one call per key, from the outermost in.
+    pub fn mapping(self, key: &(impl MappingKey + ?Sized)) -> Self {
+        let preimage = [key.encode_key(), self.0.encode_key()].concat();
+        Self(U256::from_big_endian(&keccak256(preimage)))
+    }
+
+    /// The first slot of the data of the dynamic array, or of the long `string` or `bytes`, in
+    /// this slot, `keccak256(slot)`. The slot itself holds the length.
+    pub fn data(self) -> Self {
+        Self(U256::from_big_endian(&keccak256(self.0.encode_key())))
+    }
+
+    /// The element at `index` of the dynamic array in this slot, for elements that take up
+    /// `element_slots` whole slots each, such as `uint256` or structs.
+    pub fn array_element(self, index: u64, element_slots: u64) -> Self {
+        // a product of two u64 always fits in a U256
+        self.data().offset(U256::from(index) * U256::from(element_slots))
+    }
+
+    /// The element at `index` of the dynamic array in this slot, for elements of `size` bytes
+    /// that are packed several to a slot, such as `uint64` or `address`. Fails unless `size` is
+    /// between 1 and 32.
+    pub fn packed_array_element(
+        self,
+        index: u64,
+        size: usize,
+    ) -> Result<(Self, PackedField), SlotError> {
+        if size == 0 || size > 32 {
+            return Err(SlotError::FieldOutOfSlot { offset: 0, size })
+        }
+
+        let per_slot = (32 / size) as u64;
+        let offset = (index % per_slot) as usize * size;
+        Ok((self.data().field(index / per_slot), PackedField::new(offset, size)?))
+    }
+
+    /// The slot `offset` slots after this one, e.g. a field of the struct in this slot or an
+    /// element of a fixed size array.
+    pub fn field(self, offset: u64) -> Self {
+        self.offset(U256::from(offset))
+    }
+
+    fn offset(self, slots: U256) -> Self {
+        Self(self.0.overflowing_add(slots).0)
+    }
+
+    /// The slot as a 32 byte word, as passed to `eth_getProof` and hashed into the storage trie
+    pub fn slot(&self) -> H256 {
+        H256(self.0.into())
+    }
+}
+
+/// A value of `size` bytes packed into a slot alongside others, `offset` bytes from the low
+/// order end. Values smaller than 32 bytes are packed in the order they are declared, starting
+/// with the lowest order bytes, as long as they fit.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PackedField {
+    pub offset: usize,
+    pub size: usize,
+}
+
+impl PackedField {
+    /// Fails unless the field holds at least one byte and ends within the slot.
+    pub fn new(offset: usize, size: usize) -> Result<Self, SlotError> {
+        if size == 0 || offset.checked_add(size).map_or(true, |end| end > 32) {
+            return Err(SlotError::FieldOutOfSlot { offset, size })
+        }
+
+        Ok(Self { offset, size })
+    }
+
+    /// Reads the field out of a storage word.
+    pub fn read(&self, word: U256) -> U256 {
+        (word >> (self.offset * 8)) & self.mask()
+    }
+
+    /// Replaces the field in a storage word, leaving the other fields as they are.
+    pub fn write(&self, word: U256, value: U256) -> U256 {
+        let mask = self.mask() << (self.offset * 8);
+        (word & !mask) | ((value << (self.offset * 8)) & mask)
+    }
+
+    fn mask(&self) -> U256 {
+        if self.size == 32 {
+            return U256::MAX
+        }
+
+        (U256::one() << (self.size * 8)) - 1
+    }
+}
+
+/// The words that hold a `string` or `bytes` value stored in `slot`.
+///
+/// Values of up to 31 bytes share their slot with their length, as `data . len * 2`. Longer
+/// values keep `len * 2 + 1` in their slot and their data in the slots from
+/// [`StorageSlot::data`] on.
+pub fn encode_bytes(slot: StorageSlot, value: &[u8]) -> Vec<(H256, U256)> {
+    if value.len() < 32 {
+        let mut word = [0u8; 32];
+        word[..value.len()].copy_from_slice(value);
+        word[31] = (value.len() * 2) as u8;
+        return vec![(slot.slot(), U256::from_big_endian(&word))]
+    }
+
+    let mut words = vec![(slot.slot(), U256::from(value.len() * 2 + 1))];
+    for (i, chunk) in value.chunks(32).enumerate() {
+        let mut word = [0u8; 32];
+        word[..chunk.len()].copy_from_slice(chunk);
+        words.push((slot.array_element(i as u64, 1).slot(), U256::from_big_endian(&word)));
+    }
+
+    words
+}
+
+/// The slots holding the data of the `string` or `bytes` value in `slot`, given the word in
+/// `slot` itself. Short values have none. Returns `None` if the length in `head` doesn't fit in
+/// a `u64`, which no value could have.
+pub fn bytes_data_slots(slot: StorageSlot, head: U256) -> Option<Vec<H256>> {
+    if !head.bit(0) {
+        return Some(vec![])
+    }
+
+    let len = u64::try_from(head >> 1).ok()?;
+    Some((0..len.div_ceil(32)).map(|i| slot.array_element(i, 1).slot()).collect())
+}
+
+/// Decodes a `string` or `bytes` value from the word in its slot and the words in its
+/// [`bytes_data_slots`]. Returns `None` if the words aren't a valid encoding.
+pub fn decode_bytes(head: U256, data: &[U256]) -> Option<Vec<u8>> {
+    let head = <[u8; 32]>::from(head);
+    if head[31] & 1 == 0 {
+        let len = head[31] as usize / 2;
+        if len > 31 || !data.is_empty() {
+            return None
+        }
+
+        return Some(head[..len].to_vec())
+    }
+
+    let len = U256::from_big_endian(&head) >> 1;
+    if len < U256::from(32) || (len + 31) / 32 != U256::from(data.len()) {
+        return None
+    }
+
+    let bytes = data.iter().flat_map(|word| <[u8; 32]>::from(*word)).collect::<Vec<_>>();
+    Some(bytes[..len.as_usize()].to_vec())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use hex_literal::hex;
+
+    #[test]
+    fn test_mapping_slots() {
+        let alice = Address::from_low_u64_be(0xa11ce);
+        let bridge = Address::from_low_u64_be(0xb41d6e);
+
+        // the first entry of a mapping in slot 0, keccak256(0 . 0)
+        assert_eq!(
+            StorageSlot::new(0).mapping(&0u64).slot(),
+            H256(hex!("ad3228b676f7d3cd4284a5443f17f1962b36e491b30a40b2405849e597ba5fb5"))
+        );
+        assert_eq!(
+            StorageSlot::new(1).mapping(&alice).mapping(&bridge).slot(),
+            H256(hex!("44714c19b3e372a63e700f77a4d713dc3fb8c8deb75aea93c2b8909d7e5e94eb"))
+        );
+        assert_eq!(
+            StorageSlot::new(7).mapping("EVM-11155111").slot(),
+            H256(hex!("383538842336acc6b1be49bb7c2044f7a003e7db6dba91cb485ee94b45207e29"))
+        );
+    }
+
+    #[test]
+    fn test_array_slots() {
+        assert_eq!(
+            StorageSlot::new(0).data().slot(),
+            H256(hex!("290decd9548b62a8d60345a988386fc84ba6bc95484008f6362f93160ef3e563"))
+        );
+        assert_eq!(
+            StorageSlot::new(6).array_element(2, 1).slot(),
+            H256(hex!("f652222313e28459528d920b65115c16c04f3efc82aaedc97be59f3f377c0d41"))
+        );
+        assert_eq!(StorageSlot::new(6).array_element(1, 2), StorageSlot::new(6).data().field(2));
+        // the offset is past u64, and wraps around like the slot itself
+        let offset = U256::from(u64::MAX) * U256::from(u64::MAX);
+        assert_eq!(
+            StorageSlot::new(6).array_element(u64::MAX, u64::MAX),
+            StorageSlot(StorageSlot::new(6).data().0.overflowing_add(offset).0)
+        );
+
+        // three uint64 to a slot
+        let (slot, field) = StorageSlot::new(6).packed_array_element(7, 8).unwrap();
+        assert_eq!(slot, StorageSlot::new(6).data().field(1));
+        assert_eq!(field, PackedField { offset: 24, size: 8 });
+        assert_eq!(
+            StorageSlot::new(6).packed_array_element(7, 0),
+            Err(SlotError::FieldOutOfSlot { offset: 0, size: 0 })
+        );
+        assert!(StorageSlot::new(6).packed_array_element(7, 33).is_err());
+        let (slot, field) = StorageSlot::new(6).packed_array_element(u64::MAX, 32).unwrap();
+        assert_eq!(slot, StorageSlot::new(6).data().field(u64::MAX));
+        assert_eq!(field, PackedField { offset: 0, size: 32 });
+
+        // slots wrap around
+        let last = StorageSlot(U256::MAX);
+        assert_eq!(last.field(1), StorageSlot::new(0));
+    }
+
+    #[test]
+    fn test_packed_fields() {
+        // address to; uint64 timeout; bool redeemed;
+        let to = PackedField::new(0, 20).unwrap();
+        let timeout = PackedField::new(20, 8).unwrap();
+        let redeemed = PackedField::new(28, 1).unwrap();
+        let word = to.write(U256::zero(), U256::from(0xb0b));
+        let word = timeout.write(word, U256::from(1_700_000_000u64));
+        let word = redeemed.write(word, U256::one());
+
+        assert_eq!(
+            <[u8; 32]>::from(word),
+            hex!("00000001000000006553f1000000000000000000000000000000000000000b0b")
+        );
+        assert_eq!(to.read(word), U256::from(0xb0b));
+        assert_eq!(timeout.read(word), U256::from(1_700_000_000u64));
+        assert_eq!(redeemed.read(word), U256::one());
+        assert_eq!(PackedField::new(0, 32).unwrap().read(word), word);
+
+        assert_eq!(PackedField::new(0, 0), Err(SlotError::FieldOutOfSlot { offset: 0, size: 0 }));
+        assert!(PackedField::new(31, 2).is_err());
+        assert!(PackedField::new(usize::MAX, 1).is_err());
+    }
+
+    #[test]
+    fn test_bytes_encoding() {
+        let slot = StorageSlot::new(3);
+        for len in [0, 1, 31, 32, 33, 64, 100] {
+            let value = (0..len).map(|i| i as u8 + 1).collect::<Vec<_>>();
+            let words = encode_bytes(slot, &value);
+            assert_eq!(words.len(), if len < 32 { 1 } else { 1 + (len + 31) / 32 });
+
+            let head = words[0].1;
+            let data_slots = bytes_data_slots(slot, head).unwrap();
+            assert_eq!(data_slots, words[1..].iter().map(|(slot, _)| *slot).collect::<Vec<_>>());
+
+            let data = words[1..].iter().map(|(_, word)| *word).collect::<Vec<_>>();
+            assert_eq!(decode_bytes(head, &data), Some(value));
+        }
+
+        // "BTK"
+        let words = encode_bytes(slot, b"BTK");
+        assert_eq!(
+            <[u8; 32]>::from(words[0].1),
+            hex!("42544b0000000000000000000000000000000000000000000000000000000006")
+        );
+        // long form for a short value
+        assert_eq!(decode_bytes(U256::from(3 * 2 + 1), &[U256::zero()]), None);
+        // missing data
+        assert_eq!(decode_bytes(U256::from(40 * 2 + 1), &[U256::zero()]), None);
+        // a length no value could have
+        assert_eq!(bytes_data_slots(slot, U256::MAX), None);
+        assert_eq!(decode_bytes(U256::MAX, &[]), None);
+    }
+}
@@ -0,0 +1,234 @@
+//! Typed decoding of the values returned by `VerifyKeys` and `VerifyEthereum`.
+//!
+//! Ethereum accounts and storage slots are RLP, substrate values are SCALE. A [`ScaleRegistry`]
+//! maps storage items to the SCALE types they hold, so values of any pallet can be decoded by key.
+
+use crate::{ethereum_trie::Account, storage_key::StorageKey};
+use codec::{Decode, DecodeAll, Encode};
+use primitive_types::U256;
+use rlp::DecoderError;
+use std::{
+    any::{type_name, Any, TypeId},
+    fmt,
+};
+
+/// Error types for decoding values
+#[derive(Debug)]
+pub enum ValueError {
+    /// The value is not valid RLP for the type
+    Rlp(DecoderError),
+    /// The value is not a SCALE encoding of the type, or has bytes left over
+    Scale(codec::Error),
+    /// No type is registered for the storage key
+    Unregistered(Vec<u8>),
+    /// The storage key holds a different type than the one requested
+    TypeMismatch { registered: &'static str, requested: &'static str },
+}
+
+impl fmt::Display for ValueError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ValueError::Rlp(err) => write!(f, "invalid rlp: {err}"),
+            ValueError::Scale(err) => write!(f, "invalid scale: {err}"),
+            ValueError::Unregistered(key) =>
+                write!(f, "no type registered for {}", hex::encode(key)),
+            ValueError::TypeMismatch { registered, requested } =>
+                write!(f, "key holds {registered}, not {requested}"),
+        }
+    }
+}
+
+impl From<DecoderError> for ValueError {
+    fn from(err: DecoderError) -> Self {
+        ValueError::Rlp(err)
+    }
+}
+
+impl From<codec::Error> for ValueError {
+    fn from(err: codec::Error) -> Self {
+        ValueError::Scale(err)
+    }
+}
+
+/// Decodes an account returned by `VerifyEthereum`. Addresses without an account have an empty
+/// value.
+pub fn decode_account(value: &[u8]) -> Result<Option<Account>, ValueError> {
+    if value.is_empty() {
+        return Ok(None)
+    }
+
+    Ok(Some(rlp::decode(value)?))
+}
+
+/// Decodes a storage slot returned by `VerifyEthereum`. Slots that are not set have an empty
+/// value and hold zero.
+pub fn decode_slot(value: &[u8]) -> Result<U256, ValueError> {
+    if value.is_empty() {
+        return Ok(U256::zero())
+    }
+
+    Ok(rlp::decode(value)?)
+}
+
+/// Decodes a SCALE encoded value, rejecting any bytes left over.
+pub fn decode_scale<T: Decode>(value: &[u8]) -> Result<T, ValueError> {
+    Ok(T::decode_all(&mut &value[..])?)
+}
+
+/// `pallet_timestamp::Now`, in milliseconds since the unix epoch
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Encode, Decode)]
+pub struct Timestamp(pub u64);
+
+/// `frame_system::AccountInfo`, with the `AccountData` of `pallet_balances`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Encode, Decode)]
+pub struct AccountInfo {
+    /// Number of transactions the account has sent
+    pub nonce: u32,
+    pub consumers: u32,
+    pub providers: u32,
+    pub sufficients: u32,
+    pub data: AccountData,
+}
+
+/// `pallet_balances::AccountData`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Encode, Decode)]
+pub struct AccountData {
+    pub free: u128,
+    pub reserved: u128,
+    pub frozen: u128,
+    pub flags: u128,
+}
+
+/// Decodes a value into a boxed `T`
+type Decoder = Box<dyn Fn(&[u8]) -> Result<Box<dyn Any>, ValueError>>;
+
+/// The type held by the keys starting with `prefix`
+struct Registration {
+    prefix: Vec<u8>,
+    type_id: TypeId,
+    type_name: &'static str,
+    decode: Decoder,
+}
+
+/// Maps storage items to the SCALE types they hold.
+///
+/// ```ignore
+/// let registry = ScaleRegistry::with_defaults()
+///     .register::<u128>(StorageKey::new("Balances", "TotalIssuance").build());
+/// let account = registry.decode::<AccountInfo>(&key, &value)?;
+/// ```
+#[derive(Default)]
+pub struct ScaleRegistry {
+    registrations: Vec<Registration>,
+}
+
+impl ScaleRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// A registry for `Timestamp::Now` and `System::Account`.
+    pub fn with_defaults() -> Self {
+        Self::new()
+            .register::<Timestamp>(StorageKey::new("Timestamp", "Now").build())
+            .register::<AccountInfo>(StorageKey::new("System", "Account").build())
+    }
+
+    /// Registers `T` as the type held by every key starting with `prefix`, usually the key of a
+    /// storage value or the prefix shared by the entries of a map. When several prefixes match a
+    /// key, the longest one wins.
+    pub fn register<T: Decode + 'static>(mut self, prefix: Vec<u8>) -> Self {
+        let decode: Decoder = Box::new(|value: &[u8]| -> Result<Box<dyn Any>, ValueError> {
+            Ok(Box::new(decode_scale::<T>(value)?))
+        });
+        self.registrations.push(Registration {
+            prefix,
+            type_id: TypeId::of::<T>(),
+            type_name: type_name::<T>(),
+            decode,
+        });
+        self
+    }
+
+    /// Decodes the value at `key` as `T`, which must be the type registered for the key.
+    pub fn decode<T: 'static>(&self, key: &[u8], value: &[u8]) -> Result<T, ValueError> {
+        let registration = self
+            .registrations
+            .iter()
+            .filter(|registration| key.starts_with(&registration.prefix))
+            .max_by_key(|registration| registration.prefix.len())
+            .ok_or_else(|| ValueError::Unregistered(key.to_vec()))?;
+        if registration.type_id != TypeId::of::<T>() {
+            return Err(ValueError::TypeMismatch {
+                registered: registration.type_name,
+                requested: type_name::<T>(),
+            })
+        }
+
+        let decoded = (registration.decode)(value)?;
+        Ok(*decoded.downcast::<T>().expect("type ids are equal; qed"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        ethereum_trie::{EMPTY_CODE_HASH, EMPTY_ROOT},
+        storage_key::StorageHasher,
+    };
+    use hex_literal::hex;
+
+    #[test]
+    fn test_decode_ethereum_values() {
+        let value = hex!(
+            "f84b10874ef05b2fe9d8c8a056e81f171bcc55a6ff8345e692c0f86e5b48e01b996cadc001622fb5e363b4"
+            "21a0c5d2460186f7233c927e7db2dcc703c0e500b653ca82273b7bfad8045d85a470"
+        );
+        let account = Account {
+            nonce: 16,
+            balance: U256::from(0x4ef05b2fe9d8c8u64),
+            storage_root: EMPTY_ROOT,
+            code_hash: EMPTY_CODE_HASH,
+        };
+        assert_eq!(decode_account(&value).unwrap(), Some(account));
+        assert_eq!(decode_account(&[]).unwrap(), None);
+        assert!(decode_account(&value[..40]).is_err());
+
+        assert_eq!(decode_slot(&hex!("80")).unwrap(), U256::zero());
+        assert_eq!(decode_slot(&hex!("8203e8")).unwrap(), U256::from(1000));
+        assert_eq!(decode_slot(&[]).unwrap(), U256::zero());
+    }
+
+    #[test]
+    fn test_scale_registry() {
+        let registry = ScaleRegistry::with_defaults()
+            .register::<u128>(StorageKey::new("Balances", "TotalIssuance").build());
+
+        let key = StorageKey::new("Timestamp", "Now").build();
+        let timestamp = registry.decode::<Timestamp>(&key, &1_677_168_798_005u64.encode());
+        assert_eq!(timestamp.unwrap(), Timestamp(1_677_168_798_005));
+
+        let account = AccountInfo { nonce: 3, providers: 1, ..Default::default() };
+        let key = StorageKey::new("System", "Account")
+            .key(StorageHasher::Blake2_128Concat, &[1u8; 32])
+            .build();
+        assert_eq!(registry.decode::<AccountInfo>(&key, &account.encode()).unwrap(), account);
+        assert!(matches!(
+            registry.decode::<u128>(&key, &account.encode()),
+            Err(ValueError::TypeMismatch { .. })
+        ));
+
+        let key = StorageKey::new("Balances", "TotalIssuance").build();
+        assert_eq!(registry.decode::<u128>(&key, &1000u128.encode()).unwrap(), 1000);
+        // trailing bytes
+        let value = [1000u128.encode(), vec![0]].concat();
+        assert!(matches!(registry.decode::<u128>(&key, &value), Err(ValueError::Scale(_))));
+
+        let key = StorageKey::new("Balances", "InactiveIssuance").build();
+        assert!(matches!(
+            registry.decode::<u128>(&key, &1000u128.encode()),
+            Err(ValueError::Unregistered(_))
+        ));
+    }
+}
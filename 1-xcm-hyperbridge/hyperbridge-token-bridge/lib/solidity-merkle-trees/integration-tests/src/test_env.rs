@@ -0,0 +1,164 @@
+//! A pool of environments shared by the integration tests.
+//!
+//! Each test takes an environment of its own out of the pool and puts it back when it's done, so
+//! tests run side by side instead of queueing for one EVM. New environments are only made when
+//! every pooled one is in use, which caps them at the number of tests running at once. Every
+//! contract is deployed at most once per environment. The EVM state is snapshotted after each
+//! deployment and restored whenever a test asks for a contract, so state changes made by one test
+//! never leak into the next.
+//!
+//! ```ignore
+//! let mut env = TestEnv::shared().await;
+//! let mut contract = env.contract("MerklePatriciaTest").await;
+//! ```
+
+use ethers::{
+    abi::{Abi, Tokenizable, Tokenize},
+    types::Address,
+};
+pub use forge_testsuite::Contract;
+use forge_testsuite::Runner;
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::HashMap,
+    env,
+    future::Future,
+    ops::{Deref, DerefMut},
+    path::PathBuf,
+    sync::Mutex,
+};
+use tokio::runtime::Runtime;
+
+/// Environments no test is using
+static POOL: Lazy<Mutex<Vec<TestEnv>>> = Lazy::new(|| Mutex::new(vec![]));
+
+/// Held while compiling, so environments made at the same time don't race on the build cache.
+/// Only the first compilation builds anything, the others read the cache it leaves behind.
+static COMPILING: Mutex<()> = Mutex::new(());
+
+static RUNTIME: Lazy<Runtime> = Lazy::new(|| Runtime::new().expect("failed to start runtime"));
+
+/// Restores the EVM state captured by [`snapshot`]
+type Snapshot = Box<dyn Fn(&mut Runner) + Send>;
+
+/// Compiled contracts, their deployments and the EVM state to revert to between tests.
+pub struct TestEnv {
+    runner: Runner,
+    deployments: HashMap<&'static str, (Abi, Address)>,
+    snapshot: Snapshot,
+}
+
+impl TestEnv {
+    /// Compiles the contracts in the forge project that contains the integration tests.
+    pub fn new() -> Self {
+        let base_dir = env::current_dir().unwrap().parent().unwrap().display().to_string();
+        let runner = Runner::new(PathBuf::from(&base_dir));
+        let snapshot = snapshot(&runner);
+
+        Self { runner, deployments: HashMap::new(), snapshot }
+    }
+
+    /// Takes an idle environment out of the pool, or makes a new one if every environment is in
+    /// use. It goes back to the pool when the returned guard is dropped.
+    pub async fn shared() -> PooledEnv {
+        let idle = POOL.lock().unwrap().pop();
+        PooledEnv(Some(idle.unwrap_or_else(|| {
+            let _compiling = COMPILING.lock().unwrap();
+            TestEnv::new()
+        })))
+    }
+
+    /// Reverts the EVM to the last snapshot and returns the deployment of `name`, deploying it
+    /// first if this is the first time it's requested.
+    pub async fn contract(&mut self, name: &'static str) -> Contract<'_> {
+        (self.snapshot)(&mut self.runner);
+
+        if !self.deployments.contains_key(name) {
+            let contract = self.runner.deploy(name).await;
+            let deployment = (contract.abi.clone(), contract.address);
+            self.deployments.insert(name, deployment);
+            self.snapshot = snapshot(&self.runner);
+        }
+
+        let (abi, address) = self.deployments[name].clone();
+        Contract { runner: &mut self.runner, abi, address }
+    }
+}
+
+/// An environment taken out of the pool by [`TestEnv::shared`]
+pub struct PooledEnv(Option<TestEnv>);
+
+impl Deref for PooledEnv {
+    type Target = TestEnv;
+
+    fn deref(&self) -> &TestEnv {
+        self.0.as_ref().unwrap()
+    }
+}
+
+impl DerefMut for PooledEnv {
+    fn deref_mut(&mut self) -> &mut TestEnv {
+        self.0.as_mut().unwrap()
+    }
+}
+
+impl Drop for PooledEnv {
+    fn drop(&mut self) {
+        POOL.lock().unwrap().extend(self.0.take());
+    }
+}
+
+/// Gas spent by a call to one of the `*WithGas` wrappers in the test contracts
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub struct GasUsed {
+    /// Gas spent inside the wrapped library function, as measured with `gasleft()`
+    pub execution: u64,
+    /// Gas charged for the calldata of the call
+    pub calldata: u64,
+}
+
+impl GasUsed {
+    /// Gas used by the call, excluding the base transaction cost
+    pub fn total(&self) -> u64 {
+        self.execution + self.calldata
+    }
+}
+
+/// Calls `func`, a `*WithGas` wrapper that returns `(result, gasUsed)`, and adds the cost of the
+/// calldata the call is encoded to, selector included, to the measured gas.
+pub async fn call_with_gas<T, R>(
+    contract: &mut Contract<'_>,
+    func: &'static str,
+    args: T,
+) -> (R, GasUsed)
+where
+    T: Tokenize + Clone,
+    R: Tokenizable,
+{
+    let calldata = encode_call(contract, func, args.clone());
+    let (result, execution) = contract.call::<_, (R, u64)>(func, args).await.unwrap();
+
+    (result, GasUsed { execution, calldata: calldata_gas(&calldata) })
+}
+
+/// The calldata of a call to `func`, selector included
+pub fn encode_call<T: Tokenize>(contract: &Contract<'_>, func: &str, args: T) -> Vec<u8> {
+    let function = contract.abi.function(func).unwrap();
+    function.encode_input(&args.into_tokens()).unwrap()
+}
+
+/// Gas charged for calldata as per EIP-2028
+pub fn calldata_gas(calldata: &[u8]) -> u64 {
+    calldata.iter().map(|byte| if *byte == 0 { 4 } else { 16 }).sum()
+}
+
+/// Runs a future on a runtime shared by every synchronous test, e.g. proptests.
+pub fn block_on<F: Future>(future: F) -> F::Output {
+    RUNTIME.block_on(future)
+}
+
+fn snapshot(runner: &Runner) -> Snapshot {
+    let executor = runner.executor.clone();
+    Box::new(move |runner: &mut Runner| runner.executor = executor.clone())
+}
@@ -0,0 +1,325 @@
+//! Mirrors of the structs in `trie/Node.sol`, as returned by the node decoding functions of
+//! `MerklePatriciaTest`, along with conversions to and from [`NodePlan`].
+//!
+//! Nodes are converted from a plan together with the encoded node the plan points into. They are
+//! converted back by re-encoding them with the substrate [`NodeCodec`], so a node decoded by the
+//! contract can be compared structurally with what `NodeCodec::decode_plan` produces.
+
+use ethers::contract::EthAbiType;
+use primitive_types::U256;
+use sp_trie::NodeCodec;
+use trie_db::{
+    node::{NibbleSlicePlan, NodeHandlePlan, NodePlan, Value, ValuePlan},
+    ChildReference, Hasher, NodeCodec as NodeCodecT,
+};
+
+/// Mirror of `ByteSlice` in `Bytes.sol`
+#[derive(Debug, Clone, PartialEq, Eq, Default, EthAbiType)]
+pub struct ByteSlice {
+    pub data: Vec<u8>,
+    /// Offset of the first unread byte
+    pub offset: U256,
+}
+
+/// Mirror of `NibbleSlice` in `NibbleSlice.sol`
+#[derive(Debug, Clone, PartialEq, Eq, Default, EthAbiType)]
+pub struct NibbleSlice {
+    pub data: Vec<u8>,
+    /// Offset of the first nibble, 1 if the first byte only holds a nibble of padding
+    pub offset: U256,
+}
+
+/// Mirror of `NodeKind` in `Node.sol`. Exactly one of the flags is set.
+#[derive(Debug, Clone, PartialEq, Eq, Default, EthAbiType)]
+pub struct NodeKind {
+    pub is_empty: bool,
+    pub is_leaf: bool,
+    pub is_hashed_leaf: bool,
+    pub is_nibbled_value_branch: bool,
+    pub is_nibbled_hashed_value_branch: bool,
+    pub is_nibbled_branch: bool,
+    pub is_extension: bool,
+    pub is_branch: bool,
+    /// Number of nibbles in the partial key
+    pub nibble_size: U256,
+    /// The encoded node, positioned right after the header
+    pub data: ByteSlice,
+}
+
+/// Mirror of `NodeHandle` in `Node.sol`
+#[derive(Debug, Clone, PartialEq, Eq, Default, EthAbiType)]
+pub struct NodeHandle {
+    pub is_hash: bool,
+    pub hash: [u8; 32],
+    pub is_inline: bool,
+    pub in_line: Vec<u8>,
+}
+
+/// Mirror of `NodeHandleOption` in `Node.sol`
+#[derive(Debug, Clone, PartialEq, Eq, Default, EthAbiType)]
+pub struct NodeHandleOption {
+    pub is_some: bool,
+    pub value: NodeHandle,
+}
+
+/// Mirror of `NibbledBranch` in `Node.sol`
+#[derive(Debug, Clone, PartialEq, Eq, Default, EthAbiType)]
+pub struct NibbledBranch {
+    pub key: NibbleSlice,
+    pub value: NodeHandleOption,
+    pub children: [NodeHandleOption; 16],
+}
+
+/// Mirror of `Leaf` in `Node.sol`
+#[derive(Debug, Clone, PartialEq, Eq, Default, EthAbiType)]
+pub struct Leaf {
+    pub key: NibbleSlice,
+    pub value: NodeHandle,
+}
+
+impl NibbleSlice {
+    /// The partial key of a node, padded on the left to a whole number of bytes
+    pub fn from_plan(plan: &NibbleSlicePlan, data: &[u8]) -> Self {
+        let slice = plan.build(data);
+        NibbleSlice { data: slice.right_iter().collect(), offset: U256::from(slice.len() % 2) }
+    }
+
+    /// Number of nibbles in the slice
+    pub fn len(&self) -> usize {
+        self.data.len() * 2 - self.offset.as_usize()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+impl NodeHandle {
+    pub fn hash(hash: &[u8]) -> Self {
+        let hash = hash.try_into().expect("hashes are 32 bytes");
+        NodeHandle { is_hash: true, hash, ..Default::default() }
+    }
+
+    pub fn inline(data: &[u8]) -> Self {
+        NodeHandle { is_inline: true, in_line: data.to_vec(), ..Default::default() }
+    }
+
+    pub fn from_value_plan(plan: &ValuePlan, data: &[u8]) -> Self {
+        match plan {
+            ValuePlan::Inline(range) => NodeHandle::inline(&data[range.clone()]),
+            ValuePlan::Node(range) => NodeHandle::hash(&data[range.clone()]),
+        }
+    }
+
+    pub fn from_handle_plan(plan: &NodeHandlePlan, data: &[u8]) -> Self {
+        match plan {
+            NodeHandlePlan::Inline(range) => NodeHandle::inline(&data[range.clone()]),
+            NodeHandlePlan::Hash(range) => NodeHandle::hash(&data[range.clone()]),
+        }
+    }
+
+    /// The handle as the value of a node
+    pub fn to_value(&self) -> Value<'_> {
+        if self.is_hash {
+            Value::Node(&self.hash)
+        } else {
+            Value::Inline(&self.in_line)
+        }
+    }
+
+    /// The handle as a child of a branch
+    pub fn to_child<H: Hasher>(&self) -> ChildReference<H::Out> {
+        let mut out = H::Out::default();
+        if self.is_hash {
+            out.as_mut().copy_from_slice(&self.hash);
+            ChildReference::Hash(out)
+        } else {
+            out.as_mut()[..self.in_line.len()].copy_from_slice(&self.in_line);
+            ChildReference::Inline(out, self.in_line.len())
+        }
+    }
+}
+
+impl NodeHandleOption {
+    pub fn some(value: NodeHandle) -> Self {
+        NodeHandleOption { is_some: true, value }
+    }
+
+    pub fn as_option(&self) -> Option<&NodeHandle> {
+        self.is_some.then_some(&self.value)
+    }
+}
+
+impl From<Option<NodeHandle>> for NodeHandleOption {
+    fn from(value: Option<NodeHandle>) -> Self {
+        value.map(NodeHandleOption::some).unwrap_or_default()
+    }
+}
+
+impl NodeKind {
+    /// Classifies a node of the substrate codec, the way `SubstrateTrieDB.decodeNodeKind` does.
+    pub fn from_plan(plan: &NodePlan, data: &[u8]) -> Self {
+        let mut kind = NodeKind::default();
+        let (partial, header_prefix_bits) = match plan {
+            NodePlan::Empty => {
+                kind.is_empty = true;
+                return kind
+            },
+            NodePlan::Leaf { partial, value: ValuePlan::Inline(_) } => {
+                kind.is_leaf = true;
+                (partial, 2)
+            },
+            NodePlan::Leaf { partial, value: ValuePlan::Node(_) } => {
+                kind.is_hashed_leaf = true;
+                (partial, 3)
+            },
+            NodePlan::NibbledBranch { partial, value: None, .. } => {
+                kind.is_nibbled_branch = true;
+                (partial, 2)
+            },
+            NodePlan::NibbledBranch { partial, value: Some(ValuePlan::Inline(_)), .. } => {
+                kind.is_nibbled_value_branch = true;
+                (partial, 2)
+            },
+            NodePlan::NibbledBranch { partial, value: Some(ValuePlan::Node(_)), .. } => {
+                kind.is_nibbled_hashed_value_branch = true;
+                (partial, 4)
+            },
+            NodePlan::Extension { .. } | NodePlan::Branch { .. } =>
+                panic!("the substrate codec has no extension or plain branch nodes"),
+        };
+
+        let nibble_size = partial.len();
+        kind.nibble_size = U256::from(nibble_size);
+        kind.data = ByteSlice {
+            data: data.to_vec(),
+            offset: U256::from(header_len(nibble_size, header_prefix_bits)),
+        };
+        kind
+    }
+}
+
+impl Leaf {
+    /// Returns `None` if the plan is not a leaf
+    pub fn from_plan(plan: &NodePlan, data: &[u8]) -> Option<Self> {
+        match plan {
+            NodePlan::Leaf { partial, value } => Some(Leaf {
+                key: NibbleSlice::from_plan(partial, data),
+                value: NodeHandle::from_value_plan(value, data),
+            }),
+            _ => None,
+        }
+    }
+
+    /// Encodes the leaf with the substrate codec, returning its plan and the encoded node.
+    pub fn to_plan<H: Hasher>(&self) -> (NodePlan, Vec<u8>) {
+        let encoded = NodeCodec::<H>::leaf_node(
+            self.key.data.iter().copied(),
+            self.key.len(),
+            self.value.to_value(),
+        );
+        plan::<H>(encoded)
+    }
+}
+
+impl NibbledBranch {
+    /// Returns `None` if the plan is not a nibbled branch
+    pub fn from_plan(plan: &NodePlan, data: &[u8]) -> Option<Self> {
+        match plan {
+            NodePlan::NibbledBranch { partial, value, children } => Some(NibbledBranch {
+                key: NibbleSlice::from_plan(partial, data),
+                value: value.as_ref().map(|value| NodeHandle::from_value_plan(value, data)).into(),
+                children: children.clone().map(|child| {
+                    child.map(|child| NodeHandle::from_handle_plan(&child, data)).into()
+                }),
+            }),
+            _ => None,
+        }
+    }
+
+    /// Encodes the branch with the substrate codec, returning its plan and the encoded node.
+    pub fn to_plan<H: Hasher>(&self) -> (NodePlan, Vec<u8>) {
+        let children =
+            self.children.iter().map(|child| child.as_option().map(NodeHandle::to_child::<H>));
+        let encoded = NodeCodec::<H>::branch_node_nibbled(
+            self.key.data.iter().copied(),
+            self.key.len(),
+            children,
+            self.value.as_option().map(NodeHandle::to_value),
+        );
+        plan::<H>(encoded)
+    }
+}
+
+fn plan<H: Hasher>(encoded: Vec<u8>) -> (NodePlan, Vec<u8>) {
+    let plan = NodeCodec::<H>::decode_plan(&encoded).expect("encoded by the same codec; qed");
+    (plan, encoded)
+}
+
+/// Length of a node header holding `nibble_size` after a prefix of `prefix_bits`, mirroring
+/// `SubstrateTrieDB.decodeSize`
+fn header_len(nibble_size: usize, prefix_bits: u32) -> usize {
+    let max = 255usize >> prefix_bits;
+    if nibble_size < max {
+        1
+    } else {
+        2 + (nibble_size - max) / 255
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sp_core::KeccakHasher;
+    use sp_trie::{LayoutV1, MemoryDB};
+    use trie_db::{HashDB, TrieDBMutBuilder, TrieMut, EMPTY_PREFIX};
+
+    #[test]
+    fn test_plan_round_trip() {
+        // values over the inline threshold of `LayoutV1` are hashed, covering every node kind
+        let mut db = MemoryDB::<KeccakHasher>::default();
+        let mut root = Default::default();
+        {
+            let mut trie =
+                TrieDBMutBuilder::<LayoutV1<KeccakHasher>>::new(&mut db, &mut root).build();
+            for i in 0u32..64 {
+                let key = i.to_le_bytes().repeat(i as usize % 3 + 1);
+                trie.insert(&key, &vec![i as u8; 1 + (i as usize % 2) * 40]).unwrap();
+            }
+        }
+
+        // hashed values live in the database too, so walk the nodes from the root
+        let mut kinds = vec![];
+        let mut hashes = vec![root];
+        while let Some(hash) = hashes.pop() {
+            let node = db.get(&hash, EMPTY_PREFIX).unwrap();
+            let plan = NodeCodec::<KeccakHasher>::decode_plan(&node).unwrap();
+            let kind = NodeKind::from_plan(&plan, &node);
+            kinds.push((kind.is_leaf, kind.is_hashed_leaf, kind.is_nibbled_branch));
+
+            if let Some(leaf) = Leaf::from_plan(&plan, &node) {
+                assert_eq!(leaf.to_plan::<KeccakHasher>(), (plan, node));
+            } else if let Some(branch) = NibbledBranch::from_plan(&plan, &node) {
+                for child in branch.children.iter().filter_map(NodeHandleOption::as_option) {
+                    if child.is_hash {
+                        hashes.push(child.hash.into());
+                    }
+                }
+                assert_eq!(branch.to_plan::<KeccakHasher>(), (plan, node));
+            }
+        }
+
+        assert!(kinds.iter().any(|(leaf, _, _)| *leaf));
+        assert!(kinds.iter().any(|(_, hashed_leaf, _)| *hashed_leaf));
+        assert!(kinds.iter().any(|(_, _, branch)| *branch));
+    }
+
+    #[test]
+    fn test_header_len() {
+        assert_eq!(header_len(0, 2), 1);
+        assert_eq!(header_len(62, 2), 1);
+        assert_eq!(header_len(63, 2), 2);
+        assert_eq!(header_len(63 + 254, 2), 2);
+        assert_eq!(header_len(63 + 255, 2), 3);
+    }
+}
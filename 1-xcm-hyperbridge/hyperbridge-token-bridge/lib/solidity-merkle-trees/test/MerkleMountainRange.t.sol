@@ -135,6 +135,18 @@ contract MerkleMountainRangeTest is Test {
         return MerkleMountainRange.siblingIndices(indices);
     }
 
+    function parentIndices(
+        uint256[] memory indices
+    ) public pure returns (uint256[] memory) {
+        return MerkleMountainRange.parentIndices(indices);
+    }
+
+    function subtreeHeights(
+        uint256 leavesLength
+    ) public pure returns (uint256[] memory) {
+        return MerkleMountainRange.subtreeHeights(leavesLength);
+    }
+
     function mmrLeafToNode(
         MmrLeaf[] memory leaves
     ) public pure returns (Node[] memory, uint256[] memory) {
@@ -160,4 +172,19 @@ contract MerkleMountainRangeTest is Test {
     ) public pure returns (bytes32) {
         return MerkleMountainRange.CalculateRoot(proof, leaves, leafCount);
     }
+
+    function CalculateRootWithGas(
+        bytes32[] memory proof,
+        MmrLeaf[] memory leaves,
+        uint256 leafCount
+    ) public view returns (bytes32, uint256) {
+        uint256 startGas = gasleft();
+        bytes32 root = MerkleMountainRange.CalculateRoot(
+            proof,
+            leaves,
+            leafCount
+        );
+        uint256 gasUsed = startGas - gasleft();
+        return (root, gasUsed);
+    }
 }
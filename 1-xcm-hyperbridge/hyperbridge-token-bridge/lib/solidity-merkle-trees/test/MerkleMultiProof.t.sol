@@ -37,6 +37,31 @@ contract MerkleMultiProofTest is Test {
         return root;
     }
 
+    function CalculateRootWithGas(
+        Node[][] memory proof,
+        Node[] memory leaves
+    ) public view returns (bytes32, uint256) {
+        uint256 startGas = gasleft();
+        bytes32 root = MerkleMultiProof.CalculateRoot(proof, leaves);
+        uint256 gasUsed = startGas - gasleft();
+        return (root, gasUsed);
+    }
+
+    function CalculateBalancedRootWithGas(
+        Node[] memory proof,
+        Node[] memory leaves,
+        uint256 numLeaves
+    ) public view returns (bytes32, uint256) {
+        uint256 startGas = gasleft();
+        bytes32 root = MerkleMultiProof.CalculateRootOptimized(
+            proof,
+            leaves,
+            numLeaves
+        );
+        uint256 gasUsed = startGas - gasleft();
+        return (root, gasUsed);
+    }
+
     function testCalculateRoot() public {
         (Node[][] memory proof, Node[] memory leaves) = abi.decode(
             hex"00000000000000000000000000000000000000000000000000000000000000400000000000000000000000000000000000000000000000000000000000004060000000000000000000000000000000000000000000000000000000000000000b0000000000000000000000000000000000000000000000000000000000000160000000000000000000000000000000000000000000000000000000000000254000000000000000000000000000000000000000000000000000000000000039a00000000000000000000000000000000000000000000000000000000000003e400000000000000000000000000000000000000000000000000000000000003f200000000000000000000000000000000000000000000000000000000000003f400000000000000000000000000000000000000000000000000000000000003f600000000000000000000000000000000000000000000000000000000000003f800000000000000000000000000000000000000000000000000000000000003fa00000000000000000000000000000000000000000000000000000000000003fc00000000000000000000000000000000000000000000000000000000000003fe0000000000000000000000000000000000000000000000000000000000000008f000000000000000000000000000000000000000000000000000000000000000fa6cf77153fd32fff32d081db32b6060d103a0ef175a8dea5bca02f49a2ed75460000000000000000000000000000000000000000000000000000000000000018e7f64416927ea600a6e884e87c2f9dafc1c4e0fc8f0d45a6611ef2a293cbc402000000000000000000000000000000000000000000000000000000000000001cab76145bc9703a92dae1d58cfb1756f76918a7f34be82cbeaf30dfeb57804a15000000000000000000000000000000000000000000000000000000000000001f243a18d5297db4858ba1a8919440bb0be5493cc2691e703ba8a9f939ff6dd442000000000000000000000000000000000000000000000000000000000000002532f9d25fb9f364f89a752cfd294832284c9b5f33d8b3136b40d6392f15ee35f30000000000000000000000000000000000000000000000000000000000000027c670cbbb702db45009eb625e7ecbc34f01eb425f4a00f6b025c4e03f0e1d798300000000000000000000000000000000000000000000000000000000000000289428be78e69fd3b44edee810b38d59c09e0f533c0347d5dd30324fff30bca6ff000000000000000000000000000000000000000000000000000000000000002c4861b1dc3855e852be90bb0551adf658f9355e141845fef3e232c6f1b69acf34000000000000000000000000000000000000000000000000000000000000002e5349432161ebbbd73e0c74bc7c84de5ad1a18d61d368bce418ae380a1703a09c00000000000000000000000000000000000000000000000000000000000000313a5682c9ce11158fd871bf2e4cef249b923a5805fc19f632d730f1917e440ce00000000000000000000000000000000000000000000000000000000000000036e0bb94c5f2ea631d98639796a598906f6bc09921369b3ccd325c10495cdb6f7d00000000000000000000000000000000000000000000000000000000000000382dfd04798e22e2bb8bc13ee429647cd1da9bd9fb8d821dce45400008712cd132000000000000000000000000000000000000000000000000000000000000003cd378233391a07938a0481abbf55dd7320316e08528ae4e38f9562889f519faa4000000000000000000000000000000000000000000000000000000000000003f9acfc6fcc805c9616cbffe0e62d0dd5db65b05ed3e9193de7a8e5341f08bec490000000000000000000000000000000000000000000000000000000000000045eeadd3e3d96d716d66df7b1f7dcfeaec1b785c0999b0c124b69387a3fce15862000000000000000000000000000000000000000000000000000000000000004790a0d56690e6f3c84dceebe10df122ecf7f789dd2fcbb3a1ca27a8511d3567d500000000000000000000000000000000000000000000000000000000000000481e86feb717b5e1c0bc2562ceb32ea0c2d65360858f763be10cdaaa6d608dc0f4000000000000000000000000000000000000000000000000000000000000004abe289ab8e5f9eb14e989a569fb4ccd913de2f5d0f11f1f183616de1e44c39152000000000000000000000000000000000000000000000000000000000000004de51dd48887bcc747dd4e299ca446770ebe9c3cdd472ed972ca801ca778005b69000000000000000000000000000000000000000000000000000000000000004ef623ad8266930b78e540d903e63a621e77e2646d10a94fc1059f7ec059ff5cfc0000000000000000000000000000000000000000000000000000000000000051b26fe49a5d573da64e176742c2191a1ce89a9b671f0314453e0edc058b36ce660000000000000000000000000000000000000000000000000000000000000052bdd1485bc600e926630a4257c7d601c070fc164fb92f818b9fb47b52d8494a620000000000000000000000000000000000000000000000000000000000000055f352cc1ff3dd678fefa5263f162f56a870f8eaa48efae2a0bce4500478b3d6110000000000000000000000000000000000000000000000000000000000000056a3356cc1b480b74267d7e87aad97008faee83bfa706841e2d14c9d33cc96daec000000000000000000000000000000000000000000000000000000000000005a99a1b4204aac314240774afbc69bf980b827753b00026b03364eedd15208ba380000000000000000000000000000000000000000000000000000000000000061cb20cfd2c61707e3ccb7eab16bec9caa1abaa9b36d22a6313ab7c0a9016fcec00000000000000000000000000000000000000000000000000000000000000064054a7fac6a03a15b9157f4f67d788a79cadc8bea07789d32e772631f052568900000000000000000000000000000000000000000000000000000000000000068613197016c13038f3b33a4dd6b2fa1c1f91fd97e2e40dae34d3dff984e943252000000000000000000000000000000000000000000000000000000000000006c02c5918de0ed462d366bb774c20e8c3d5ee4720a86d7b4ece5ddf9ee1ff956060000000000000000000000000000000000000000000000000000000000000077f124cd7a0445b774bc8aa7c757ec2b2772c40ab174ff72ff2806ce6b04639422000000000000000000000000000000000000000000000000000000000000007891007afefa7a64eecf8eac49fabeb0c5654533e896350c4549bb1d8533f29770000000000000000000000000000000000000000000000000000000000000008115a32a956711386c90b351fd9ddc3d3f322e4358a09852433c75c567d6246f600000000000000000000000000000000000000000000000000000000000000082761e90c67623b0db04b13434b758bc1af4411600dcad41baf27c1c63db1bfa28000000000000000000000000000000000000000000000000000000000000008ac8161fd97e113a8f040323982ebccbb4de490c4bdbc5e1021b95624948f86a650000000000000000000000000000000000000000000000000000000000000091e757f8fe469e673eb3355a555d3e4e574edbbbca01d8405bf42e75d84c32bf3600000000000000000000000000000000000000000000000000000000000000960a4293c69eb8ab3dcb377afd94f344f6d175508b0f6ad889e838a7ae9b445e77000000000000000000000000000000000000000000000000000000000000009fd0501cc046a2822eb4290ddae3924e387233b3f853074394aa89af231fa09aec00000000000000000000000000000000000000000000000000000000000000a604e5f7967f8632cbe961311eb9b6b2bc8f1607a3df3e21cad01b280545daaa2d00000000000000000000000000000000000000000000000000000000000000aaa1b2fa4bfb5b21fdf127ae1dd656d402bb86bd4e64300503ddf397b54d6a2c2900000000000000000000000000000000000000000000000000000000000000b0762769e2f55973315c56cfb3c6e9a5dac648306b501d4e5062d97e24dc85422c00000000000000000000000000000000000000000000000000000000000000b7aa64539dd2ff5643b42dcdac3d8d18017fb25380855799d5e8d5a4754cdca47b00000000000000000000000000000000000000000000000000000000000000b97c7ec8a3a02db6e648a2f41705b13307aceccfba50ebc9dc6c238579cf433f5300000000000000000000000000000000000000000000000000000000000000bf9d096d104d2342ae160a034c4d2c2dd73742a72b815200748770ebcd79ef29ae00000000000000000000000000000000000000000000000000000000000000c5fcd9cbfee588fb427b785d46b3da73a46e1bb6d93e035868461df1c52b45e98400000000000000000000000000000000000000000000000000000000000000c721d691058bb1bf7196f60c53aa9c4d549a2e1d242fe3f0790058bc893108d91500000000000000000000000000000000000000000000000000000000000000d233bb429ef2714910c34b3261c1cc9bda5e1bc57092073dd530d025bb8fe6a27000000000000000000000000000000000000000000000000000000000000000d5221c86affd5bbf6e6e6951ea0393de86827d5adc46d557c4fd77f13b133dfc6500000000000000000000000000000000000000000000000000000000000000d7a3f373a244c5ab746e75bb91947ea572a6d42e74254ba5d9c4eeb04b1ce1ec9b00000000000000000000000000000000000000000000000000000000000000ddc1143e9122cb6a619fdf95b1eb1bf6bcff0e97b6b00e315b7a4cc74bc147579f00000000000000000000000000000000000000000000000000000000000000def9177b2eccf65d2d75d73d78bd456af6c95340f3967e1d3fca4080aaffcdd62300000000000000000000000000000000000000000000000000000000000000e1bad0047f86ca96e3527511157d26ecd2163a3e1ada392d839c7fc66fd3db460800000000000000000000000000000000000000000000000000000000000000e23f7b0cad0ed43d2992b64febbc8770d5020d21113309b7559ed430e5c653937f00000000000000000000000000000000000000000000000000000000000000e977e6fab14c7c5d82f211a1172c926ec6608077478e7d530e1f3a96b0bb97d26900000000000000000000000000000000000000000000000000000000000000f0ffa25a5be946ded8af9ec990023f2bb42b34692b5dc2b98b6bba9056e9c758b000000000000000000000000000000000000000000000000000000000000000fceed3a7495bc711e1c5f5f41573a80ec176d56ce1cf4de883b2f0a1514bdb705900000000000000000000000000000000000000000000000000000000000000ff786f5065b582475bd26801bb63bba3900c393dc54c0994c46ee1393d5ef7dda200000000000000000000000000000000000000000000000000000000000001045f158e277af442d66578dfdba523c45efc8d896b0a733d107ed85fe20cc05a78000000000000000000000000000000000000000000000000000000000000010a5b6245b9733bc95952014668687782df04f049765eb861855e9ebef4e30ba87d000000000000000000000000000000000000000000000000000000000000010da0645045933d3ab1470f39c9938207fea09d5624eacb02b0a75930a6393a6513000000000000000000000000000000000000000000000000000000000000010e861fd80f489e5b27915f0d2ece57e1d84147c031210ebb0d41cf124b570ac7ec00000000000000000000000000000000000000000000000000000000000001166f2ea5cf4d1dcc32815bf1b8e4dd15825940345c6757ab7ea724b28ab9555a9a0000000000000000000000000000000000000000000000000000000000000118f86de61a52007b2bbee384b11cd41df9ca1cde5bdadceefadffba7e0e50fda72000000000000000000000000000000000000000000000000000000000000011de5ddcf14d68119553f5cc88a4f75a336408a517f685e0cdc4036214e6df3d5d7000000000000000000000000000000000000000000000000000000000000011e16c10b96c4f75f2459638bb2fc51014926fd0857b9dd40ea1da73795ef1186f500000000000000000000000000000000000000000000000000000000000001213c59ba2daff1e629793d90be181a3ac6532110f2709b51aa34ca592a8dd63cbb0000000000000000000000000000000000000000000000000000000000000126c718d7211d268b1e5c5ff12f184d9506fb3298d27ff1fcd7d7e914a3ba5041a7000000000000000000000000000000000000000000000000000000000000012c9ab00b8427222528a28d18523e55456dac99dfa5652f4b360d4f7933716d29c3000000000000000000000000000000000000000000000000000000000000013065b337c5897bc5b54b6a31812f0ed2c6c5fd499f26c43fb01b21b31eea834fc6000000000000000000000000000000000000000000000000000000000000013533017b953467ef3cc145074eaf356a1f42ffe2b83530a09fa4c85171fa17cd6c0000000000000000000000000000000000000000000000000000000000000137891a76a1197186820d83ee2d7846237d563a3a1cf839b9a3ffcfc385af73fe4a000000000000000000000000000000000000000000000000000000000000013d601770a7c971e490f9675dc8da3f2027224d4d5d564a071a4f9c0c154faa482e000000000000000000000000000000000000000000000000000000000000013f84c575cb18f66d697dbcc6d273addbcfb35f8ccab767881b2a164e0dc8c707fe0000000000000000000000000000000000000000000000000000000000000142ca6e29e6f8d2a51dd92aa0467b33e812ddc19e4b99b24e94251b88bb94b0334a000000000000000000000000000000000000000000000000000000000000014428479c1baf58c47a567a5de72891a2528aef80067115f46a47bbe99fed832c1300000000000000000000000000000000000000000000000000000000000001491672dac27d03e80550187e6a048e599e1503a5294b2e9ab4f8d27230a8d03db5000000000000000000000000000000000000000000000000000000000000014acb4abf3e63888a7576d751f110216166bbbb86be2d3dcd2544e080b21d8cfa8a000000000000000000000000000000000000000000000000000000000000014e4645d37d907af31b8dd5ba38d230c6b82544cd116cc5c962f4ac5a9a998e6fad0000000000000000000000000000000000000000000000000000000000000152321e7268dca65d66d8282765d896d8015e23af4b946f22ec6ec28c2df27aa79a00000000000000000000000000000000000000000000000000000000000001551001888661f1ab6f3e38940cf6ef5dede9506ca60a7bc45cc6505ed37ed2f429000000000000000000000000000000000000000000000000000000000000015d81beeda8b306328bdbca906a032f5f6736b9fe8d40250dc37798ce65292d853500000000000000000000000000000000000000000000000000000000000001606e6aa46bc0ddc8f184580ade94fe6c8f8b61cb12146c241e35936720692c46e900000000000000000000000000000000000000000000000000000000000001659863304a82f31828782f889ee2929e824f503ecf8ce73c53a70414338521e5ae00000000000000000000000000000000000000000000000000000000000001696f51f778e59675e3c2a9de29519c6b2506192c15b2088002731e35c1d0a16cfd000000000000000000000000000000000000000000000000000000000000016e8a2463983bec399d850bb9a3b924653bf88278b0369639e51ad1581b1c984f1f000000000000000000000000000000000000000000000000000000000000017b3c99abf51be06e40a2b93e76e756e3588da5816a083f3136d9805f8ff2629ec2000000000000000000000000000000000000000000000000000000000000017f882363cdbfcf4f2f15d5c5990a1ce598f9c2bb80e87263671d4f6cdc80fbaaf600000000000000000000000000000000000000000000000000000000000001839e390ccdcab915090274773779e3321d6c3519ca9368d83fbca94916141f383a0000000000000000000000000000000000000000000000000000000000000185402af2a58f16c84eaff3c5e86b176b4d2cef1e49dd5ba66d89dfe5f2741725a4000000000000000000000000000000000000000000000000000000000000018dc521615040d7e67b7a12905cb368b3f358d0a9f5b13fda2c07018c75467e687a00000000000000000000000000000000000000000000000000000000000001911a87351bddb2cb64f001fa075913f2eb72ea76d57c2f41368f15ba3fe36ea994000000000000000000000000000000000000000000000000000000000000019367e6b6d524ce4256560faec16cddea44e38be368ed23edb5d168a54b9e006de20000000000000000000000000000000000000000000000000000000000000194ed031c7a582cd1fce6ea7785379e7a275b381fd77a2642c81af200cbd96d68a2000000000000000000000000000000000000000000000000000000000000019991260bfcb5999bfdc507826782d841c105b4875795c1db284e6dd6b97261ea5f000000000000000000000000000000000000000000000000000000000000019dcb8d6a771be4041de4e18497ff521677547d6ced71be6e222937aafd7a041b2000000000000000000000000000000000000000000000000000000000000001a30238dc6ed35f786d920e39ad36a3d0916ea12d891bd6dd9dadd507a238ffe82400000000000000000000000000000000000000000000000000000000000001a67833b0222f6bc1b16aa2e7f22d59019d269b39e66ac5b109c7bd0bdaf1c7c04f00000000000000000000000000000000000000000000000000000000000001ad49225a99460fe3324682066e1e9e75d581523fbc958c465dc539e25e237b457c00000000000000000000000000000000000000000000000000000000000001b36ca8a8a90f912fb66a532e1a47f0ba25e3249ab5edd300a96a923957c8f2199400000000000000000000000000000000000000000000000000000000000001b5e89626eabf24134617eb417910b16a2adaac3b31d8002733678f39afc910167f00000000000000000000000000000000000000000000000000000000000001bb6f53ca56882dfa9a55a1e7c1df82c8dbd5602ce2b94840208e360b68270abd4700000000000000000000000000000000000000000000000000000000000001c14ec00af4e2fdd53691f1cd6234678a60f4a6b0b5e77a78dc2b2285742fa2292000000000000000000000000000000000000000000000000000000000000001c3e7531c5e48c4c4aa9bddaec3a1cee85f0800806415fed0f89d81bea91ba4a86500000000000000000000000000000000000000000000000000000000000001c96a5c83aade21a9ea6537b341b9dc1196cd015fbb7b95b1cd19a13049e660f6cb00000000000000000000000000000000000000000000000000000000000001ca5b86473c4ddd25884ebe97a0de3359c5b6a24136c1e94468df1dbe96f3a8fa1d00000000000000000000000000000000000000000000000000000000000001cd2ee7791009af51a8436f5f913e21b705e69620cc172538705d8a7c5ef4209ac900000000000000000000000000000000000000000000000000000000000001d5868f60b178ee24839a83e7a2eab348374929bbcd3c2ce503ffee19905d659b8d00000000000000000000000000000000000000000000000000000000000001d7a33154944c018bc2e2d33f67f4989f880cde82a8ac193c5f2b594b381e32763a00000000000000000000000000000000000000000000000000000000000001d8e81282f6d587666b863b8346216deb5f6c4a1855a8af42747307dbd287bed79300000000000000000000000000000000000000000000000000000000000001da35d630e901555d2e7eca8e1e477e6ca1bb6dc38db3ce2d3ae0cfa26ad8cb908a00000000000000000000000000000000000000000000000000000000000001e2291220904af9a31c9a6bc9a1ff101c790f4617f76ad0bcfdceb20f9a892878ff00000000000000000000000000000000000000000000000000000000000001e51a8578f6f27d0782fc9ddda27c2eff75035d017d43d6046e04b56d11894bbe6300000000000000000000000000000000000000000000000000000000000001e7a8306ed6bd80b10a44edf1d4ea9a1da521a680736bcb010b87b3a7a9d29f7df900000000000000000000000000000000000000000000000000000000000001e8db8cd2aaf3a60c8fd4a672c9a0c0d11c2d867412361fbfdd693c9c289c7a73c400000000000000000000000000000000000000000000000000000000000001ec5721d9ed57540f646fea93aadda263eafeca77b838e64d4bf288ad7da0106bce00000000000000000000000000000000000000000000000000000000000001ef05cab11303d9cac9b54f5a4e0833ec1a8ee47da0469ce7a97c3c9d5dc7b9317800000000000000000000000000000000000000000000000000000000000001f1f314f3163b4b9c79d10b813f8fcb475ebabbdf1f18ae2508cb4f15067181c20900000000000000000000000000000000000000000000000000000000000001f328f1ef5a03655816904f129d635e6fed9b6223c8d89612d63c6548089134cc0b00000000000000000000000000000000000000000000000000000000000001f6f22f5ea73c68142e878da4be41a3a5d2c09507e520bda473e3f5d3acc460f88800000000000000000000000000000000000000000000000000000000000002064cd8f52eb87eaac880d699764caac9a73313ad469a162c4cf77e7760dc385260000000000000000000000000000000000000000000000000000000000000020d6ed90cbba6f5ed33b627db933254167a240a01e79a20b2c108d1aad4b56ba047000000000000000000000000000000000000000000000000000000000000020ff28e494847a92a284ef6b65c121976bd155cdaf1d824c1203599df46584cb38300000000000000000000000000000000000000000000000000000000000002112af99e11237f6cdf1dd85a4099dda4beaa24cd7a9ae0c5b37a5afe00b3f0496800000000000000000000000000000000000000000000000000000000000002183798db8f6403619d66a01730d926a297984bcf4709f46964630d327d70d408c0000000000000000000000000000000000000000000000000000000000000021a4b01754efdc225a85df751b050d60c329389de1db4ac565971ee641b4ab2e7c6000000000000000000000000000000000000000000000000000000000000021c9906832a414dd3dcc85d015109870cfb8ceb616ee0aa048d53db3db7b27443f2000000000000000000000000000000000000000000000000000000000000021e067da803a364ae62339ab7391cc5878a09cea6af216d60dae46df4e6cda62bba00000000000000000000000000000000000000000000000000000000000002207ec148226c718aab927e4f56fbe47d96d82313f12678bd1cafd2af5a8966aaaf0000000000000000000000000000000000000000000000000000000000000222beba7e52fa6e725387e1147510015ecd09febf59b81570432cbead8883f39e5f0000000000000000000000000000000000000000000000000000000000000226a5b76dd9cd894dce4e2b4debf36834da68ac44c0ab6aeac0e3f769748b49590e00000000000000000000000000000000000000000000000000000000000002294eb480652df0b7504bd4e5379b531b2037a2dc8b8bae196b1a2cb91db7ff5ad0000000000000000000000000000000000000000000000000000000000000022a1086b6d339a98eb9ddf9079d75c03250ea5de14d7260bd1a66160fd9b6cf2180000000000000000000000000000000000000000000000000000000000000022dbbed2e8a313418761c61746d29c8b00cb335f6f80ffd0d9b0886e24cb968463600000000000000000000000000000000000000000000000000000000000002328e19476e88c5e394aeb3242a47f56f1c86708fdfa3d8411ce372c8f164546cad0000000000000000000000000000000000000000000000000000000000000235eac296311d3c259608498a5cd15d4bcdf964c77a7deab42c8d6a4ac642065ba00000000000000000000000000000000000000000000000000000000000000236a2261cd6b3b77ea038dc508608f49de75a49500285b292a83c041d71914b87de000000000000000000000000000000000000000000000000000000000000023ae6302c850039f2901f627d498f350f854e7aa581e0ab046da307044daab99b21000000000000000000000000000000000000000000000000000000000000023c26109766b7b6f8041cd2e49c44fd31916ce1ce844fc3c1ed6f65d4f91e0061240000000000000000000000000000000000000000000000000000000000000240a2f824611dbaa770eecab4c3dca67c316e963bdf9878972785056f3c057aa2460000000000000000000000000000000000000000000000000000000000000247ee7af2f804b19091ff314bc53b7fb7604aa7005d1e85954bc10bc0a055c2415c00000000000000000000000000000000000000000000000000000000000002482f28e401dcc84a417be6703e1bb6719bebd7d3c23d2ad9430b45d5becd4f2ec8000000000000000000000000000000000000000000000000000000000000024ddb7f31e20a43253f1a7b13dccd4fa922625bfd030c70f571fbd7aadd95d3cc5a00000000000000000000000000000000000000000000000000000000000002503f71715738bdd9bf22ddd171b68fd3a6de507febd91ea93148bf74046b4f1f7000000000000000000000000000000000000000000000000000000000000002575d4574ac63eb6e2c07f2ebbbc0f9d0073092f4eedb7dacefee90012f8a852f8b00000000000000000000000000000000000000000000000000000000000000510000000000000000000000000000000000000000000000000000000000000006bf503e03d9ab82691be86d1b644d79ff50ef351a6022e70da223f3fcb86a81660000000000000000000000000000000000000000000000000000000000000009fab76dd25b0b5deb915ee13399238df5de41f85d7639e1b730c2631f5a44c855000000000000000000000000000000000000000000000000000000000000000d704c1fe8670c8deb30fea3903afe997262775a8c0c10e400c3fbaf876267a0c10000000000000000000000000000000000000000000000000000000000000015b1fed71de9403833699f77743b9c74b68e6a66d6d88be4bf729bb9d8217dfd6f000000000000000000000000000000000000000000000000000000000000001a5570837a70f95b28c61b81c71c0b63faf7817dd1206a646614e0fc5bda923a69000000000000000000000000000000000000000000000000000000000000001d9bcb39498d530fec875f45f4f4bbc51f6d71a46042e15240a10dc0d804e710f30000000000000000000000000000000000000000000000000000000000000021a4fe2eb287f741c516e2356202f999a60d9b7b3f6341a18144dec1d15e589830000000000000000000000000000000000000000000000000000000000000002c166d12e48341d76b94d20d6ae81aa81d0e812812f8df10069eacdef3ecd39d340000000000000000000000000000000000000000000000000000000000000031410c47ba1258bfc907b5c7415a16f90ae4007e91d41459926ae79c722bc1f5590000000000000000000000000000000000000000000000000000000000000033bc2b9c629319cac602ad7c91971c8ceebe908a4fce681aefd178b9106e783b420000000000000000000000000000000000000000000000000000000000000035cf727b17528b89ce7f906b99a1017cb4de2fefa35ec4f550f6bc9c497aedbfcf0000000000000000000000000000000000000000000000000000000000000037dc603b617563881303f7cc0ab6443bec871b0f45ac65a0d744eab59d48fb64e20000000000000000000000000000000000000000000000000000000000000039bef26d1a667369b3334e1d25f7a174a30d674b0918d0a528b36896e5b8a07bc2000000000000000000000000000000000000000000000000000000000000003afbc1c7b3e5aa0f52c2991648922459b83232a1e800f6170c751c149be5ce9d81000000000000000000000000000000000000000000000000000000000000003d0457ea87ebc02e028089fcc5d6ebc209bd377257db750267e1f180266e8127eb000000000000000000000000000000000000000000000000000000000000003e3244ac09ed4c9af10df65cd5b45078fbd18512f48ed717c64faa0100fca9c8ea00000000000000000000000000000000000000000000000000000000000000449c5248489c24ad9778b6a0e8ae784a21e6c44557b1298a4787810076b5d7b117000000000000000000000000000000000000000000000000000000000000004902dbf53861700c0277b5be942e56edbb7de5f89a7ae7b365176f4db042a45841000000000000000000000000000000000000000000000000000000000000004a8c6a1c913bd941db44dea52e0bb5946de153b2dd992293548768ee9e053929d9000000000000000000000000000000000000000000000000000000000000004e997184b2ce97f545a7fbebb597e956c67006663fb88a38688c64243e7853603d0000000000000000000000000000000000000000000000000000000000000052df5823e0bd34342d2c3e5f9a8d0c44f1bcf5024435ca3118433b85d4438379d200000000000000000000000000000000000000000000000000000000000000541eeaadb194a8053fbd3990569250954757107e789a8c0f8a45de5437ad2aef4d000000000000000000000000000000000000000000000000000000000000005a799dc5e5d906015bc62173de8983014c9db50a68c50745919655f86c649796b9000000000000000000000000000000000000000000000000000000000000005d6f880aaf0f0610f2f19dd3b615be52056dd88cda2b7eb70d1ba8f4b0f1e7ff9a000000000000000000000000000000000000000000000000000000000000005e6a9d432e60585cb61a1c2a5a6d91340e128b6bc67fe0605ca11d4ccce49bb42c000000000000000000000000000000000000000000000000000000000000006068496ace1512b6da30d420751b8f03e92b5ab2752f9b9be3c87b3a0b71b789290000000000000000000000000000000000000000000000000000000000000068fd77b62b186c43ea2bc3f3773264313f0ad9dcbd87c71c3bf4a20fa280b2a1c70000000000000000000000000000000000000000000000000000000000000075dbe604db7471db1a93609021ddfa0de8f5c379cb13dff3cc258e3ba33fecfe6b00000000000000000000000000000000000000000000000000000000000000796f6f7ea46f7aa8d86de87a125feb5d4f33b5450dca040fc71ff51ad697611b6f0000000000000000000000000000000000000000000000000000000000000081a43fd6caa040a36c9289175283e8620b54717eca0298a6dda0b4bc6a7b14d84a0000000000000000000000000000000000000000000000000000000000000083eacd8a0320a21808fc2e77f528b8c240f87e062e5e9131f95b0444ad0450f3ab0000000000000000000000000000000000000000000000000000000000000084ecdb50ae6e9f7414ebe5e5238dd51e6b65f482ff810acee49e3e400076498d2f00000000000000000000000000000000000000000000000000000000000000887d7babb5c6400448c4c7d85eeaaeff4d97be8823afc0e1a1ddc999108f1b2f2a000000000000000000000000000000000000000000000000000000000000008d095eb4f21c3ef836b6372f1a31ceafba0e0dbc4d9c8379080cd3f8beeb2ad6be000000000000000000000000000000000000000000000000000000000000009172ce737396adeecf08f79286f779360053ffd44f4963826034d0a1c3789fffe00000000000000000000000000000000000000000000000000000000000000092beb68b26414c2506418be2d89c4329a6d44b6a3c47e222807794c067bb54a3bc0000000000000000000000000000000000000000000000000000000000000094865e266a2b12e57774558e2b75305f215d06bb252c76c6f4199188d4c7d768c9000000000000000000000000000000000000000000000000000000000000009759a2a87f60e27b7b3ed3c07830312199b8c616bd3b76fed56380a240799b422e000000000000000000000000000000000000000000000000000000000000009986deeada8bbcb70ac54794745afa1488903fd2edacf87e74ff3eeb87882a1900000000000000000000000000000000000000000000000000000000000000009c6c8713beb896ab4072f1357d1c222b12590e9db424a44ec0b8182038406f6bee00000000000000000000000000000000000000000000000000000000000000a04ea50af27cea308b545d83c4ac7d9dfc54f333bed1ae728fa691a788ee5185cf00000000000000000000000000000000000000000000000000000000000000a3b2f596e902bd0a4bce7df849eed2c27e6c5722b5e08224c4ab6c0b4e3ad32a5d00000000000000000000000000000000000000000000000000000000000000a6ba772b3603fb5a303ee8a6372e80e98494d7cac2a450457f9b5589539aa8f3d200000000000000000000000000000000000000000000000000000000000000ac256b0c35e41ee8fbe3e77a80f178018284e55d0f48547d2355d4049de9ec8ced00000000000000000000000000000000000000000000000000000000000000afbf18ea4e8754eb3bd6ceadff9e023fac05fbd118138a0d6ffccae79fc568dcc300000000000000000000000000000000000000000000000000000000000000b18213d2096d8b8e2fb5bd063ba83f948b8645cee29cda69e85eb6c055912e6adf00000000000000000000000000000000000000000000000000000000000000b3f3b4838f7050fe4cec29c64103435473ade32e1a535618a832ab29bd6acc936c00000000000000000000000000000000000000000000000000000000000000b6dba9c7e244af64030182a54e4ecc34da6ffb72373d734f238ec7b98e5036ea7c00000000000000000000000000000000000000000000000000000000000000b90c11ec692cfcfc1ca5cba46bdd6887fae7391af085369d8d0f8c2f9a89e9c49d00000000000000000000000000000000000000000000000000000000000000be96b2ed162ef11ae0f29aaedfbd131123b2bed2cd42775b7726de80a2dbfdd75500000000000000000000000000000000000000000000000000000000000000c0bc8c32bba0b386dc8cb42f8eabff6554bc51c1f98d8bf84943b090f8f534b11d00000000000000000000000000000000000000000000000000000000000000c391efb74649885d2fc8e6c9b96543e95044fa2a9f5baf71d08765ca8a853dbfc500000000000000000000000000000000000000000000000000000000000000c76ed82aa8724aa9f4be97ba857564a1429cb662345a13c61f57989fbcc9e8886200000000000000000000000000000000000000000000000000000000000000cb13ecbf80cea8730392a2b936869200675a39a5b5c3ad856d15392d86d026ec2900000000000000000000000000000000000000000000000000000000000000cd545266819e931fe3b294b0b256f9f12dc92767378bed2737aad3064a89fb626300000000000000000000000000000000000000000000000000000000000000cf364466fb88ef0ed7e7f67b689f6b4b7d93dd0ccf5c8cebbea3ee6ec3bf92341300000000000000000000000000000000000000000000000000000000000000d08c40fab8aa9ea2f52ba42d75d4969b1a5cbd0edf7ad066f98f1964a989497ee200000000000000000000000000000000000000000000000000000000000000d52bf5ebd93eace543f1e6812efccc9cfc0aa51c254f41b22acff31ebfa6aed53c00000000000000000000000000000000000000000000000000000000000000d752ad2c030f78b0f27ea107eb6af05949b2970f11751fe0eb421986e6d3f325d700000000000000000000000000000000000000000000000000000000000000d81881fad6c43a974e80160148d183ad38740ee2503f04c4fd59261348d98bbf3400000000000000000000000000000000000000000000000000000000000000db114df7556015a4b3a796268f6db469d9b26ea388497b7b038683a7fa2bd34dc300000000000000000000000000000000000000000000000000000000000000dc69885e2eca1320c02e379a4449e7fbb298cceda644618eae9c2a9136528cf24200000000000000000000000000000000000000000000000000000000000000dfe1c1220959db61bc22cb9b3bb33bb73f4615daeeac6334e01021add730b37d2400000000000000000000000000000000000000000000000000000000000000e7ed9c17c3e6f455d65c8752ae0fede6eb2b464c1fe99e3506c9d331f944fecf5d00000000000000000000000000000000000000000000000000000000000000e9de27c36897791def5454fff496b2c8d26cf4e2eefcd1cce4969dd41a2252fa7100000000000000000000000000000000000000000000000000000000000000f0148f481f4c1e14420354a3b49f7dcd6c97750bc8c8edac0168ab7fb5dab4088600000000000000000000000000000000000000000000000000000000000000f5a40044ba2525f87666ddf2b19e006649311561ae476e2b8827cae573140b4e8900000000000000000000000000000000000000000000000000000000000000fa75dae2e75bb067de0ec824a224cb6ce3ba02f65a232bb932151c1d8fa198f6cf0000000000000000000000000000000000000000000000000000000000000101b05f9ce488dc086530fb9b049d0f5f2a604dda0f3cdae2a888dbd34ea66ca24b00000000000000000000000000000000000000000000000000000000000001026a4c704ac892d88b073b4c75d51ac271580c123f9d52db286aaa55e2f47e4643000000000000000000000000000000000000000000000000000000000000010929094004f53199ade1f890a80196c412d4067f490e1656c17264c9ba3627e11000000000000000000000000000000000000000000000000000000000000001125298d2518b9de58be2cec06f93fde4f35524af9f7606120e274026fef0cbb4970000000000000000000000000000000000000000000000000000000000000117de4df425afb00c02a7229c193dd7f91a560ff3c1ae417f6ff34efba45d7a068000000000000000000000000000000000000000000000000000000000000001184b28374aedd5ec38290bd72a009de9c562145106a4bd7dcc66632eeaae0c7554000000000000000000000000000000000000000000000000000000000000011c5d15e6dc27f11833f0f22023b58061e3c89c7c586f61aa53f515f29dfe388071000000000000000000000000000000000000000000000000000000000000011f37f4dfc5512d27078fa5e17f2f6a3246fd5ffb4f4365e57c489fa390fd27556e0000000000000000000000000000000000000000000000000000000000000121ef6a61d0da56c3990b25db06d1ddb9d238602f995839f94d7fd8cac5d3d8f07a0000000000000000000000000000000000000000000000000000000000000122930ce4c9b448d8041814b2e7dc069107e51b9c0cefecd03e9de06f6fff6c566500000000000000000000000000000000000000000000000000000000000001251c46cf8cc179708bff2501ac23d9f5cdb12984e3ab24fa7e255ee52ad3071a0b00000000000000000000000000000000000000000000000000000000000001293ec94edab1e96943a34f8bd483ddea005075279774040feb65262ac8e41b5949000000000000000000000000000000000000000000000000000000000000012a6a6d89385a58094e658e3e7c736d8c9870d58e78d7737e3d8ec9da7fb273b5fb000000000000000000000000000000000000000000000000000000000000001200000000000000000000000000000000000000000000000000000000000000027e107e04ce017ac8b545f94c974545072808e1dcb14971ee9171cc539f1717fb0000000000000000000000000000000000000000000000000000000000000005f21770c3aa0c1236a68bef92db8b239e3ef30753de0f819b4cd97958528a7ca1000000000000000000000000000000000000000000000000000000000000000850c73207607d78d2e647e345ee7a495973c9d1e993f866972141ee6a5775e10a00000000000000000000000000000000000000000000000000000000000000177f807363ab9eafa72ce1c362b5e0ff1af06f728564ab6c7462629513804c18ab00000000000000000000000000000000000000000000000000000000000000213edd6a8170b68275b98aa576fbd634e4bff444e22bcbbc7a6d97979c90b0cf0300000000000000000000000000000000000000000000000000000000000000230aedf466c6803c221d0d9bc5c5103b736c2fa373d2256383ee01270246e88c0f0000000000000000000000000000000000000000000000000000000000000028f1971dc74793d186fbcf040accd4c5d781f3632d58bbead2b94c6e14082e7d0f000000000000000000000000000000000000000000000000000000000000002b8818da99c22b7c372069980e10a7c00741c6816a08a2b32b3d154c56cb7394c300000000000000000000000000000000000000000000000000000000000000366e573e51f9ec06edcaa9c9f8aebc587c336d9545ca0ba0f3805e4840a810bfa70000000000000000000000000000000000000000000000000000000000000039e2534672af7fbfea4417175c9e4209858185272f6da3787f842462b5186ab111000000000000000000000000000000000000000000000000000000000000003bef52fe958742731be8880a8bac9961581a50316f2bcadaa03c6f9be8627f5e4f000000000000000000000000000000000000000000000000000000000000003df6400ca9093786dc2acd567df62d1ac92ca46ebbd03092614c1cb701c0e70535000000000000000000000000000000000000000000000000000000000000003ee0f077f9898ca532fc1ef8826a9143173b23717c0b4074333d4c2400f841d427000000000000000000000000000000000000000000000000000000000000005dbdf5ac6e3cb649b77371af4c10b58ef645a151f84d60c0d4a9bca70fb84b0ed3000000000000000000000000000000000000000000000000000000000000006234f18dc26b2cc7aa588432cb82fe72af434e2de8f3c84a2e8f3a80e1ebffc196000000000000000000000000000000000000000000000000000000000000007746218d4b4a89ac10ca5bccc5b3837499e74295caf36c11ce1aabf6faad1f70b90000000000000000000000000000000000000000000000000000000000000082d9115471ead6befc964c323e0ab93abfb7b6073cbcd1daa5826a97431a267190000000000000000000000000000000000000000000000000000000000000008581cd9b2c7dd0a3e8838a1839f0fb392a1e48ca25f3df2446b1a8ea17676ba7fd00000000000000000000000000000000000000000000000000000000000000030000000000000000000000000000000000000000000000000000000000000000d876faba405c7b6ceef2bb4cc628af002087d3ac2a5a6d3604318d2c7f6d1b620000000000000000000000000000000000000000000000000000000000000019f614be9657b86e77ed816dbee8f172026649c8971f978e1d6b15e328bf4bf652000000000000000000000000000000000000000000000000000000000000003f5e0e2894c3e30eb61cbc0d569066cb44a5128fde770b52254cc6890db038816e000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000c7000000000000000000000000000000000000000000000000000000000000000ed1615c0eb0b971ca60d4ee1caf1dd8b108e7ed59cb91e8886f3bbf5029ccc60600000000000000000000000000000000000000000000000000000000000000100d5ca02505efd8cc9d0224293cd3594b59a8f832c1ac6e87167c947d2c834e6d0000000000000000000000000000000000000000000000000000000000000011fb791fb1ee7693b8b90afe659c3e83c148503da9027fafe323393878b7ea30d30000000000000000000000000000000000000000000000000000000000000019ff7378735480ac4a12e20659a62ce5e58a495bcee77db92bbaf3e2b871eede04000000000000000000000000000000000000000000000000000000000000001dc4eed6b05d4950876eb50fedd092988af821ecc9b0904dd8c8e0949ae829ff4a000000000000000000000000000000000000000000000000000000000000001e611e258465e4dc207ae4311c20fd0600814fb80e6cf512206059978c1758db27000000000000000000000000000000000000000000000000000000000000002487ada8eb18010dbf652d1ee60b7289be289f1b8162740c02a53781713ca484170000000000000000000000000000000000000000000000000000000000000026899acd6a2765d74f2f3eb9bfd5b30819d8bcb8492dea506f58c2f24a9022c39c0000000000000000000000000000000000000000000000000000000000000029745e1f6a8cda0d7898b1ea24a2fe5fac98ce4d03483b9bdb3a1cf007cde0661e000000000000000000000000000000000000000000000000000000000000002d569c2cb96f128a4dbb569373713168ff64e0b8d8c16dcad174cbe342b53dbf90000000000000000000000000000000000000000000000000000000000000002f862cd29c79e6562ee9217cb9fe353f54071034ddd8df19673ac44d5e1e16c28900000000000000000000000000000000000000000000000000000000000000308df725d04971111cdba2370efaa1dff594b0c3e36c6d192ab399862b2c43694b000000000000000000000000000000000000000000000000000000000000003265572a85d82bd5a5c34c881fc1a72d9e97de64b6f754cb4e31b2dbda15d52c780000000000000000000000000000000000000000000000000000000000000033126fc2067e0b00b4f3549adb85883805e7e652bb186db9d5a76623333b51e43100000000000000000000000000000000000000000000000000000000000000372df7b28ff6e85bb4c8774c207fe2a62eba86b4061d3abe27f9b559658e085a2a0000000000000000000000000000000000000000000000000000000000000039587cc42ff706460297e79e4a5d68df9782be6ae5381637311ada6276742b0d17000000000000000000000000000000000000000000000000000000000000003d4771994c71fb4c73568aa3a1a32adeb0cd22b8a1a92f3c31c316684110580027000000000000000000000000000000000000000000000000000000000000003ed4a460cf1f05f5774b2839a9841308315daa8c3643c764c0dca5030728e3f28c000000000000000000000000000000000000000000000000000000000000004076e4a236173768484c4a3597370087b0afce1ad4626840c7e561e4b4c655723700000000000000000000000000000000000000000000000000000000000000412091582d8177108615b64eb793410bd76206fb4af0107a3b1fa0ced4e8202dbd000000000000000000000000000000000000000000000000000000000000004485dc6187a411718d9ed8bd871669ef4c173b43729f56adc6f2229331a31a56010000000000000000000000000000000000000000000000000000000000000046d51ed3e002a2390619eaa546eeda5000c17ea67235945f35b52a829a60fce69c00000000000000000000000000000000000000000000000000000000000000491968385640f9d7c9c831d119af51e09ba84e4d7e8000ef922195f1ab4c10ffd6000000000000000000000000000000000000000000000000000000000000004be9e48263c3967bdba1b97038eaaa0831f5033e9092fa2a85cf7483af310f2455000000000000000000000000000000000000000000000000000000000000004cad8d591ba286f3fc90d48f3ff6eff37908a4d86873b6cf7ee1b619f1e34848bc000000000000000000000000000000000000000000000000000000000000004f1e07f4612182b9ac2bf6004cc03576b254f404be8773651f23022b0c49a620df0000000000000000000000000000000000000000000000000000000000000050974bbaf2784f85c278b9ae64200739fa7bab05cbe6c1b7c60141895bbf9da473000000000000000000000000000000000000000000000000000000000000005307c27f112d6a34867eebe50924d3bdcf087be60e237763bf95fdbd4d968624230000000000000000000000000000000000000000000000000000000000000054bda27c9a792187f8999a491797d529dc14096b60c54f0f27a50d3ecfdd10bcfd00000000000000000000000000000000000000000000000000000000000000571dc3ab866ea1526a7e4cfa9175b191b584444c810c4026e033ed5629592dd755000000000000000000000000000000000000000000000000000000000000005b3b4ae10bff83b1d15e7b9a73dae8db21b894c89f28c4ae526abfa22e5981e6850000000000000000000000000000000000000000000000000000000000000060c5a233cd75dd581089dde31b9eb205336b4a5a216afd52bf0f9e5c3a7624b48a000000000000000000000000000000000000000000000000000000000000006598f20404e164d7c4f0c56b834d53d284b92edd03deb139c5fd2f2e9b244874fb0000000000000000000000000000000000000000000000000000000000000069dd8e9a2571e72560b9c02fe50f1197bbd79b684c1dcee983f0bff2dc856f7f1b000000000000000000000000000000000000000000000000000000000000006d92e82d2bd7677c920116218c30bf4069406422f36be8835a23e10bb7541e7c860000000000000000000000000000000000000000000000000000000000000070fb3af51222e7c00d8bf8c962e5d20bb751ab1dfc62a6205aedd6d149df861a820000000000000000000000000000000000000000000000000000000000000071098bde48557948381f2957d230b13cbffd7b34b43a6903481c0d7f08bd7e62040000000000000000000000000000000000000000000000000000000000000076f75dc5d04a96a3bf8d4a2b5ff9004526778aa1793f0a7a039f9228e25c2550200000000000000000000000000000000000000000000000000000000000000079c31ca7923e4707870c6e97933bc0cbea942251c34cddfca91ac48daa742d56dc000000000000000000000000000000000000000000000000000000000000007ee54b6e5cab2622c494251dc33d1703b4c38d5dbacac980c53fadfe324239c9fe000000000000000000000000000000000000000000000000000000000000007fcd701dc38746d236bea40ec8138ce26ca9394ddeef7cc3ac1bb83119103af9640000000000000000000000000000000000000000000000000000000000000080f3218a4ac80360979373f3758a9ef652421e7983862f46cefd77da533b6a5612000000000000000000000000000000000000000000000000000000000000008320cb7c53a4b9e9a3adb2b9987dc0705a8bb7ce6e3d4e225ca7464df980fb1a69000000000000000000000000000000000000000000000000000000000000008b6ec0121792d76c2399326bdbe4b044129ae8131b28bb35c1b2525a2d4e593fae00000000000000000000000000000000000000000000000000000000000000905abde181f4d375098365a95a34c22cdbce71db5a58a8201f8602b4deab023dc40000000000000000000000000000000000000000000000000000000000000097a35fe1d06cbcfb4b69d1dfab543a6cb36515838ced5701f535e20a0c7135c4280000000000000000000000000000000000000000000000000000000000000098ca63d613e8601197278bfda0d41ada189ebcabf74adb5a6b1ce56c3d903ca3c10000000000000000000000000000000000000000000000000000000000000099f4f99cd11fe631b2e2557cdfec821deb23d50f9ae97fdd7e9d1004b497d69a20000000000000000000000000000000000000000000000000000000000000009a00713582c44b492d20183ff14c239f0ebd462794f6b8c0ee6e0af10d69d94235000000000000000000000000000000000000000000000000000000000000009b058d884f23548beeccc6446f81f56c873337452138da5fe59106cb72fd30eec1000000000000000000000000000000000000000000000000000000000000009ed1084dcadb0c5f69880e6ba891d56ff822365724364276afdb18d9ab2cd828b700000000000000000000000000000000000000000000000000000000000000a79c842cb7222bac0635026e014d1e0a2eb38c918480ac435077f71f42668d262800000000000000000000000000000000000000000000000000000000000000abff0c3ea86e1f84ed5138c52de5f2a2075e727ffb15d903ae041d38a8ec014bc100000000000000000000000000000000000000000000000000000000000000b17f3102c98d324c064843f9adadc3f3bd460704953692f64393af4fdf556c18ef00000000000000000000000000000000000000000000000000000000000000b24ea35fb33e614d8bd54d7773012071c22e39e4ee740030db080f77005185789500000000000000000000000000000000000000000000000000000000000000b35c0c90d70b94efa2f1d33440ba6680fa4bb34d759450f1898ed5f84c1189b57500000000000000000000000000000000000000000000000000000000000000b64e9290b7a8a7145fc78d9b6d63c323f2b04e6f88a0621d35abe29006ea76272300000000000000000000000000000000000000000000000000000000000000b8cb394621275558347e45f1ede3c5fa719c7f97e8892bb36731f11043e17939cc00000000000000000000000000000000000000000000000000000000000000be0bfbda104bd40976fd62a86eb83cdd5addbbe0257c02fb5cb19f2e064e62ff5200000000000000000000000000000000000000000000000000000000000000c23f904c4ccce284ec7e463425ea7267ce16bea9090d348803955970db83f3f46500000000000000000000000000000000000000000000000000000000000000c3e9c8cf2040a99fe15017612ea94793f9ec27c501f9319207083666cc0d82f8e000000000000000000000000000000000000000000000000000000000000000c44750c0fd543b797d367f5a5834c280c53574a3921ec7c857cc9a6aaa177a5fa900000000000000000000000000000000000000000000000000000000000000c643eeb59432df4bd72216fa66f7b916c34b4dc71ca9cee5393f6ab0a8d8bceca500000000000000000000000000000000000000000000000000000000000000d3f7c5936dbf1f66036baada13aaf5a8030d405f3b85ba0d07da4e272a4a5dedee00000000000000000000000000000000000000000000000000000000000000d4b7fd51729e35366b64e4ae04e75c6bec1ff3502fca642bf9e31f6f5efdf1d15600000000000000000000000000000000000000000000000000000000000000d63fa2b751acf469a86a4548ef60efba8f99c8a2f35f628101e0f0d169c9c5a87500000000000000000000000000000000000000000000000000000000000000dcf68f21cf85a615c3a5bf2714e10154fd39c69a07b46ce4f5f7e1e8037ede5a0100000000000000000000000000000000000000000000000000000000000000dfe6413089d08c702574dc935e77699d38c3b0674ef027cd1a142342b98a8c4c8100000000000000000000000000000000000000000000000000000000000000e04e09e6547a499bceb15a6519343c149afacfd5bf97d497b47d664a891f62200800000000000000000000000000000000000000000000000000000000000000e3d86a98e3ed44db4ed264e1b09c7750fee18ce649c40342e073b51c536948d97900000000000000000000000000000000000000000000000000000000000000e8dfd701e5ff45bfa37d0b064c4eab4e97c153a51f31164485368d7b442bdf35e900000000000000000000000000000000000000000000000000000000000000f1eec9e8c0498a3b5fac75975e489d4beea019acc0202403fff9abdd018e9bf0c400000000000000000000000000000000000000000000000000000000000000fd98c61909d9380bac087eb8666aeb88d91eb1dda74b0668b6f3b2e4f6c1dd5f7100000000000000000000000000000000000000000000000000000000000000feb70c4278c01424309f3999025da7704d294c76f01dda5d50dee09e6cf5e4bf710000000000000000000000000000000000000000000000000000000000000100a3ef964c92efe4e6012dffaef9ad12c57a3cc1295f9960ff5e2268b986dd800300000000000000000000000000000000000000000000000000000000000001013893a2e0cc1430a8ef9ccec81415ad16ba899897e7ead4ba254a73532e8cbdb700000000000000000000000000000000000000000000000000000000000001055bfbf48ccbe990903cedd2a0f67eedd81386317fb1499530eead7f8e2dda4088000000000000000000000000000000000000000000000000000000000000010b7cdd5c618fe0e9d6061838f93439ef127b55f0fd1c676a5f2f12f4c0cb52888a000000000000000000000000000000000000000000000000000000000000010c411ef39a0e7bef7933465e7dafb77a1d7bf8f8560320be8d65bdcd074b1a559e000000000000000000000000000000000000000000000000000000000000010f1b7518e6556e9e1cf4aed32dac9971f6c67c9d7c0d5f1d7aede6d7c7dd37f13c00000000000000000000000000000000000000000000000000000000000001120b5dde3018e5cfa0dfd54f40b049e0f094bd18fa6b22c22a9e7ff7512b87acde0000000000000000000000000000000000000000000000000000000000000113a0fe3a9d239518f1b7259c439777687214c5afe2076bebcc1f92c60e0b839d9400000000000000000000000000000000000000000000000000000000000001142119be342ac45d78ea5186b88e820baddd4b0ec3ba555499ef3ec0f779d8dbc200000000000000000000000000000000000000000000000000000000000001151bf44912ee7cc0f4d0de7a5f7141dc0587bfaf22108c5eb304d7395b25f460ce0000000000000000000000000000000000000000000000000000000000000117a7d7db56402c73b162fa2d13a1e842cb7d4b57ccda010d4a92dec487a812598b00000000000000000000000000000000000000000000000000000000000001197c54075f3c34892cbd7b0a93f4a26ac9b032c6a4b6c3cad5fadb4b574f1fb4f8000000000000000000000000000000000000000000000000000000000000011cd471f8ceff28af46de55efcf572b499f9d3548b8096d25feceed9884805be37d000000000000000000000000000000000000000000000000000000000000011fdac3fab7877c39eb870d0f7602d4eb410701aa5f80e744f3d0ec71c3c3d82069000000000000000000000000000000000000000000000000000000000000012064db4e9c5cc7e50c810309728fa88fca22d7eaccd69faf32127ae5c7f211176a00000000000000000000000000000000000000000000000000000000000001279f3b470da5b71980004e7d70f567e819522ce9f38316adacfb5b01868827ca54000000000000000000000000000000000000000000000000000000000000012a45023c13708293e2028bb4b1263ba3a0b226a019a9ed082c22fff8e30936ebfd000000000000000000000000000000000000000000000000000000000000012bcfab64ed57a97e5ea61150c2ea4d85356484f4dc28f32aac06b4f578740df796000000000000000000000000000000000000000000000000000000000000012dad07bf0926e89e99ee3f59ba63052b0add0d3c1ddfce11854b057130547d7fa60000000000000000000000000000000000000000000000000000000000000131cb920cf0f949dcd39150772ec2d8c59553e0bc1354f0623bbeb73d3435d43b970000000000000000000000000000000000000000000000000000000000000134c5de2a4b8ad48ce1892249e4f81bc7bf807e8584320d65e6571099c59e86b0cc00000000000000000000000000000000000000000000000000000000000001361fb6c82f4d112a8aa6c895de41d912a23e4b96364569cc64afd6c6b999102bf9000000000000000000000000000000000000000000000000000000000000013a9e1dd9113b9b0fe8d33572c3b60769b1dd689e9762750f7106c00565f5495461000000000000000000000000000000000000000000000000000000000000013b7276c1f2f5f9d3b5b699198da7e89d5ad747b10ce81282353e705373c3c7ac7e000000000000000000000000000000000000000000000000000000000000013cb351e50a356596997672138b941280c6cd6eb8771262483a803c3a43bd896fa5000000000000000000000000000000000000000000000000000000000000013ec17753c1b0a0c5fe39e894f6c7eb7a1f19a0f72dc195a8b8e24d4a052cdcd263000000000000000000000000000000000000000000000000000000000000014359b62765488b9361cb75966a1ef787e358343ad8b27a1974208442546aac781b0000000000000000000000000000000000000000000000000000000000000145eccecc801c7cc310a3c953cac681e8c6740a89cc09c44ef7f0fecb593ba5a7440000000000000000000000000000000000000000000000000000000000000148f7151403017bffa0e6d80f7e8165dbfb9e9aca1bb50687db172afc59517d9073000000000000000000000000000000000000000000000000000000000000014bb622e2e7d27557cbe3df5068865ba602e0e30bbd500fa043ac6b3eabb7355609000000000000000000000000000000000000000000000000000000000000014f3f3288d886472e367ee3cc70b46bff6344a961d072af84541f8088d95dd79280000000000000000000000000000000000000000000000000000000000000015089da63f0b3415d70ed3556adf942815a2eb8a3d5c52cd5c6841a6af350285b870000000000000000000000000000000000000000000000000000000000000151b289532e4d3400106463275d173f7f0cf079456896fc1d77d55ff577197303bc0000000000000000000000000000000000000000000000000000000000000153a26fc2625a0412c6851e3beb5137ed5dd91de7a2f801a4bdc0d80f61f4a5564c0000000000000000000000000000000000000000000000000000000000000154738bef309e063b0945ea98c3226cb75a282198d18819151b58109b51f4af353e00000000000000000000000000000000000000000000000000000000000001567d385c42c498ba654747cd47951da15382b9f9a32d1f85661aa2756ae01ecedf0000000000000000000000000000000000000000000000000000000000000157fb0e1391a496bb6874a936583d8260cdab64401293500337eeb1e723f6722df0000000000000000000000000000000000000000000000000000000000000015aa908e8d26c34176cfde3c9caa6c613cf373cce423b2ba13194e5ca1f05947c4e000000000000000000000000000000000000000000000000000000000000015b051928e3de52689bc87d9aa7103452e040475ffa51cf475345b032d8d66ca2f7000000000000000000000000000000000000000000000000000000000000015c317f28f4f1a1d3597cc77ddca897c9ba865f9b84914f481a6cd26f8fbc81629f0000000000000000000000000000000000000000000000000000000000000161eef86f1baeea0991a8168a72441b3f0c0258403d7766f412459bcef76d28c1000000000000000000000000000000000000000000000000000000000000000164a5f87bdbd1caece772f8a975b7a96b7efba6092e8f79ed4104d58c83262c693a0000000000000000000000000000000000000000000000000000000000000168be1b769aa7fd2c116e7fe45f590660bfaca094ab1378e70fa59051fd203f948b000000000000000000000000000000000000000000000000000000000000016a06331fb1b779ce63847576b999cd3bd785b5ee97dc44f231d475740b3ec21911000000000000000000000000000000000000000000000000000000000000016b626e1313714c6d1f05af4ca4b98c9dd40eb5acec90bd4a50c4d682c54fa89d43000000000000000000000000000000000000000000000000000000000000016f0cb7e28475237a00ed80656b505507d4b9e47fcc0bafe577472a44b7610194660000000000000000000000000000000000000000000000000000000000000170366346813012bb18ab1f639ad6b506c62d588d69372635977e4be391fca2f4e60000000000000000000000000000000000000000000000000000000000000171875c3d2a26527772ca105255c610c04a4c88a32e1c05f8920a5daa5e11d98a7d0000000000000000000000000000000000000000000000000000000000000178a9c7b8938cb0848bd6d7b3f7f90e7f1976f3f5c9c3a0a5b2bae8c74f3c2701920000000000000000000000000000000000000000000000000000000000000179cbcd87f824593af65114a214ec318293424899aa1818b2f6b210d5865d7b65a6000000000000000000000000000000000000000000000000000000000000017a2bf46d0dec887552caf08e4134cf87bd89934ed5ff0a610f4d4fcdde81cb3b93000000000000000000000000000000000000000000000000000000000000017e345ef7c77264259a98745369537bbec95b12b2b5c238eb987011510a6af86ebf0000000000000000000000000000000000000000000000000000000000000182a946ae2a4ffe31e2063a20baf85ead29fd1dc5f3edf93da7aa5b4476282f061a000000000000000000000000000000000000000000000000000000000000018451480c3e9f23992639c53b978551c94bc3379a02eab81f7f193d6397fe76d612000000000000000000000000000000000000000000000000000000000000018cd6f752d7cea9355cd94d2db4378be2332b8ca817af2c73909008e9f7dc03588b000000000000000000000000000000000000000000000000000000000000019097a298ca5f8f508e30c1d29713b7a1b32e11944279c18605a49f9c1017efed7d000000000000000000000000000000000000000000000000000000000000019248a99d16a8244c2003c05831531e0e44872f1079f3322cd4a041b00ec119354800000000000000000000000000000000000000000000000000000000000001952b01d35a4463994b8ea2b7c13f5f25bb670a5d14e343da2efc90b4ed1084bd520000000000000000000000000000000000000000000000000000000000000198115910807c983d6d163b24139ec384d0602c10e685d8aa9de6dca110941b2e3b000000000000000000000000000000000000000000000000000000000000019ccec5ee41ce8d5877802e1f70bd8a0c30be213b365d810bf77d2cbae86f5f9f3700000000000000000000000000000000000000000000000000000000000001a228b36a38d9e1f22b184d550093127d0408ff13116929e7570b30b7465879f12800000000000000000000000000000000000000000000000000000000000001a4269d0a49f074a9b03889a1e91d95934c5776cc08aa19c554da4d4110bc643bbc00000000000000000000000000000000000000000000000000000000000001a5c3811002a186f702059aa4ee4b4e6bb645a92f189043c0e66dd087819ffcab9400000000000000000000000000000000000000000000000000000000000001a7c84368a0ef3dc08160b93a205d7863bd4621bac10b756a4ecb510479407b3aad00000000000000000000000000000000000000000000000000000000000001a8d643b279dbd7d14b3247c4182876022afb1c1f0069d98d663c5e89a8f7a25a2800000000000000000000000000000000000000000000000000000000000001a9c29c5821544e13592e775bb006907dea7b01820aebaeb20435f9b0d710ffdd7e00000000000000000000000000000000000000000000000000000000000001acf87663a864b2814cf5d93536edf6c0c7e5292f3ef18009ff033b6d2d7b5ec9c800000000000000000000000000000000000000000000000000000000000001b2f97cf05c21ee94fbdac5d794cb68ff84cf2ef24d24beee55e1e1c003147f668e00000000000000000000000000000000000000000000000000000000000001b4f9af3b45f863e0eb0aa09687f9fe1b328bcb596aaff3deb7419fff946fc2ad1600000000000000000000000000000000000000000000000000000000000001ba9264737a0eac89d103bf88962ffe2edce74848aefb6998cc4393f6d998e09f3d00000000000000000000000000000000000000000000000000000000000001bcebdca761a79649cca497c5d92041f3da9383b195f64bf521e6ef3d3b8e9bf6ca00000000000000000000000000000000000000000000000000000000000001bd243218307c724da82b54650a3d38bc7e28fc17df91536350aacb569ecf04289a00000000000000000000000000000000000000000000000000000000000001c07907e766c67c82e6bfbe5694ca7ba855f8aeac738e6d233506c5cf30ba8fd99a00000000000000000000000000000000000000000000000000000000000001c268abd8a0c687a024aa3ef924a92e5fd54a87e7a6f53867c5af36f86b97e432af00000000000000000000000000000000000000000000000000000000000001c44eb2a29e03557e64fb4e32fa8a5df77693fa89d152d9eba3c269da03648f5dea00000000000000000000000000000000000000000000000000000000000001c525f0a1dd87c303b99201a7d96563b301246bbbfe049fddb8185da8e4f3ec88e800000000000000000000000000000000000000000000000000000000000001c6eff5f766a43ff4741c6554d0c9a86b13f4baebde5686fc3413849bba0ce7142c00000000000000000000000000000000000000000000000000000000000001c7b9efd74741a7b8ec46df9c38cda0cb6ecc3e7ca970165db1b52560548441b12300000000000000000000000000000000000000000000000000000000000001c8efcbd2fd42c734ab7511bb4b6e889289fd970e042f7ee36d14aee3d244cbc82100000000000000000000000000000000000000000000000000000000000001cb0a797b40f119cbddc0e0bf9337a3d0366d0d2fb7e64e2727486a5f44a5db0bbd00000000000000000000000000000000000000000000000000000000000001cc49ed55c831d60de971746f5826403ae3e33aa8f4ded26a187a9d6d20ad02f62900000000000000000000000000000000000000000000000000000000000001d05ef629cc870df2b907287b232c2561f68e160a96c805bc62fc4adc82d8ec081100000000000000000000000000000000000000000000000000000000000001d169e0128f5cdc4646e327486316cf17a93030b5fc3c98227f610bf123e470e5fa00000000000000000000000000000000000000000000000000000000000001d4fa9a62de26c29eae5c9ee42a5253b0d870224a3fcb37b5345f20a6232e7c76b200000000000000000000000000000000000000000000000000000000000001d6dfe28fb5f1eab1b4d100a1503b750689391e0706e607ea4eeb7a200319d170ea00000000000000000000000000000000000000000000000000000000000001d99bec648eb07db35dd9f6cf55824bf55feb3ac2d4460430cc2dd454f3585f8fe100000000000000000000000000000000000000000000000000000000000001db2488ba407f68dbbf535c775aa7e4f3ca96712810021b8a0461a2e2ac828e073300000000000000000000000000000000000000000000000000000000000001e34a4d4e13bf2149cb0c1826048a99fc2202bd586f3d9e2a88f4df3d6101bffe7b00000000000000000000000000000000000000000000000000000000000001e477a89b0f35e3c64cb8300d42f09c5481c5467e0845cec316fc666abf2e6bb18c00000000000000000000000000000000000000000000000000000000000001e6b4d70b3152304d5efb0e6f7cff58602fd290a74378099b05689e2ab3f6a9257500000000000000000000000000000000000000000000000000000000000001e9c128263d88a19eaa3197fe2011a9879c3a02646d19095e4d8cbb0e948416e49d00000000000000000000000000000000000000000000000000000000000001ede9677a1942618521192d2613cbdf032a51488d7f150faa3ea9d2949981a8b27f00000000000000000000000000000000000000000000000000000000000001eeee165e5d8380616fc65bd334aad483d708ad6a12db86abf32d55aa4ec0a4e02500000000000000000000000000000000000000000000000000000000000001f03ed0478a9dbee76c5a9a491ae7219d4f210d2782bafd82d7c9aa297fd816da0000000000000000000000000000000000000000000000000000000000000001f28713bab63635b9fd80cb92656bf96e5a45234ff5ff43645e1803dcbee14d4a8c00000000000000000000000000000000000000000000000000000000000001f749ce7853ff7ab6ef41302d8416db387e60bce1f5c6e6d108c052f43c3bd8713b0000000000000000000000000000000000000000000000000000000000000200f5f5ef818008395eb927360cc033e2985574b570b8b98a5ae6798fcdb327b97100000000000000000000000000000000000000000000000000000000000002018dc78cbd7f5ad99d8391f50445a45fec51c9c6c31687bf37ba02e974f2cd63080000000000000000000000000000000000000000000000000000000000000207792cf3a2ad88fb421500990939a044e4e7d4fa6ac18a84f711a9de467c46b05f000000000000000000000000000000000000000000000000000000000000020cd358e6c1cdf0a5a8338099fa22bbd45ec0906ac3473d9da1a4dd7f0c7b46b349000000000000000000000000000000000000000000000000000000000000020e4c01a7ef3866c25b308c8713d06819e2851ab4913bbe2d22944be91a535c7753000000000000000000000000000000000000000000000000000000000000021078814dc61fc23a70be4907445b1c24d909aa5d117e9145c6219ad41456771db000000000000000000000000000000000000000000000000000000000000002190daae609505e24bc695236f5b90b94fbc3b8c4a224c4c9e1ac80c33c88aa6810000000000000000000000000000000000000000000000000000000000000021b48dcc4520d1ad5affb6b144eca1e9baa2d0d8ebaa8341c8d81ec59b60e40ae0d000000000000000000000000000000000000000000000000000000000000021dad2f75ced719fc6caea32933146924e92f7754ef09e4031392aa6dacfa134a34000000000000000000000000000000000000000000000000000000000000021feadc47b0a03b0958170459c3d201f840b0e721d208ec3881cd9525594da8e2840000000000000000000000000000000000000000000000000000000000000221881e015296da57eb833995cae253937f43264a5c6390cb60b43442b923f33e7f0000000000000000000000000000000000000000000000000000000000000223a171064e3e434ae05e8e861f47bd1ce9d30ef24446167344cfa26052aa87643d000000000000000000000000000000000000000000000000000000000000022786f429f689ddb615d8f563ba623ffb7c7b0e1637871179c6dcd5b05762f3e7350000000000000000000000000000000000000000000000000000000000000228f5b403a8a32cbee6d27554a2cb6155a30412bcda50651e8ed7ff48c73563b373000000000000000000000000000000000000000000000000000000000000022b584d0119e0c6c02f64a87b7f6d053a213b02482a4837a46b92087fa2d6e0d5ff000000000000000000000000000000000000000000000000000000000000022cc11f414f72a7650c992d099b24e6a62c99aecad67c9444dba6a83ab93356ebfc0000000000000000000000000000000000000000000000000000000000000233977419c5144551366468e0dfc9fb8323e78c371f9bc4dd28f21dff0de66dfa7700000000000000000000000000000000000000000000000000000000000002344fce7046e78a31ad40a417d1622795b6b1743759fea33f0acf46523ed600a31d0000000000000000000000000000000000000000000000000000000000000237ea4f3531c1ee16b7397e57b8250ee9d67b2ed80bbad5707a53d35d177b443b1c000000000000000000000000000000000000000000000000000000000000023bf6fd29459d2feac2ef2576c30f7c2889b79083abbb99a5db77c1760a16cc0d93000000000000000000000000000000000000000000000000000000000000023d3f65ce17ad06225bb30646fb0776c0e1ec3853299519dbc56e9d9e882b98d23300000000000000000000000000000000000000000000000000000000000002415c51361a169de29fe5b76f9854ac0e526027078772e42402af88e6bf03c4209f00000000000000000000000000000000000000000000000000000000000002465f9394ce4005cfe42a02c28245c2de0b33661e2cbfc0f254b5d41fe547ff63830000000000000000000000000000000000000000000000000000000000000249c553dcfc1b40d2934a73a3e80489ece214ccdf12934a58e40d940b5c1b86d92d000000000000000000000000000000000000000000000000000000000000024c6493f52e5ff1381ec6211031a441538abfee9f759b5366585c59546ab7d8abc4000000000000000000000000000000000000000000000000000000000000024efdf1470863f2907463ad998f305e9e431d6ffd6d3d2da06c0d012342e9b0d3db000000000000000000000000000000000000000000000000000000000000024f3fad0a5170462e696f4adc920ee0ac0a61307a689364052e79a8ecaaaee14fae0000000000000000000000000000000000000000000000000000000000000251759d75347b7728945467fb9081e8ad3bff400cbba8d5a03dd29eebfa5ef532fa0000000000000000000000000000000000000000000000000000000000000256dfbe4e08ecf5a8bed372aff24af9a010d68181bfda2172594063f971e749a517",
@@ -6,10 +6,14 @@ import "forge-std/Test.sol";
 import "../src/MerklePatricia.sol";
 import "../src/trie/substrate/SubstrateTrieDB.sol";
 import "../src/trie/substrate/ScaleCodec.sol";
+import "../src/trie/ethereum/RLPReader.sol";
 import "../src/trie/NibbleSlice.sol";
 import "../src/trie/Bytes.sol";
 
 contract MerklePatriciaTest is Test {
+    using RLPReader for bytes;
+    using RLPReader for RLPReader.RLPItem;
+
     function testSubstrateMerklePatricia() public pure {
         bytes[] memory keys = new bytes[](1);
         // trie key for pallet_timestamp::Now
@@ -105,6 +109,45 @@ contract MerklePatriciaTest is Test {
         return MerklePatricia.VerifyEthereumProof(root, proof, keys);
     }
 
+    function ReadChildProofCheck(
+        bytes32 root,
+        bytes[] memory proof,
+        bytes[] memory keys,
+        bytes memory childInfo
+    ) public pure returns (StorageValue[] memory) {
+        return MerklePatricia.ReadChildProofCheck(root, proof, keys, childInfo);
+    }
+
+    function VerifyKeysWithGas(
+        bytes32 root,
+        bytes[] memory proof,
+        bytes[] memory keys
+    ) public view returns (StorageValue[] memory, uint256) {
+        uint256 startGas = gasleft();
+        StorageValue[] memory values = MerklePatricia.VerifySubstrateProof(
+            root,
+            proof,
+            keys
+        );
+        uint256 gasUsed = startGas - gasleft();
+        return (values, gasUsed);
+    }
+
+    function VerifyEthereumWithGas(
+        bytes32 root,
+        bytes[] memory proof,
+        bytes[] memory keys
+    ) public view returns (StorageValue[] memory, uint256) {
+        uint256 startGas = gasleft();
+        StorageValue[] memory values = MerklePatricia.VerifyEthereumProof(
+            root,
+            proof,
+            keys
+        );
+        uint256 gasUsed = startGas - gasleft();
+        return (values, gasUsed);
+    }
+
     function decodeNodeKind(
         bytes memory node
     ) public pure returns (NodeKind memory) {
@@ -168,4 +211,52 @@ contract MerklePatriciaTest is Test {
     ) public pure returns (uint256) {
         return NibbleSliceOps.commonPrefix(self, other);
     }
+
+    function decodeUintCompact(
+        bytes memory data
+    ) public pure returns (uint256 value, uint256 offset) {
+        ByteSlice memory slice = ByteSlice(data, 0);
+        value = ScaleCodec.decodeUintCompact(slice);
+        offset = slice.offset;
+    }
+
+    function decodeUint256(bytes memory data) public pure returns (uint256) {
+        return ScaleCodec.decodeUint256(data);
+    }
+
+    function encodeUintCompact(
+        uint256 value
+    ) public pure returns (bytes memory) {
+        return ScaleCodec.encodeUintCompact(value);
+    }
+
+    function encodeBytes(
+        bytes memory input
+    ) public pure returns (bytes memory) {
+        return ScaleCodec.encodeBytes(input);
+    }
+
+    // Decodes `encoded` and serializes the items in pre-order, each string as
+    // (0, length, bytes) and each list as (1, item count).
+    function decodeRlp(
+        bytes memory encoded
+    ) public pure returns (bytes memory) {
+        return flattenRlp(encoded.toRlpItem());
+    }
+
+    function flattenRlp(
+        RLPReader.RLPItem memory item
+    ) internal pure returns (bytes memory) {
+        if (!item.isList()) {
+            bytes memory data = item.toBytes();
+            return abi.encodePacked(uint8(0), data.length, data);
+        }
+
+        RLPReader.RLPItem[] memory items = item.toList();
+        bytes memory flattened = abi.encodePacked(uint8(1), items.length);
+        for (uint256 i = 0; i < items.length; i++) {
+            flattened = bytes.concat(flattened, flattenRlp(items[i]));
+        }
+        return flattened;
+    }
 }
@@ -0,0 +1,100 @@
+// SPDX-License-Identifier: UNLICENSED
+pragma solidity ^0.8.20;
+
+import "@openzeppelin/contracts/token/ERC20/ERC20.sol";
+
+address constant ALICE = address(uint160(0xa11ce));
+address constant BOB = address(uint160(0xb0b));
+address constant BRIDGE = address(uint160(0xb41d6e));
+
+/// Reads raw storage words. Declares no state, so it doesn't move the slots of the contracts
+/// that inherit it.
+abstract contract SlotReader {
+    function readSlots(
+        bytes32[] memory slots
+    ) public view returns (bytes32[] memory values) {
+        values = new bytes32[](slots.length);
+        for (uint256 i = 0; i < slots.length; i++) {
+            bytes32 slot = slots[i];
+            bytes32 value;
+            assembly {
+                value := sload(slot)
+            }
+            values[i] = value;
+        }
+    }
+}
+
+/// The storage of `BridgeableToken`. It inherits `HyperFungibleToken`, which adds no state of its
+/// own to the OpenZeppelin `ERC20` it builds on, and keeps its gateway in an immutable, so its
+/// slots are the ones `ERC20` declares:
+///
+/// | slot | variable       |
+/// |------|----------------|
+/// | 0    | `_balances`    |
+/// | 1    | `_allowances`  |
+/// | 2    | `_totalSupply` |
+/// | 3    | `_name`        |
+/// | 4    | `_symbol`      |
+///
+/// Compare them with `forge inspect BridgeableToken storageLayout` in the token bridge when its
+/// dependencies change. `TokenBridge` itself keeps nothing in storage, its gateway and fee token
+/// are immutables.
+///
+/// This doesn't inherit `Test`, whose own state would move every variable to a later slot.
+contract BridgeableTokenStorageTest is ERC20, SlotReader {
+    address private immutable _gateway;
+
+    constructor() ERC20("Hyperbridge Bridgeable Test Token", "BTK") {
+        _gateway = BRIDGE;
+        _mint(ALICE, 1000 ether);
+        _mint(BOB, 250 ether);
+        _approve(ALICE, BRIDGE, type(uint256).max);
+    }
+
+    // needs a test method so that forge can detect it
+    function testBridgeableTokenStorage() public {}
+}
+
+/// Layout rules the token doesn't exercise: structs, dynamic arrays, mappings keyed by `bytes`
+/// and variables packed into one slot.
+contract StorageLayoutTest is SlotReader {
+    struct Transfer {
+        address to;
+        uint64 timeout;
+        bool redeemed;
+        uint256 amount;
+    }
+
+    // slot 0, `to`, `timeout` and `redeemed` share the first slot of each transfer
+    mapping(bytes32 => Transfer) public transfers;
+    // slot 1
+    bytes32[] public commitments;
+    // slot 2
+    mapping(bytes => uint256) public fees;
+    // slot 3, packed
+    uint64 public nonce;
+    bool public paused;
+    address public owner;
+
+    constructor() {
+        transfers[keccak256("first")] = Transfer({
+            to: BOB,
+            timeout: 1700000000,
+            redeemed: true,
+            amount: 42 ether
+        });
+        for (uint256 i = 0; i < 3; i++) {
+            commitments.push(keccak256(abi.encode(i)));
+        }
+        fees["EVM-11155111"] = 0.001 ether;
+        fees["POLKADOT-3367"] = 0.002 ether;
+
+        nonce = 7;
+        paused = true;
+        owner = ALICE;
+    }
+
+    // needs a test method so that forge can detect it
+    function testStorageLayout() public {}
+}